@@ -0,0 +1,36 @@
+use std::env;
+use zero_bounce::ZeroBounceAsync;
+use zero_bounce::utility::ZBResult;
+
+// Run with: cargo run --example async_client --features async
+#[tokio::main]
+async fn main() -> ZBResult<()> {
+    // Load environment variables from .env file
+    dotenv::dotenv().ok();
+
+    // Get API key from environment variable (standard: ZEROBOUNCE_API_KEY; legacy: ZERO_BOUNCE_API_KEY)
+    let api_key = env::var("ZEROBOUNCE_API_KEY")
+        .or_else(|_| env::var("ZERO_BOUNCE_API_KEY"))
+        .expect("ZEROBOUNCE_API_KEY (or ZERO_BOUNCE_API_KEY) must be set in .env file");
+
+    let zb_instance = ZeroBounceAsync::new(&api_key);
+
+    let credits = zb_instance.get_credits().await?;
+    println!("Credits left: {}", credits);
+
+    // Requests can be issued concurrently from the same client
+    let (valid, invalid) = tokio::join!(
+        zb_instance.validate_email("valid@example.com"),
+        zb_instance.validate_email("invalid@example.com"),
+    );
+    println!("Validation result: {:#?}", valid?);
+    println!("Validation result: {:#?}", invalid?);
+
+    let domain_search = zb_instance.domain_search_v2()
+        .domain("example.com")
+        .call()
+        .await?;
+    println!("Domain search: {:#?}", domain_search);
+
+    Ok(())
+}
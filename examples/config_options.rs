@@ -21,6 +21,7 @@ fn main() -> ZBResult<()> {
         .domain("example.com")
         .last_name("Doe")
         .call()?;
+    println!("Response: {:#?}", find_email_response);
     
     println!();
     
@@ -34,6 +35,7 @@ fn main() -> ZBResult<()> {
         .domain("example.com")
         .last_name("Doe")
         .call()?;
+    println!("Response: {:#?}", find_email_response);
     
     println!();
     
@@ -47,6 +49,7 @@ fn main() -> ZBResult<()> {
         .domain("example.com")
         .last_name("Doe")
         .call()?;
+    println!("Response: {:#?}", find_email_response);
     
     println!();
     
@@ -61,6 +64,7 @@ fn main() -> ZBResult<()> {
         .domain("example.com")
         .last_name("Doe")
         .call()?;
+    println!("Response: {:#?}", find_email_response);
     
     println!();
     
@@ -74,6 +78,7 @@ fn main() -> ZBResult<()> {
         .domain("example.com")
         .last_name("Doe")
         .call()?;
+    println!("Response: {:#?}", find_email_response);
     
     
     Ok(())
@@ -1,3 +1,6 @@
+// Demonstrates the deprecated v1 method; see the `_v2` example for the replacement.
+#![allow(deprecated)]
+
 use std::env;
 use zero_bounce::ZeroBounce;
 use zero_bounce::utility::ZBResult;
@@ -1,35 +1,57 @@
 use std::collections::HashMap;
+use std::io::Write;
 
-use serde_json::from_str;
+use crate::utility::error::parse_response;
 
 use crate::ZeroBounce;
-use crate::utility::{ZBResult, ZBError};
+use crate::utility::{ZBError, ZBResult};
 use crate::utility::{ENDPOINT_FILE_SEND, ENDPOINT_FILE_STATUS, ENDPOINT_FILE_RESULT, ENDPOINT_FILE_DELETE};
 use crate::utility::{ENDPOINT_SCORING_DELETE, ENDPOINT_SCORING_STATUS, ENDPOINT_SCORING_RESULT, ENDPOINT_SCORING_SEND};
-use crate::utility::structures::bulk::{ZBBulkResponse, ZBFile, ZBFileFeedback, ZBFileStatus, ZBGetFileOptions};
-use crate::utility::bulk_get_file::should_treat_get_file_body_as_error;
-use crate::utility::format_get_file_error_message;
+use crate::utility::structures::bulk::{ZBBulkResponse, ZBFile, ZBFileDownload, ZBFileFeedback, ZBFileStatus, ZBGetFileOptions};
+use crate::utility::rate_limit::EndpointFamily;
+use crate::transport::{ZBMultipartField, ZBRequest, ZBRequestBody};
+use crate::utility::bulk_get_file::{get_file_query, write_get_file_response};
 
 
 impl ZeroBounce {
 
     fn generic_file_submit(&self, endpoint: &str, zb_file: &ZBFile) -> ZBResult<ZBFileFeedback> {
-        let mut multi_part_form = zb_file.generate_multipart()?
-            .text("api_key", self.api_key.clone());
-
-        if endpoint == ENDPOINT_FILE_SEND {
-            if let Some(v) = zb_file.allow_phase_2 {
-                multi_part_form = multi_part_form.text("allow_phase_2", v.to_string());
+        // The fields are generated again on each attempt, the file content
+        // being consumed when sent
+        let build_fields = || -> ZBResult<Vec<ZBMultipartField>> {
+            let mut fields = zb_file.multipart_fields()?;
+            fields.push(ZBMultipartField::text("api_key", self.api_key.clone()));
+
+            if endpoint == ENDPOINT_FILE_SEND {
+                if let Some(v) = zb_file.allow_phase_2 {
+                    fields.push(ZBMultipartField::text("allow_phase_2", v.to_string()));
+                }
             }
-        }
+            Ok(fields)
+        };
 
         let url = self.url_provider.bulk_url_of(endpoint);
-        let response = self.client.post(url)
-            .multipart(multi_part_form)
-            .send()?;
-
-        let response_ok = response.status().is_success();
-        let response_content = response.text()?;
+        let build_request = || Ok(ZBRequest::post(&url).body(ZBRequestBody::Multipart(build_fields()?)));
+        let send = || {
+            // A streamed file is consumed by the first attempt
+            let response = if zb_file.is_streamed() {
+                self.send_request_once(Some(EndpointFamily::Bulk), build_request()?)?
+            } else {
+                self.send_request(Some(EndpointFamily::Bulk), build_request)?
+            };
+            response.text()
+        };
+
+        // Every row costs a credit, the count is only needed by the guard
+        let response_content = match self.credit_guard {
+            Some(_) if zb_file.is_streamed() => {
+                return Err(ZBError::validation(
+                    "streamed files cannot be submitted with a credit guard, their row count is unknown",
+                ))
+            }
+            Some(_) => self.spend_credits(zb_file.row_count()?, send)?,
+            None => send()?,
+        };
 
         // Debug: Print raw response to examine structure in debug mode
         #[cfg(debug_assertions)]
@@ -37,11 +59,7 @@ impl ZeroBounce {
             eprintln!("Raw API response: {}", response_content);
         }
 
-        if !response_ok {
-            return Err(ZBError::ExplicitError(response_content));
-        }
-
-        let feedback_object = from_str::<ZBFileFeedback>(&response_content)?;
+        let feedback_object = parse_response::<ZBFileFeedback>(&response_content)?;
         Ok(feedback_object)
     }
 
@@ -51,10 +69,10 @@ impl ZeroBounce {
         ]);
 
         let response_content = self.generic_get_request(
-            self.url_provider.bulk_url_of(endpoint), query_args
+            self.url_provider.bulk_url_of(endpoint), query_args, Some(EndpointFamily::Bulk)
         )?;
 
-        let file_status = from_str::<ZBFileStatus>(&response_content)?;
+        let file_status = parse_response::<ZBFileStatus>(&response_content)?;
         Ok(file_status)
     }
 
@@ -65,57 +83,26 @@ impl ZeroBounce {
         options: Option<&ZBGetFileOptions>,
         is_scoring: bool,
     ) -> ZBResult<ZBBulkResponse> {
-        let url = self.url_provider.bulk_url_of(endpoint);
-        let mut query: Vec<(&str, String)> = vec![
-            ("api_key", self.api_key.clone()),
-            ("file_id", file_id.to_string()),
-        ];
-        if let Some(opts) = options {
-            if let Some(dt) = &opts.download_type {
-                query.push(("download_type", dt.clone()));
-            }
-            if !is_scoring {
-                if let Some(ad) = opts.activity_data {
-                    query.push(("activity_data", if ad { "true".into() } else { "false".into() }));
-                }
-            }
-        }
-
-        let response = self.client.get(&url).query(&query).send()?;
-
-        let status = response.status();
-        let content_type = response
-            .headers()
-            .get(reqwest::header::CONTENT_TYPE)
-            .ok_or_else(|| ZBError::explicit("content type not specified in response"))?
-            .to_str()
-            .map_err(|e| ZBError::ExplicitError(e.to_string()))?
-            .to_string();
-
-        let body_bytes = response.bytes()?;
-        let body_str = String::from_utf8_lossy(&body_bytes).into_owned();
-
-        #[cfg(debug_assertions)]
-        if !status.is_success() || should_treat_get_file_body_as_error(&body_str, &content_type) {
-            eprintln!("Raw API response: {}", body_str);
-        }
+        let mut content = Vec::new();
+        self.generic_result_download(endpoint, file_id, options, is_scoring, &mut content)?;
+        Ok(ZBBulkResponse::Content(content.into()))
+    }
 
-        if !status.is_success() {
-            let msg = if body_str.trim_start().starts_with('{') {
-                format_get_file_error_message(body_str.trim())
-            } else if body_str.is_empty() {
-                format!("HTTP {}", status.as_u16())
-            } else {
-                body_str
-            };
-            return Err(ZBError::ExplicitError(msg));
-        }
+    fn generic_result_download<W: Write + ?Sized>(
+        &self,
+        endpoint: &str,
+        file_id: &str,
+        options: Option<&ZBGetFileOptions>,
+        is_scoring: bool,
+        writer: &mut W,
+    ) -> ZBResult<ZBFileDownload> {
+        let url = self.url_provider.bulk_url_of(endpoint);
+        let query = get_file_query(&self.api_key, file_id, options, is_scoring);
 
-        if should_treat_get_file_body_as_error(&body_str, &content_type) {
-            return Err(ZBError::ExplicitError(format_get_file_error_message(body_str.trim())));
-        }
+        let response = self.send_request(Some(EndpointFamily::Bulk), || Ok(ZBRequest::get(&url).query(query.clone())))?;
 
-        Ok(ZBBulkResponse::Content(body_bytes))
+        let content_type = response.headers.get(reqwest::header::CONTENT_TYPE).cloned();
+        write_get_file_response(content_type.as_ref(), response.body, writer)
     }
 
     fn generic_result_delete(&self, endpoint: &str, file_id: &str) -> ZBResult<ZBFileFeedback>{
@@ -124,10 +111,10 @@ impl ZeroBounce {
         ]);
 
         let response_content = self.generic_get_request(
-            self.url_provider.bulk_url_of(endpoint), query_args
+            self.url_provider.bulk_url_of(endpoint), query_args, Some(EndpointFamily::Bulk)
         )?;
 
-        let file_status = from_str::<ZBFileFeedback>(&response_content)?;
+        let file_status = parse_response::<ZBFileFeedback>(&response_content)?;
         Ok(file_status)
     }
 
@@ -152,6 +139,19 @@ impl ZeroBounce {
         self.generic_result_fetch(ENDPOINT_FILE_RESULT, file_id, Some(options), false)
     }
 
+    /// Bulk validation getfile streamed into `writer` instead of being held
+    /// in memory; returns the number of bytes written and the content type.
+    /// A failure while reading the body is not retried, `writer` may then
+    /// hold a partial file.
+    pub fn bulk_validation_result_download_to<W: Write + ?Sized>(
+        &self,
+        file_id: &str,
+        writer: &mut W,
+        options: Option<&ZBGetFileOptions>,
+    ) -> ZBResult<ZBFileDownload> {
+        self.generic_result_download(ENDPOINT_FILE_RESULT, file_id, options, false, writer)
+    }
+
     pub fn bulk_validation_result_delete(&self, file_id: &str) -> ZBResult<ZBFileFeedback> {
         self.generic_result_delete(ENDPOINT_FILE_DELETE, file_id)
     }
@@ -177,6 +177,17 @@ impl ZeroBounce {
         self.generic_result_fetch(ENDPOINT_SCORING_RESULT, file_id, Some(options), true)
     }
 
+    /// AI scoring getfile streamed into `writer` (see
+    /// `bulk_validation_result_download_to`).
+    pub fn ai_scoring_result_download_to<W: Write + ?Sized>(
+        &self,
+        file_id: &str,
+        writer: &mut W,
+        options: Option<&ZBGetFileOptions>,
+    ) -> ZBResult<ZBFileDownload> {
+        self.generic_result_download(ENDPOINT_SCORING_RESULT, file_id, options, true, writer)
+    }
+
     pub fn ai_scoring_result_delete(&self, file_id: &str) -> ZBResult<ZBFileFeedback> {
         self.generic_result_delete(ENDPOINT_SCORING_DELETE, file_id)
     }
@@ -0,0 +1,416 @@
+//! Submit a bulk file, wait for it to be processed and download the results.
+
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+
+use crate::ZeroBounce;
+use crate::utility::{ZBError, ZBResult};
+use crate::utility::structures::bulk::{ZBBulkResponse, ZBFile, ZBFileFeedback, ZBFileStatus, ZBGetFileOptions};
+use crate::utility::structures::bulk_split::{merge_result_files, ZBSplitLimits};
+use crate::utility::structures::validate_enums::ZBFileProcessingStatus;
+
+/// Shortest delay between two status checks of a `BulkJob`.
+pub const MIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Bulk API a job is sent to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BulkJobKind {
+    Validation,
+    AiScoring,
+}
+
+/// State of a submitted file, derived from its `ZBFileStatus`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BulkJobState {
+    /// Still queued or being processed.
+    Pending,
+    /// Results are ready to be downloaded.
+    Complete,
+    /// The file was deleted before its results were downloaded.
+    Deleted,
+    /// Processing failed; holds the `error_reason` (or the status when the
+    /// API did not send a reason).
+    Failed(String),
+}
+
+impl BulkJobState {
+    /// State of a file status. Only a `Complete` status means the results
+    /// are ready, whatever `complete_percentage` says. Statuses unknown to
+    /// `ZBFileProcessingStatus` count as failed when the API sent an
+    /// `error_reason`, and as pending otherwise.
+    pub fn of(status: &ZBFileStatus) -> BulkJobState {
+        let Ok(file_status) = status.file_status.parse::<ZBFileProcessingStatus>();
+        let error_reason = status.error_reason.clone().filter(|reason| !reason.is_empty());
+
+        match file_status {
+            ZBFileProcessingStatus::Complete => BulkJobState::Complete,
+            ZBFileProcessingStatus::Deleted => BulkJobState::Deleted,
+            ZBFileProcessingStatus::Failed => {
+                BulkJobState::Failed(error_reason.unwrap_or_else(|| status.file_status.clone()))
+            }
+            ZBFileProcessingStatus::UnknownValue(_) if error_reason.is_some() => {
+                BulkJobState::Failed(error_reason.unwrap_or_default())
+            }
+            ZBFileProcessingStatus::Uploading
+            | ZBFileProcessingStatus::Queued
+            | ZBFileProcessingStatus::Processing
+            | ZBFileProcessingStatus::UnknownValue(_) => BulkJobState::Pending,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        *self != BulkJobState::Pending
+    }
+}
+
+/// Final result of a `BulkJob`.
+#[derive(Debug)]
+pub enum BulkJobOutcome {
+    /// The file was processed and its results downloaded. `deleted` tells
+    /// whether the file was then removed from the account.
+    Complete {
+        file_id: String,
+        status: ZBFileStatus,
+        content: Bytes,
+        deleted: bool,
+    },
+    Deleted {
+        file_id: String,
+        status: ZBFileStatus,
+    },
+    Failed {
+        file_id: String,
+        status: ZBFileStatus,
+        error_reason: String,
+    },
+}
+
+impl BulkJobOutcome {
+    pub fn file_id(&self) -> &str {
+        match self {
+            BulkJobOutcome::Complete { file_id, .. }
+            | BulkJobOutcome::Deleted { file_id, .. }
+            | BulkJobOutcome::Failed { file_id, .. } => file_id,
+        }
+    }
+
+    /// Downloaded results, for a completed job.
+    pub fn content(&self) -> Option<&Bytes> {
+        match self {
+            BulkJobOutcome::Complete { content, .. } => Some(content),
+            _ => None,
+        }
+    }
+}
+
+/// Final result of a `BulkJob` run on a file split into chunks.
+#[derive(Debug)]
+pub struct BulkSplitOutcome {
+    /// Outcome of each chunk, in the order of the file; an error when the
+    /// chunk could not be submitted, waited for or downloaded.
+    pub chunks: Vec<ZBResult<BulkJobOutcome>>,
+    /// Results of all the chunks merged into one file, in the order of the
+    /// rows of the original file (see `ZBFile::join_results`); `None` unless
+    /// every chunk completed.
+    pub content: Option<Bytes>,
+}
+
+impl BulkSplitOutcome {
+    /// Ids of the files of the submitted chunks, in order.
+    pub fn file_ids(&self) -> Vec<&str> {
+        self.chunks.iter().flatten().map(BulkJobOutcome::file_id).collect()
+    }
+
+    /// Whether every chunk was processed and its results downloaded.
+    pub fn is_complete(&self) -> bool {
+        self.content.is_some()
+    }
+}
+
+/// Runs a bulk validation or AI scoring file from submission to download,
+/// created with `ZeroBounce::bulk_job`.
+///
+/// The file status is polled every `poll_interval`, the interval doubling
+/// after each check up to `max_poll_interval`. When `timeout` elapses before
+/// the file reaches a terminal state, `ZBError::BulkJobTimeout` is returned
+/// with the file id, so that the job can be picked up later with `resume`.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use zero_bounce::{ZeroBounce, ZBFile, BulkJobKind, BulkJobOutcome};
+/// use zero_bounce::utility::ZBResult;
+///
+/// # fn main() -> ZBResult<()> {
+/// let zb = ZeroBounce::new("your_api_key");
+/// let zb_file = ZBFile::from_path("emails.csv".to_string());
+///
+/// let outcome = zb.bulk_job(BulkJobKind::Validation)
+///     .poll_interval(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(3600))
+///     .delete_after_download(true)
+///     .run(&zb_file)?;
+///
+/// if let BulkJobOutcome::Complete { content, .. } = outcome {
+///     std::fs::write("results.csv", content)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct BulkJob<'a> {
+    client: &'a ZeroBounce,
+    kind: BulkJobKind,
+    poll_interval: Duration,
+    max_poll_interval: Duration,
+    timeout: Option<Duration>,
+    get_file_options: Option<ZBGetFileOptions>,
+    delete_after_download: bool,
+}
+
+impl ZeroBounce {
+    /// Start configuring a `BulkJob` for the given bulk API.
+    pub fn bulk_job(&self, kind: BulkJobKind) -> BulkJob<'_> {
+        BulkJob {
+            client: self,
+            kind,
+            poll_interval: Duration::from_secs(2),
+            max_poll_interval: Duration::from_secs(30),
+            timeout: None,
+            get_file_options: None,
+            delete_after_download: false,
+        }
+    }
+}
+
+impl<'a> BulkJob<'a> {
+    /// Delay before the first status check (default: 2 seconds). Delays
+    /// below `MIN_POLL_INTERVAL` are raised to it, so a zero interval cannot
+    /// turn the wait into a busy loop.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval.max(MIN_POLL_INTERVAL);
+        self
+    }
+
+    /// Upper bound of the delay between two status checks (default: 30 seconds).
+    pub fn max_poll_interval(mut self, interval: Duration) -> Self {
+        self.max_poll_interval = interval;
+        self
+    }
+
+    /// Give up waiting after this long (default: wait until a terminal state).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Query parameters of the results download (`download_type`, `activity_data`).
+    pub fn get_file_options(mut self, options: ZBGetFileOptions) -> Self {
+        self.get_file_options = Some(options);
+        self
+    }
+
+    /// Delete the file from the account once its results are downloaded
+    /// (default: false). A failed deletion does not fail the job.
+    pub fn delete_after_download(mut self, delete: bool) -> Self {
+        self.delete_after_download = delete;
+        self
+    }
+
+    /// Submit the file, then wait for it and download its results.
+    pub fn run(&self, zb_file: &ZBFile) -> ZBResult<BulkJobOutcome> {
+        let file_id = self.submit(zb_file)?;
+        self.resume(&file_id)
+    }
+
+    /// Submit the file and return its id.
+    pub fn submit(&self, zb_file: &ZBFile) -> ZBResult<String> {
+        let feedback = match self.kind {
+            BulkJobKind::Validation => self.client.bulk_validation_file_submit(zb_file)?,
+            BulkJobKind::AiScoring => self.client.ai_scoring_file_submit(zb_file)?,
+        };
+
+        match feedback {
+            ZBFileFeedback { success: true, file_id: Some(file_id), .. } => Ok(file_id),
+            ZBFileFeedback { message, .. } => {
+                Err(ZBError::ExplicitError(format!("file submission rejected: {}", message)))
+            }
+        }
+    }
+
+    /// Split a file too large for a single submission into chunks within
+    /// `limits` (see `ZBFile::split`), submit them, wait for all of them and
+    /// merge their results back into the original row order. `timeout`
+    /// applies to the wait of each chunk.
+    ///
+    /// A chunk failing does not stop the others; its error is kept in
+    /// `BulkSplitOutcome::chunks`. Only splitting the file or merging the
+    /// results fails the whole run.
+    pub fn run_split(&self, zb_file: &ZBFile, limits: ZBSplitLimits) -> ZBResult<BulkSplitOutcome> {
+        let submissions = self.submit_split(zb_file, limits)?;
+        let chunks = submissions.into_iter()
+            .map(|submission| submission.and_then(|file_id| self.resume(&file_id)))
+            .collect();
+        Self::merge(zb_file, chunks)
+    }
+
+    /// Split a file into chunks within `limits` and submit them; returns the
+    /// id of the file of each chunk, or the error of its submission, in
+    /// order. A failed submission does not stop the next ones.
+    pub fn submit_split(&self, zb_file: &ZBFile, limits: ZBSplitLimits) -> ZBResult<Vec<ZBResult<String>>> {
+        Ok(zb_file.split(limits)?.iter().map(|chunk| self.submit(chunk)).collect())
+    }
+
+    /// Wait for the already submitted chunks of `zb_file`, download their
+    /// results and merge them back into the order of its rows. A chunk
+    /// failing does not stop the others.
+    pub fn resume_split(&self, zb_file: &ZBFile, file_ids: &[String]) -> ZBResult<BulkSplitOutcome> {
+        Self::merge(zb_file, file_ids.iter().map(|file_id| self.resume(file_id)).collect())
+    }
+
+    // Merges the results of the chunks when all of them completed, then joins
+    // them to the original file: the API may return the rows of a chunk in
+    // another order.
+    fn merge(zb_file: &ZBFile, chunks: Vec<ZBResult<BulkJobOutcome>>) -> ZBResult<BulkSplitOutcome> {
+        let contents = chunks.iter()
+            .map(|chunk| chunk.as_ref().ok().and_then(BulkJobOutcome::content))
+            .collect::<Option<Vec<_>>>();
+        let content = match contents {
+            Some(contents) => {
+                let merged = merge_result_files(&contents)?;
+                Some(Bytes::from(zb_file.join_results(merged.as_ref())?))
+            }
+            None => None,
+        };
+        Ok(BulkSplitOutcome { chunks, content })
+    }
+
+    /// Wait for an already submitted file, then download its results.
+    pub fn resume(&self, file_id: &str) -> ZBResult<BulkJobOutcome> {
+        let status = self.wait(file_id)?;
+
+        let outcome = match BulkJobState::of(&status) {
+            BulkJobState::Complete => {
+                let content = self.download(file_id)?;
+                let deleted = self.delete_after_download && self.delete(file_id);
+                BulkJobOutcome::Complete { file_id: file_id.to_string(), status, content, deleted }
+            }
+            BulkJobState::Failed(error_reason) => {
+                BulkJobOutcome::Failed { file_id: file_id.to_string(), status, error_reason }
+            }
+            // `wait` only returns terminal states
+            BulkJobState::Deleted | BulkJobState::Pending => {
+                BulkJobOutcome::Deleted { file_id: file_id.to_string(), status }
+            }
+        };
+        Ok(outcome)
+    }
+
+    /// Poll the file status until it reaches a terminal state.
+    pub fn wait(&self, file_id: &str) -> ZBResult<ZBFileStatus> {
+        let started_at = Instant::now();
+        let mut interval = self.poll_interval;
+
+        loop {
+            let status = self.status(file_id)?;
+            if BulkJobState::of(&status).is_terminal() {
+                return Ok(status);
+            }
+
+            let mut delay = interval;
+            if let Some(timeout) = self.timeout {
+                let remaining = timeout.saturating_sub(started_at.elapsed());
+                if remaining.is_zero() {
+                    return Err(ZBError::BulkJobTimeout {
+                        file_id: file_id.to_string(),
+                        status: Box::new(status),
+                    });
+                }
+                delay = delay.min(remaining);
+            }
+
+            std::thread::sleep(delay);
+            interval = interval.saturating_mul(2).min(self.max_poll_interval.max(self.poll_interval));
+        }
+    }
+
+    fn status(&self, file_id: &str) -> ZBResult<ZBFileStatus> {
+        match self.kind {
+            BulkJobKind::Validation => self.client.bulk_validation_file_status_check(file_id),
+            BulkJobKind::AiScoring => self.client.ai_scoring_file_status_check(file_id),
+        }
+    }
+
+    fn download(&self, file_id: &str) -> ZBResult<Bytes> {
+        let response = match (self.kind, &self.get_file_options) {
+            (BulkJobKind::Validation, None) => self.client.bulk_validation_result_fetch(file_id)?,
+            (BulkJobKind::Validation, Some(options)) => {
+                self.client.bulk_validation_result_fetch_with_options(file_id, options)?
+            }
+            (BulkJobKind::AiScoring, None) => self.client.ai_scoring_result_fetch(file_id)?,
+            (BulkJobKind::AiScoring, Some(options)) => {
+                self.client.ai_scoring_result_fetch_with_options(file_id, options)?
+            }
+        };
+
+        match response {
+            ZBBulkResponse::Content(content) => Ok(content),
+            ZBBulkResponse::Feedback(feedback) => Err(ZBError::ExplicitError(feedback.message)),
+        }
+    }
+
+    // Whether the file was deleted; failures are not reported since the
+    // results were already downloaded.
+    fn delete(&self, file_id: &str) -> bool {
+        let feedback = match self.kind {
+            BulkJobKind::Validation => self.client.bulk_validation_result_delete(file_id),
+            BulkJobKind::AiScoring => self.client.ai_scoring_result_delete(file_id),
+        };
+        feedback.map(|feedback| feedback.success).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utility::mock_constants::{BULK_VALIDATION_STATUS_DELETED, BULK_VALIDATION_STATUS_OK};
+
+    fn status_of(file_status: &str, complete_percentage: f32, error_reason: Option<&str>) -> ZBFileStatus {
+        let mut status = serde_json::from_str::<ZBFileStatus>(BULK_VALIDATION_STATUS_OK).unwrap();
+        status.file_status = file_status.to_string();
+        status.complete_percentage = complete_percentage;
+        status.error_reason = error_reason.map(str::to_string);
+        status
+    }
+
+    #[test]
+    fn test_job_states() {
+        assert_eq!(BulkJobState::of(&status_of("Queued", 0., None)), BulkJobState::Pending);
+        assert_eq!(BulkJobState::of(&status_of("Processing", 42., None)), BulkJobState::Pending);
+        // a complete percentage alone does not make the results ready
+        assert_eq!(BulkJobState::of(&status_of("Processing", 100., None)), BulkJobState::Pending);
+        assert_eq!(BulkJobState::of(&status_of("Complete", 100., None)), BulkJobState::Complete);
+        assert_eq!(
+            BulkJobState::of(&status_of("Error", 10., Some("Invalid file format"))),
+            BulkJobState::Failed("Invalid file format".to_string()),
+        );
+        assert_eq!(BulkJobState::of(&status_of("Failed", 0., Some(""))), BulkJobState::Failed("Failed".to_string()));
+        assert_eq!(BulkJobState::of(&status_of("complete", 100., None)), BulkJobState::Complete);
+        assert_eq!(BulkJobState::of(&status_of("Paused", 50., None)), BulkJobState::Pending);
+
+        let deleted = serde_json::from_str::<ZBFileStatus>(BULK_VALIDATION_STATUS_DELETED).unwrap();
+        assert_eq!(BulkJobState::of(&deleted), BulkJobState::Deleted);
+        assert!(BulkJobState::Deleted.is_terminal());
+    }
+
+    #[test]
+    fn test_poll_interval_minimum() {
+        let zb = ZeroBounce::new("mock_api_key");
+
+        let job = zb.bulk_job(BulkJobKind::Validation).poll_interval(Duration::ZERO);
+        assert_eq!(job.poll_interval, MIN_POLL_INTERVAL);
+
+        let job = zb.bulk_job(BulkJobKind::Validation).poll_interval(Duration::from_secs(5));
+        assert_eq!(job.poll_interval, Duration::from_secs(5));
+    }
+}
@@ -1,10 +1,11 @@
 pub mod bulk;
+pub mod bulk_job;
 pub mod validation;
 
 use std::collections::HashMap;
 
 use chrono::{NaiveDate, Utc};
-use serde_json::from_str;
+use crate::utility::error::parse_response;
 
 pub use crate::ZeroBounce;
 use crate::utility::structures::generic::{FindEmailResponse, FindEmailResponseV2, DomainSearchResponseV2};
@@ -61,8 +62,8 @@ pub struct DomainSearchV2Builder<'a> {
 
 impl ZeroBounce {
 
-    fn get_credits_from_string(string_value: String) -> ZBResult<i64> {
-        from_str::<serde_json::Value>(string_value.as_ref())?
+    pub(crate) fn get_credits_from_string(string_value: String) -> ZBResult<i64> {
+        parse_response::<serde_json::Value>(string_value.as_ref())?
             .get("Credits")
             .and_then(serde_json::Value::as_str)
             .map(str::parse::<i64>)
@@ -74,7 +75,7 @@ impl ZeroBounce {
         let query_args = HashMap::new();
 
         let response_content = self.generic_get_request(
-            self.url_provider.url_of(ENDPOINT_CREDITS), query_args
+            self.url_provider.url_of(ENDPOINT_CREDITS), query_args, None
         )?;
 
         Self::get_credits_from_string(response_content)
@@ -89,10 +90,10 @@ impl ZeroBounce {
         ]);
 
         let response_content = self.generic_get_request(
-            self.url_provider.url_of(ENDPOINT_API_USAGE), query_args
+            self.url_provider.url_of(ENDPOINT_API_USAGE), query_args, None
         )?;
 
-        let api_usage = from_str::<ApiUsage>(&response_content)?;
+        let api_usage = parse_response::<ApiUsage>(&response_content)?;
         Ok(api_usage)
     }
 
@@ -108,10 +109,10 @@ impl ZeroBounce {
         ]);
 
         let response_content = self.generic_get_request(
-            self.url_provider.url_of(ENDPOINT_ACTIVITY_DATA), query_args
+            self.url_provider.url_of(ENDPOINT_ACTIVITY_DATA), query_args, None
         )?;
 
-        let activity_data = from_str::<ActivityData>(&response_content)?;
+        let activity_data = parse_response::<ActivityData>(&response_content)?;
         Ok(activity_data)
     }
 
@@ -137,10 +138,10 @@ impl ZeroBounce {
         }
 
         let response_content = self.generic_get_request(
-            self.url_provider.url_of(ENDPOINT_EMAIL_FINDER), query_args
+            self.url_provider.url_of(ENDPOINT_EMAIL_FINDER), query_args, None
         )?;
 
-        let activity_data = from_str::<FindEmailResponse>(&response_content)?;
+        let activity_data = parse_response::<FindEmailResponse>(&response_content)?;
         Ok(activity_data)
     }
 
@@ -265,61 +266,15 @@ impl<'a> FindEmailV2Builder<'a> {
 
     /// Execute the API call and return the result.
     pub fn call(self) -> ZBResult<FindEmailResponseV2> {
-        let first_name = self.first_name.ok_or_else(|| ZBError::explicit("first_name is mandatory and must be set"))?;
-        
-        if first_name.is_empty() {
-            return Err(ZBError::explicit("first_name cannot be empty"));
-        }
-
-        // Validate XOR requirement: exactly one of domain or company_name must be provided
-        match (self.domain, self.company_name) {
-            (Some(d), None) => {
-                if d.is_empty() {
-                    return Err(ZBError::explicit("domain cannot be empty"));
-                }
-            }
-            (None, Some(c)) => {
-                if c.is_empty() {
-                    return Err(ZBError::explicit("company_name cannot be empty"));
-                }
-            }
-            (Some(_), Some(_)) => {
-                return Err(ZBError::explicit("exactly one of domain or company_name must be provided, not both"));
-            }
-            (None, None) => {
-                return Err(ZBError::explicit("either domain or company_name must be provided"));
-            }
-        }
-
-        let mut query_args = HashMap::from([
-            ("first_name", first_name),
-        ]);
-
-        if let Some(d) = self.domain {
-            query_args.insert("domain", d);
-        }
-
-        if let Some(c) = self.company_name {
-            query_args.insert("company_name", c);
-        }
-
-        if let Some(middle) = self.middle_name {
-            if !middle.is_empty() {
-                query_args.insert("middle_name", middle);
-            }
-        }
-
-        if let Some(last) = self.last_name {
-            if !last.is_empty() {
-                query_args.insert("last_name", last);
-            }
-        }
+        let query_args = find_email_v2_query_args(
+            self.first_name, self.domain, self.company_name, self.middle_name, self.last_name,
+        )?;
 
         let response_content = self.client.generic_get_request(
-            self.client.url_provider.url_of(ENDPOINT_EMAIL_FINDER), query_args
+            self.client.url_provider.url_of(ENDPOINT_EMAIL_FINDER), query_args, None
         )?;
 
-        let find_email_response = from_str::<FindEmailResponseV2>(&response_content)?;
+        let find_email_response = parse_response::<FindEmailResponseV2>(&response_content)?;
         Ok(find_email_response)
     }
 }
@@ -339,43 +294,104 @@ impl<'a> DomainSearchV2Builder<'a> {
 
     /// Execute the API call and return the result.
     pub fn call(self) -> ZBResult<DomainSearchResponseV2> {
-        // Validate XOR requirement: exactly one of domain or company_name must be provided
-        match (self.domain, self.company_name) {
-            (Some(d), None) => {
-                if d.is_empty() {
-                    return Err(ZBError::explicit("domain cannot be empty"));
-                }
-            }
-            (None, Some(c)) => {
-                if c.is_empty() {
-                    return Err(ZBError::explicit("company_name cannot be empty"));
-                }
-            }
-            (Some(_), Some(_)) => {
-                return Err(ZBError::explicit("exactly one of domain or company_name must be provided, not both"));
+        let query_args = domain_search_v2_query_args(self.domain, self.company_name)?;
+
+        let response_content = self.client.generic_get_request(
+            self.client.url_provider.url_of(ENDPOINT_EMAIL_FINDER), query_args, None
+        )?;
+
+        let domain_search_response = parse_response::<DomainSearchResponseV2>(&response_content)?;
+        Ok(domain_search_response)
+    }
+}
+
+// Validate the XOR requirement shared by the finder endpoints: exactly one
+// of domain or company_name must be provided, and it cannot be empty.
+fn check_domain_xor_company(domain: Option<&str>, company_name: Option<&str>) -> ZBResult<()> {
+    match (domain, company_name) {
+        (Some(d), None) => {
+            if d.is_empty() {
+                return Err(ZBError::validation("domain cannot be empty"));
             }
-            (None, None) => {
-                return Err(ZBError::explicit("either domain or company_name must be provided"));
+        }
+        (None, Some(c)) => {
+            if c.is_empty() {
+                return Err(ZBError::validation("company_name cannot be empty"));
             }
         }
+        (Some(_), Some(_)) => {
+            return Err(ZBError::validation("exactly one of domain or company_name must be provided, not both"));
+        }
+        (None, None) => {
+            return Err(ZBError::validation("either domain or company_name must be provided"));
+        }
+    }
+    Ok(())
+}
+
+// Build the query arguments of the `find_email_v2` call after checking the
+// builder parameters. Shared by the blocking and async builders.
+pub(crate) fn find_email_v2_query_args<'a>(
+    first_name: Option<&'a str>,
+    domain: Option<&'a str>,
+    company_name: Option<&'a str>,
+    middle_name: Option<&'a str>,
+    last_name: Option<&'a str>,
+) -> ZBResult<HashMap<&'a str, &'a str>> {
+    let first_name = first_name.ok_or_else(|| ZBError::validation("first_name is mandatory and must be set"))?;
+
+    if first_name.is_empty() {
+        return Err(ZBError::validation("first_name cannot be empty"));
+    }
+
+    check_domain_xor_company(domain, company_name)?;
 
-        let mut query_args = HashMap::new();
+    let mut query_args = HashMap::from([
+        ("first_name", first_name),
+    ]);
+
+    if let Some(d) = domain {
+        query_args.insert("domain", d);
+    }
 
-        if let Some(d) = self.domain {
-            query_args.insert("domain", d);
+    if let Some(c) = company_name {
+        query_args.insert("company_name", c);
+    }
+
+    if let Some(middle) = middle_name {
+        if !middle.is_empty() {
+            query_args.insert("middle_name", middle);
         }
+    }
 
-        if let Some(c) = self.company_name {
-            query_args.insert("company_name", c);
+    if let Some(last) = last_name {
+        if !last.is_empty() {
+            query_args.insert("last_name", last);
         }
+    }
 
-        let response_content = self.client.generic_get_request(
-            self.client.url_provider.url_of(ENDPOINT_EMAIL_FINDER), query_args
-        )?;
+    Ok(query_args)
+}
 
-        let domain_search_response = from_str::<DomainSearchResponseV2>(&response_content)?;
-        Ok(domain_search_response)
+// Build the query arguments of the `domain_search_v2` call after checking the
+// builder parameters. Shared by the blocking and async builders.
+pub(crate) fn domain_search_v2_query_args<'a>(
+    domain: Option<&'a str>,
+    company_name: Option<&'a str>,
+) -> ZBResult<HashMap<&'a str, &'a str>> {
+    check_domain_xor_company(domain, company_name)?;
+
+    let mut query_args = HashMap::new();
+
+    if let Some(d) = domain {
+        query_args.insert("domain", d);
     }
+
+    if let Some(c) = company_name {
+        query_args.insert("company_name", c);
+    }
+
+    Ok(query_args)
 }
 
 #[cfg(test)]
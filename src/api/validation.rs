@@ -1,17 +1,37 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use serde::Serialize;
-use serde_json::from_str;
+use crate::utility::error::parse_response;
 use serde_json::{Map as SerdeMap, Value};
 
 use crate::{ZeroBounce,  ZBResult};
 use crate::utility::{ENDPOINT_VALIDATE, ZBError, ENDPOINT_BATCH_VALIDATE, CONTENT_TYPE_JSON};
+use crate::utility::rate_limit::EndpointFamily;
+use crate::transport::{ZBRequest, ZBRequestBody};
+use crate::utility::BATCH_VALIDATE_MAX_EMAILS;
+use crate::utility::precheck::check_email;
 use crate::utility::structures::validation::{ZBValidation, ZBBatchValidation};
+use crate::utility::structures::validation::{ZBBatchChunkFailure, ZBBatchOptions, ZBBatchValidationReport};
 
 
 impl ZeroBounce {
 
     pub fn validate_email_and_ip(&self, email: &str, ip_address: &str) -> ZBResult<ZBValidation> {
+        if self.precheck {
+            if let Err(error) = check_email(email) {
+                return Ok(ZBValidation::failed_syntax_check(email, error));
+            }
+        }
+        // Results depend on the IP address, which the cache and store do not
+        // keep: validations with one bypass them
+        let remember = ip_address.is_empty();
+        if remember {
+            if let Some(known) = self.known_validation(email)? {
+                return Ok(known);
+            }
+        }
+
         let mut query_args = HashMap::from([
             ("email", email),
         ]);
@@ -20,11 +40,14 @@ impl ZeroBounce {
             query_args.insert("ip_address", ip_address);
         }
 
-        let response_content = self.generic_get_request(
-            self.url_provider.url_of(ENDPOINT_VALIDATE), query_args
-        )?;
+        let response_content = self.spend_credits(1, || self.generic_get_request(
+            self.url_provider.url_of(ENDPOINT_VALIDATE), query_args, Some(EndpointFamily::Validate)
+        ))?;
 
-        let validation = from_str::<ZBValidation>(&response_content)?;
+        let validation = parse_response::<ZBValidation>(&response_content)?;
+        if remember {
+            self.remember_validations(std::slice::from_ref(&validation))?;
+        }
         Ok(validation)
     }
 
@@ -32,62 +55,98 @@ impl ZeroBounce {
         self.validate_email_and_ip(email, "")
     }
 
-    // Represent a list of tuples (containing email and ip_address) into a
-    // serializable `serde_json::Value` that respects the expected structure
-    // of the batch validation endpoint.
-    //
-    // Said structure:
-    // ```json
-    // {
-    //     "api_key": {{apikey}},
-    //     "email_batch": [
-    //         {"email_address": "valid@example.com", "ip_address": "0.0.0.0"},
-    //         {"email_address": "invalid@example.com", "ip_address": "1.1.1.1"}
-    //     ]
-    // }
-    // ```
-    // After the value is built, serialize and return the resulted string.
     fn batch_validate_prepare_body(&self, emails_and_ip_addresses: Vec<(String, String)>) -> ZBResult<String> {
-        let email_batch = emails_and_ip_addresses
-            .into_iter()
-            .map(|(email, ip_address)|
-                [
-                    ("email_address".to_string(), Value::String(email)),
-                    ("ip_address".to_string(), Value::String(ip_address)),
-                ]
-            )
-            .map(SerdeMap::<String, Value>::from_iter)
-            .map(Value::Object)
-            .collect::<Vec<Value>>();
-
-        let request_body_map = SerdeMap::from_iter([
-            ("api_key".to_string(), Value::String(self.api_key.clone())),
-            ("email_batch".to_string(), Value::Array(email_batch)),
-        ]);
+        batch_validate_body(&self.api_key, emails_and_ip_addresses)
+    }
+
+    pub fn batch_validate(&self, emails_and_ip_addresses: Vec<(String, String)>) -> ZBResult<ZBBatchValidation> {
+        if !self.precheck && self.cache.is_none() && self.store.is_none() {
+            return self.batch_validate_request(emails_and_ip_addresses);
+        }
+
+        // Only the addresses passing the pre-check and missing from the
+        // cache and store are sent; the results are then merged back in
+        // input order. Addresses sent with an IP address bypass the cache and
+        // store, like in `validate_email_and_ip`
+        let position = input_positions(&emails_and_ip_addresses);
+        let mut answered = Vec::new();
+        let mut to_send = Vec::new();
+        let mut with_ip_address = HashSet::new();
+        for (email, ip_address) in emails_and_ip_addresses {
+            if self.precheck {
+                if let Err(error) = check_email(&email) {
+                    answered.push(ZBValidation::failed_syntax_check(&email, error));
+                    continue;
+                }
+            }
+            if !ip_address.is_empty() {
+                with_ip_address.insert(email.trim().to_ascii_lowercase());
+                to_send.push((email, ip_address));
+                continue;
+            }
+            match self.known_validation(&email)? {
+                Some(known) => answered.push(known),
+                None => to_send.push((email, ip_address)),
+            }
+        }
 
-        // let request_body_object = Value::Object(request_body_map);
-        let mut serializer = serde_json::Serializer::new(Vec::new());
-        Value::Object(request_body_map)
-            .serialize(&mut serializer)
-            .map_err(ZBError::JsonError)?;
+        let mut validation = if to_send.is_empty() {
+            ZBBatchValidation { email_batch: Vec::new(), errors: Vec::new() }
+        } else {
+            self.batch_validate_request(to_send)?
+        };
+        let to_remember: Vec<ZBValidation> = validation.email_batch.iter()
+            .filter(|item| !with_ip_address.contains(&item.address.trim().to_ascii_lowercase()))
+            .cloned()
+            .collect();
+        self.remember_validations(&to_remember)?;
+        if !answered.is_empty() {
+            validation.email_batch.extend(answered);
+            validation.email_batch.sort_by_key(|item| position(&item.address));
+        }
+        Ok(validation)
+    }
 
-        let final_string = String::from_utf8(serializer.into_inner())
-            .map_err(|error| ZBError::ExplicitError(error.to_string()))?;
+    // Result of an address found in the cache or, failing that, in the
+    // store; the latter is then cached.
+    fn known_validation(&self, email: &str) -> ZBResult<Option<ZBValidation>> {
+        if let Some(cached) = self.cache.as_ref().and_then(|cache| cache.get(email)) {
+            return Ok(Some(cached));
+        }
+        let Some(store) = &self.store else {
+            return Ok(None);
+        };
+        let stored = store.get(email)?;
+        if let (Some(cache), Some(stored)) = (&self.cache, &stored) {
+            cache.insert(stored);
+        }
+        Ok(stored)
+    }
 
-        Ok(final_string)
+    // Save results received from the API in the cache and store.
+    fn remember_validations(&self, validations: &[ZBValidation]) -> ZBResult<()> {
+        if let Some(cache) = &self.cache {
+            validations.iter().for_each(|validation| cache.insert(validation));
+        }
+        match &self.store {
+            Some(store) if !validations.is_empty() => store.put(validations),
+            _ => Ok(()),
+        }
     }
 
-    pub fn batch_validate(&self, emails_and_ip_addresses: Vec<(String, String)>) -> ZBResult<ZBBatchValidation> {
+    fn batch_validate_request(&self, emails_and_ip_addresses: Vec<(String, String)>) -> ZBResult<ZBBatchValidation> {
+        let cost = emails_and_ip_addresses.len() as u64;
         let body_content = self.batch_validate_prepare_body(emails_and_ip_addresses)?;
         let url = self.url_provider.url_of(ENDPOINT_BATCH_VALIDATE);
 
-        let response = self.client.post(url)
-            .body(body_content)
-            .header("content-type", CONTENT_TYPE_JSON)
-            .send()?;
-
-        let response_ok = response.status().is_success();
-        let response_content = response.text()?;
+        let response_content = self.spend_credits(cost, || {
+            self.send_request(Some(EndpointFamily::BatchValidate), || Ok(
+                ZBRequest::post(&url).body(ZBRequestBody::Bytes {
+                    content_type: CONTENT_TYPE_JSON.to_string(),
+                    content: body_content.clone().into_bytes(),
+                })
+            ))?.text()
+        })?;
 
         // Debug: Print raw response to examine structure in debug mode
         #[cfg(debug_assertions)]
@@ -95,16 +154,148 @@ impl ZeroBounce {
             eprintln!("Raw API response: {}", response_content);
         }
 
-        if !response_ok {
-            return Err(ZBError::ExplicitError(response_content));
+        let validation = parse_response::<ZBBatchValidation>(response_content.as_str())?;
+        Ok(validation)
+    }
+
+    /// Validate any number of emails with `batch_validate`, using the
+    /// default `ZBBatchOptions`: chunks of `BATCH_VALIDATE_MAX_EMAILS`
+    /// emails, 4 requests at a time.
+    pub fn batch_validate_all(
+        &self,
+        emails_and_ip_addresses: impl IntoIterator<Item = (String, String)>,
+    ) -> ZBBatchValidationReport {
+        self.batch_validate_all_with_options(emails_and_ip_addresses, &ZBBatchOptions::default())
+    }
+
+    /// Split the input into chunks sent concurrently with `batch_validate`,
+    /// then merge their results in input order. A failed chunk does not stop
+    /// the others; it is reported in `failed_chunks` along with its emails.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zero_bounce::ZeroBounce;
+    ///
+    /// let zb = ZeroBounce::new("your_api_key");
+    /// let emails = (0..1000).map(|i| (format!("user{}@example.com", i), String::new()));
+    ///
+    /// let report = zb.batch_validate_all(emails);
+    /// println!("{} validated, {} failed chunks", report.email_batch.len(), report.failed_chunks.len());
+    /// ```
+    pub fn batch_validate_all_with_options(
+        &self,
+        emails_and_ip_addresses: impl IntoIterator<Item = (String, String)>,
+        options: &ZBBatchOptions,
+    ) -> ZBBatchValidationReport {
+        let chunk_size = options.chunk_size.clamp(1, BATCH_VALIDATE_MAX_EMAILS);
+        let mut chunks: Vec<Vec<(String, String)>> = Vec::new();
+        for email_and_ip in emails_and_ip_addresses {
+            match chunks.last_mut() {
+                Some(chunk) if chunk.len() < chunk_size => chunk.push(email_and_ip),
+                _ => chunks.push(vec![email_and_ip]),
+            }
         }
 
-        let validation = from_str::<ZBBatchValidation>(response_content.as_str())?;
-        Ok(validation)
+        // Workers pick the next chunk until none is left
+        let next_chunk = AtomicUsize::new(0);
+        let workers = options.concurrency.clamp(1, chunks.len().max(1));
+        let mut results = std::thread::scope(|scope| {
+            let handles = (0..workers)
+                .map(|_| scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next_chunk.fetch_add(1, Ordering::Relaxed);
+                        let Some(chunk) = chunks.get(index) else {
+                            return results;
+                        };
+                        results.push((index, self.batch_validate(chunk.clone())));
+                    }
+                }))
+                .collect::<Vec<_>>();
+
+            handles.into_iter()
+                .flat_map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect::<Vec<_>>()
+        });
+        results.sort_by_key(|(index, _)| *index);
+
+        let mut report = ZBBatchValidationReport::default();
+        for ((chunk_index, result), chunk) in results.into_iter().zip(chunks) {
+            match result {
+                Ok(mut validation) => {
+                    let position = input_positions(&chunk);
+                    validation.email_batch.sort_by_key(|item| position(&item.address));
+                    validation.errors.sort_by_key(|item| position(&item.email_address));
+                    report.email_batch.extend(validation.email_batch);
+                    report.errors.extend(validation.errors);
+                }
+                Err(error) => report.failed_chunks.push(ZBBatchChunkFailure {
+                    chunk_index,
+                    emails_and_ip_addresses: chunk,
+                    error,
+                }),
+            }
+        }
+        report
     }
 
 }
 
+// Position of an email in a chunk, used to restore the input order of the
+// batch results. Unknown emails are placed last.
+fn input_positions(chunk: &[(String, String)]) -> impl Fn(&str) -> usize {
+    let mut positions = HashMap::new();
+    for (index, (email, _)) in chunk.iter().enumerate() {
+        positions.entry(email.trim().to_ascii_lowercase()).or_insert(index);
+    }
+    move |email| positions.get(&email.trim().to_ascii_lowercase()).copied().unwrap_or(usize::MAX)
+}
+
+// Represent a list of tuples (containing email and ip_address) into a
+// serializable `serde_json::Value` that respects the expected structure
+// of the batch validation endpoint.
+//
+// Said structure:
+// ```json
+// {
+//     "api_key": {{apikey}},
+//     "email_batch": [
+//         {"email_address": "valid@example.com", "ip_address": "0.0.0.0"},
+//         {"email_address": "invalid@example.com", "ip_address": "1.1.1.1"}
+//     ]
+// }
+// ```
+// After the value is built, serialize and return the resulted string.
+pub(crate) fn batch_validate_body(api_key: &str, emails_and_ip_addresses: Vec<(String, String)>) -> ZBResult<String> {
+    let email_batch = emails_and_ip_addresses
+        .into_iter()
+        .map(|(email, ip_address)|
+            [
+                ("email_address".to_string(), Value::String(email)),
+                ("ip_address".to_string(), Value::String(ip_address)),
+            ]
+        )
+        .map(SerdeMap::<String, Value>::from_iter)
+        .map(Value::Object)
+        .collect::<Vec<Value>>();
+
+    let request_body_map = SerdeMap::from_iter([
+        ("api_key".to_string(), Value::String(api_key.to_string())),
+        ("email_batch".to_string(), Value::Array(email_batch)),
+    ]);
+
+    // let request_body_object = Value::Object(request_body_map);
+    let mut serializer = serde_json::Serializer::new(Vec::new());
+    Value::Object(request_body_map)
+        .serialize(&mut serializer)
+        .map_err(ZBError::JsonError)?;
+
+    let final_string = String::from_utf8(serializer.into_inner())
+        .map_err(|error| ZBError::ExplicitError(error.to_string()))?;
+
+    Ok(final_string)
+}
+
 #[cfg(test)]
 mod test {
     use crate::ZeroBounce;
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use crate::utility::error::parse_response;
+
+use crate::async_api::{error_for_status, ZeroBounceAsync};
+use crate::utility::ZBResult;
+use crate::utility::{ENDPOINT_FILE_SEND, ENDPOINT_FILE_STATUS, ENDPOINT_FILE_RESULT, ENDPOINT_FILE_DELETE};
+use crate::utility::{ENDPOINT_SCORING_DELETE, ENDPOINT_SCORING_STATUS, ENDPOINT_SCORING_RESULT, ENDPOINT_SCORING_SEND};
+use crate::utility::structures::bulk::{ZBBulkResponse, ZBFile, ZBFileFeedback, ZBFileStatus, ZBGetFileOptions};
+use crate::utility::bulk_get_file::{get_file_query, get_file_response};
+
+
+impl ZeroBounceAsync {
+
+    async fn generic_file_submit(&self, endpoint: &str, zb_file: &ZBFile) -> ZBResult<ZBFileFeedback> {
+        let mut multi_part_form = zb_file.generate_multipart_async().await?
+            .text("api_key", self.api_key.clone());
+
+        if endpoint == ENDPOINT_FILE_SEND {
+            if let Some(v) = zb_file.allow_phase_2 {
+                multi_part_form = multi_part_form.text("allow_phase_2", v.to_string());
+            }
+        }
+
+        let url = self.url_provider.bulk_url_of(endpoint);
+        let response = self.client.post(url)
+            .multipart(multi_part_form)
+            .send()
+            .await?;
+        let response = error_for_status(response).await?;
+
+        let response_content = response.text().await?;
+
+        // Debug: Print raw response to examine structure in debug mode
+        #[cfg(debug_assertions)]
+        {
+            eprintln!("Raw API response: {}", response_content);
+        }
+
+        let feedback_object = parse_response::<ZBFileFeedback>(&response_content)?;
+        Ok(feedback_object)
+    }
+
+    async fn generic_file_status_check(&self, endpoint: &str, file_id: &str) -> ZBResult<ZBFileStatus> {
+        let query_args = HashMap::from([
+            ("file_id", file_id),
+        ]);
+
+        let response_content = self.generic_get_request(
+            self.url_provider.bulk_url_of(endpoint), query_args
+        ).await?;
+
+        let file_status = parse_response::<ZBFileStatus>(&response_content)?;
+        Ok(file_status)
+    }
+
+    async fn generic_result_fetch(
+        &self,
+        endpoint: &str,
+        file_id: &str,
+        options: Option<&ZBGetFileOptions>,
+        is_scoring: bool,
+    ) -> ZBResult<ZBBulkResponse> {
+        let url = self.url_provider.bulk_url_of(endpoint);
+        let query = get_file_query(&self.api_key, file_id, options, is_scoring);
+
+        let response = self.client.get(&url).query(&query).send().await?;
+        let response = error_for_status(response).await?;
+
+        let content_type = response.headers().get(reqwest::header::CONTENT_TYPE).cloned();
+        let body_bytes = response.bytes().await?;
+
+        get_file_response(content_type.as_ref(), body_bytes)
+    }
+
+    async fn generic_result_delete(&self, endpoint: &str, file_id: &str) -> ZBResult<ZBFileFeedback>{
+        let query_args = HashMap::from([
+            ("file_id", file_id),
+        ]);
+
+        let response_content = self.generic_get_request(
+            self.url_provider.bulk_url_of(endpoint), query_args
+        ).await?;
+
+        let file_status = parse_response::<ZBFileFeedback>(&response_content)?;
+        Ok(file_status)
+    }
+
+    pub async fn bulk_validation_file_submit(&self, zb_file: &ZBFile) -> ZBResult<ZBFileFeedback> {
+        self.generic_file_submit(ENDPOINT_FILE_SEND, zb_file).await
+    }
+
+    pub async fn bulk_validation_file_status_check(&self, file_id: &str) -> ZBResult<ZBFileStatus> {
+        self.generic_file_status_check(ENDPOINT_FILE_STATUS, file_id).await
+    }
+
+    pub async fn bulk_validation_result_fetch(&self, file_id: &str) -> ZBResult<ZBBulkResponse> {
+        self.generic_result_fetch(ENDPOINT_FILE_RESULT, file_id, None, false).await
+    }
+
+    /// Bulk validation getfile with optional v2 query parameters (`download_type`, `activity_data`).
+    pub async fn bulk_validation_result_fetch_with_options(
+        &self,
+        file_id: &str,
+        options: &ZBGetFileOptions,
+    ) -> ZBResult<ZBBulkResponse> {
+        self.generic_result_fetch(ENDPOINT_FILE_RESULT, file_id, Some(options), false).await
+    }
+
+    pub async fn bulk_validation_result_delete(&self, file_id: &str) -> ZBResult<ZBFileFeedback> {
+        self.generic_result_delete(ENDPOINT_FILE_DELETE, file_id).await
+    }
+
+    pub async fn ai_scoring_file_submit(&self, zb_file: &ZBFile) -> ZBResult<ZBFileFeedback> {
+        self.generic_file_submit(ENDPOINT_SCORING_SEND, zb_file).await
+    }
+
+    pub async fn ai_scoring_file_status_check(&self, file_id: &str) -> ZBResult<ZBFileStatus> {
+        self.generic_file_status_check(ENDPOINT_SCORING_STATUS, file_id).await
+    }
+
+    pub async fn ai_scoring_result_fetch(&self, file_id: &str) -> ZBResult<ZBBulkResponse> {
+        self.generic_result_fetch(ENDPOINT_SCORING_RESULT, file_id, None, true).await
+    }
+
+    /// AI scoring getfile with optional `download_type` (`activity_data` is not sent).
+    pub async fn ai_scoring_result_fetch_with_options(
+        &self,
+        file_id: &str,
+        options: &ZBGetFileOptions,
+    ) -> ZBResult<ZBBulkResponse> {
+        self.generic_result_fetch(ENDPOINT_SCORING_RESULT, file_id, Some(options), true).await
+    }
+
+    pub async fn ai_scoring_result_delete(&self, file_id: &str) -> ZBResult<ZBFileFeedback> {
+        self.generic_result_delete(ENDPOINT_SCORING_DELETE, file_id).await
+    }
+
+}
@@ -0,0 +1,313 @@
+//! Non-blocking client, available with the `async` cargo feature.
+//!
+//! `ZeroBounceAsync` offers the endpoint calls of the blocking `ZeroBounce`
+//! client (validation, batch validation, credits, API usage, activity data,
+//! `find_email_v2` / `domain_search_v2`, and the bulk validation and AI
+//! scoring file operations) and returns the same response structures.
+//!
+//! The client features built on top of the calls are only available on the
+//! blocking client: retry policy, rate limiter, syntax pre-check, validation
+//! cache and store, credit guard, `batch_validate_all*`,
+//! `*_result_download_to` and `BulkJob`.
+
+pub mod bulk;
+pub mod validation;
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use crate::utility::error::parse_response;
+
+use crate::api::{domain_search_v2_query_args, find_email_v2_query_args};
+use crate::utility::structures::generic::{FindEmailResponseV2, DomainSearchResponseV2};
+use crate::utility::structures::{ActivityData, ApiUsage};
+use crate::utility::retry::parse_retry_after;
+use crate::utility::{ZBError, ZBResult, ApiBaseUrl};
+use crate::utility::{ENDPOINT_ACTIVITY_DATA, ENDPOINT_API_USAGE, ENDPOINT_CREDITS, ENDPOINT_EMAIL_FINDER};
+use crate::{ZBUrlProvider, ZeroBounce};
+
+// Async client offering the endpoint calls of `ZeroBounce`
+pub struct ZeroBounceAsync {
+    pub api_key: String,
+    pub base_url: String,
+    pub client: reqwest::Client,
+    pub url_provider: ZBUrlProvider,
+}
+
+/// Async builder for the `find_email_v2` API call.
+///
+/// # Example
+/// ```no_run
+/// use zero_bounce::ZeroBounceAsync;
+/// use zero_bounce::utility::ZBResult;
+///
+/// # async fn run() -> ZBResult<()> {
+/// let zb = ZeroBounceAsync::new("your_api_key");
+/// let result = zb.find_email_v2()
+///     .first_name("John")
+///     .domain("example.com")
+///     .last_name("Doe")
+///     .call()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FindEmailV2AsyncBuilder<'a> {
+    client: &'a ZeroBounceAsync,
+    first_name: Option<&'a str>,
+    domain: Option<&'a str>,
+    company_name: Option<&'a str>,
+    middle_name: Option<&'a str>,
+    last_name: Option<&'a str>,
+}
+
+/// Async builder for the `domain_search_v2` API call.
+///
+/// # Example
+/// ```no_run
+/// use zero_bounce::ZeroBounceAsync;
+/// use zero_bounce::utility::ZBResult;
+///
+/// # async fn run() -> ZBResult<()> {
+/// let zb = ZeroBounceAsync::new("your_api_key");
+/// let result = zb.domain_search_v2()
+///     .domain("example.com")
+///     .call()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DomainSearchV2AsyncBuilder<'a> {
+    client: &'a ZeroBounceAsync,
+    domain: Option<&'a str>,
+    company_name: Option<&'a str>,
+}
+
+// More method implementations of this class can be found in the
+// `validation` and `bulk` submodules.
+impl ZeroBounceAsync {
+    /// Create a new async ZeroBounce client instance with the default API URL.
+    ///
+    /// # Arguments
+    /// * `api_key` - Your ZeroBounce API key
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zero_bounce::ZeroBounceAsync;
+    ///
+    /// let zb = ZeroBounceAsync::new("your_api_key");
+    /// ```
+    pub fn new(api_key: &str) -> ZeroBounceAsync {
+        Self::with_base_url(api_key, ApiBaseUrl::Default)
+    }
+
+    /// Create a new async ZeroBounce client instance with a custom base URL.
+    ///
+    /// Accepts the same values as `ZeroBounce::with_base_url`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zero_bounce::{ZeroBounceAsync, ApiBaseUrl};
+    ///
+    /// let zb = ZeroBounceAsync::with_base_url("your_api_key", ApiBaseUrl::EU);
+    /// ```
+    pub fn with_base_url<T>(api_key: &str, base_url: T) -> ZeroBounceAsync
+    where
+        T: Into<String>,
+    {
+        let base_url_string = base_url.into();
+
+        let url_provider = ZBUrlProvider {
+            url: base_url_string.clone(),
+            ..Default::default()
+        };
+
+        ZeroBounceAsync {
+            api_key: api_key.to_string(),
+            base_url: base_url_string,
+            client: reqwest::Client::default(),
+            url_provider,
+        }
+    }
+
+    async fn generic_get_request<'a>(&'a self, url: String, mut query_args: HashMap<&'a str, &'a str>) -> ZBResult<String> {
+        // Automatically add api_key to query arguments
+        query_args.insert("api_key", self.api_key.as_str());
+
+        let response = self.client.get(url).query(&query_args).send().await?;
+        let response = error_for_status(response).await?;
+
+        let response_content = response.text().await?;
+
+        // Debug: Print raw response to examine structure in debug mode
+        #[cfg(debug_assertions)]
+        {
+            eprintln!("Raw API response: {}", response_content);
+        }
+
+        Ok(response_content)
+    }
+
+    pub async fn get_credits(&self) -> ZBResult<i64> {
+        let query_args = HashMap::new();
+
+        let response_content = self.generic_get_request(
+            self.url_provider.url_of(ENDPOINT_CREDITS), query_args
+        ).await?;
+
+        ZeroBounce::get_credits_from_string(response_content)
+    }
+
+    pub async fn get_api_usage(&self, start_date: NaiveDate, end_date: NaiveDate) -> ZBResult<ApiUsage> {
+        let start_date_str = start_date.format("%F").to_string();
+        let end_date_str = end_date.format("%F").to_string();
+        let query_args = HashMap::from([
+            ("start_date", start_date_str.as_str()),
+            ("end_date", end_date_str.as_str()),
+        ]);
+
+        let response_content = self.generic_get_request(
+            self.url_provider.url_of(ENDPOINT_API_USAGE), query_args
+        ).await?;
+
+        let api_usage = parse_response::<ApiUsage>(&response_content)?;
+        Ok(api_usage)
+    }
+
+    pub async fn get_api_usage_overall(&self) -> ZBResult<ApiUsage> {
+        let start_date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        let end_date = Utc::now().naive_local().date();
+        self.get_api_usage(start_date, end_date).await
+    }
+
+    pub async fn get_activity_data(&self, email: &str) -> ZBResult<ActivityData> {
+        let query_args = HashMap::from([
+            ("email", email),
+        ]);
+
+        let response_content = self.generic_get_request(
+            self.url_provider.url_of(ENDPOINT_ACTIVITY_DATA), query_args
+        ).await?;
+
+        let activity_data = parse_response::<ActivityData>(&response_content)?;
+        Ok(activity_data)
+    }
+
+    /// Find an email address using either a domain or company name.
+    ///
+    /// Same requirements as `ZeroBounce::find_email_v2`; finish the chain
+    /// with `.call().await`.
+    pub fn find_email_v2(&self) -> FindEmailV2AsyncBuilder<'_> {
+        FindEmailV2AsyncBuilder {
+            client: self,
+            first_name: None,
+            domain: None,
+            company_name: None,
+            middle_name: None,
+            last_name: None,
+        }
+    }
+
+    /// Search for email formats using either a domain or company name.
+    ///
+    /// Same requirements as `ZeroBounce::domain_search_v2`; finish the chain
+    /// with `.call().await`.
+    pub fn domain_search_v2(&self) -> DomainSearchV2AsyncBuilder<'_> {
+        DomainSearchV2AsyncBuilder {
+            client: self,
+            domain: None,
+            company_name: None,
+        }
+    }
+}
+
+// Turn a response with an unsuccessful status into the matching error.
+pub(crate) async fn error_for_status(response: reqwest::Response) -> ZBResult<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let retry_after = parse_retry_after(response.headers().get(reqwest::header::RETRY_AFTER));
+    let body = response.text().await.unwrap_or_default();
+
+    // Debug: Print raw response to examine structure in debug mode
+    #[cfg(debug_assertions)]
+    {
+        eprintln!("Raw API response: {}", body);
+    }
+
+    Err(ZBError::from_response(status.as_u16(), body, retry_after))
+}
+
+impl<'a> FindEmailV2AsyncBuilder<'a> {
+    /// Set the first name (mandatory).
+    pub fn first_name(mut self, name: &'a str) -> Self {
+        self.first_name = Some(name);
+        self
+    }
+
+    /// Set the domain name (exactly one of domain or company_name must be provided).
+    pub fn domain(mut self, domain: &'a str) -> Self {
+        self.domain = Some(domain);
+        self
+    }
+
+    /// Set the company name (exactly one of domain or company_name must be provided).
+    pub fn company_name(mut self, company: &'a str) -> Self {
+        self.company_name = Some(company);
+        self
+    }
+
+    /// Set the middle name (optional).
+    pub fn middle_name(mut self, name: &'a str) -> Self {
+        self.middle_name = Some(name);
+        self
+    }
+
+    /// Set the last name (optional).
+    pub fn last_name(mut self, name: &'a str) -> Self {
+        self.last_name = Some(name);
+        self
+    }
+
+    /// Execute the API call and return the result.
+    pub async fn call(self) -> ZBResult<FindEmailResponseV2> {
+        let query_args = find_email_v2_query_args(
+            self.first_name, self.domain, self.company_name, self.middle_name, self.last_name,
+        )?;
+
+        let response_content = self.client.generic_get_request(
+            self.client.url_provider.url_of(ENDPOINT_EMAIL_FINDER), query_args
+        ).await?;
+
+        let find_email_response = parse_response::<FindEmailResponseV2>(&response_content)?;
+        Ok(find_email_response)
+    }
+}
+
+impl<'a> DomainSearchV2AsyncBuilder<'a> {
+    /// Set the domain name (exactly one of domain or company_name must be provided).
+    pub fn domain(mut self, domain: &'a str) -> Self {
+        self.domain = Some(domain);
+        self
+    }
+
+    /// Set the company name (exactly one of domain or company_name must be provided).
+    pub fn company_name(mut self, company: &'a str) -> Self {
+        self.company_name = Some(company);
+        self
+    }
+
+    /// Execute the API call and return the result.
+    pub async fn call(self) -> ZBResult<DomainSearchResponseV2> {
+        let query_args = domain_search_v2_query_args(self.domain, self.company_name)?;
+
+        let response_content = self.client.generic_get_request(
+            self.client.url_provider.url_of(ENDPOINT_EMAIL_FINDER), query_args
+        ).await?;
+
+        let domain_search_response = parse_response::<DomainSearchResponseV2>(&response_content)?;
+        Ok(domain_search_response)
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use crate::utility::error::parse_response;
+
+use crate::api::validation::batch_validate_body;
+use crate::async_api::{error_for_status, ZeroBounceAsync};
+use crate::utility::{ENDPOINT_VALIDATE, ZBResult, ENDPOINT_BATCH_VALIDATE, CONTENT_TYPE_JSON};
+use crate::utility::structures::validation::{ZBValidation, ZBBatchValidation};
+
+
+impl ZeroBounceAsync {
+
+    pub async fn validate_email_and_ip(&self, email: &str, ip_address: &str) -> ZBResult<ZBValidation> {
+        let mut query_args = HashMap::from([
+            ("email", email),
+        ]);
+
+        if !ip_address.is_empty() {
+            query_args.insert("ip_address", ip_address);
+        }
+
+        let response_content = self.generic_get_request(
+            self.url_provider.url_of(ENDPOINT_VALIDATE), query_args
+        ).await?;
+
+        let validation = parse_response::<ZBValidation>(&response_content)?;
+        Ok(validation)
+    }
+
+    pub async fn validate_email(&self, email: &str) -> ZBResult<ZBValidation> {
+        self.validate_email_and_ip(email, "").await
+    }
+
+    pub async fn batch_validate(&self, emails_and_ip_addresses: Vec<(String, String)>) -> ZBResult<ZBBatchValidation> {
+        let body_content = batch_validate_body(&self.api_key, emails_and_ip_addresses)?;
+        let url = self.url_provider.url_of(ENDPOINT_BATCH_VALIDATE);
+
+        let response = self.client.post(url)
+            .body(body_content)
+            .header("content-type", CONTENT_TYPE_JSON)
+            .send()
+            .await?;
+        let response = error_for_status(response).await?;
+
+        let response_content = response.text().await?;
+
+        // Debug: Print raw response to examine structure in debug mode
+        #[cfg(debug_assertions)]
+        {
+            eprintln!("Raw API response: {}", response_content);
+        }
+
+        let validation = parse_response::<ZBBatchValidation>(response_content.as_str())?;
+        Ok(validation)
+    }
+
+}
@@ -0,0 +1,458 @@
+//! `zb`, command-line client of the ZeroBounce API (cargo feature `cli`).
+//!
+//! The API key is read from `--api-key`, `ZEROBOUNCE_API_KEY` or the legacy
+//! `ZERO_BOUNCE_API_KEY`. Results are printed as tables, or as the JSON
+//! responses of the API with `--json`.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::Value;
+
+use zero_bounce::builder::SDK_USER_AGENT;
+use zero_bounce::transport::{ReqwestTransport, Transport, ZBRequest, ZBResponse};
+use zero_bounce::utility::structures::validation::ZBBatchValidationReport;
+use zero_bounce::utility::{ZBError, ZBResult};
+use zero_bounce::{ApiBaseUrl, ZBFile, ZeroBounce};
+
+#[derive(Parser)]
+#[command(name = "zb", version, about = "Command-line client of the ZeroBounce API")]
+struct Cli {
+    /// API key [default: ZEROBOUNCE_API_KEY or ZERO_BOUNCE_API_KEY]
+    #[arg(long, global = true)]
+    api_key: Option<String>,
+
+    /// Region of the API
+    #[arg(long, global = true, value_enum, default_value_t = Region::Default)]
+    region: Region,
+
+    /// Custom API URL, instead of the one of the region
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+
+    /// Custom bulk API URL
+    #[arg(long, global = true)]
+    bulk_url: Option<String>,
+
+    /// Print the JSON responses of the API instead of tables
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Region {
+    Default,
+    Usa,
+    Eu,
+}
+
+impl From<Region> for ApiBaseUrl {
+    fn from(region: Region) -> ApiBaseUrl {
+        match region {
+            Region::Default => ApiBaseUrl::Default,
+            Region::Usa => ApiBaseUrl::USA,
+            Region::Eu => ApiBaseUrl::EU,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the credits left
+    Credits,
+    /// Show the API usage over a period (default: since the account was created)
+    Usage {
+        /// First day, as YYYY-MM-DD
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last day, as YYYY-MM-DD [default: today]
+        #[arg(long)]
+        to: Option<NaiveDate>,
+    },
+    /// Validate an email address
+    Validate {
+        email: String,
+        /// IP address the email signed up from
+        #[arg(long)]
+        ip: Option<String>,
+    },
+    /// Validate the emails of a file: one per line, optionally followed by
+    /// a comma and an IP address
+    Batch { file: PathBuf },
+    /// Show the activity data of an email address
+    Activity { email: String },
+    /// Find the email address of a person
+    FindEmail(FindEmailArgs),
+    /// Find the email format of a domain or company
+    DomainSearch {
+        #[command(flatten)]
+        target: Target,
+    },
+    /// Bulk validation files
+    #[command(subcommand)]
+    Bulk(FileCommand),
+    /// AI scoring files
+    #[command(subcommand)]
+    Scoring(FileCommand),
+}
+
+#[derive(Args)]
+struct FindEmailArgs {
+    #[arg(long)]
+    first_name: String,
+    #[arg(long)]
+    middle_name: Option<String>,
+    #[arg(long)]
+    last_name: Option<String>,
+    #[command(flatten)]
+    target: Target,
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct Target {
+    #[arg(long)]
+    domain: Option<String>,
+    #[arg(long)]
+    company: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum FileCommand {
+    /// Submit a CSV file
+    Submit(SubmitArgs),
+    /// Show the processing status of a file
+    Status { file_id: String },
+    /// Download the results of a file
+    Fetch {
+        file_id: String,
+        /// Write the results to this file instead of the standard output
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Delete a file
+    Delete { file_id: String },
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    file: PathBuf,
+    /// The first row holds data, not column names
+    #[arg(long)]
+    no_header: bool,
+    /// Column of the email addresses, starting at 1
+    #[arg(long, default_value_t = 1)]
+    email_column: u32,
+    /// Remove the duplicate emails before processing
+    #[arg(long)]
+    remove_duplicate: bool,
+    /// URL called when the file is processed
+    #[arg(long)]
+    return_url: Option<String>,
+}
+
+// Transport keeping the JSON bodies of the successful responses, which are
+// printed as they are. Result downloads are passed through untouched.
+struct RecordingTransport {
+    inner: ReqwestTransport,
+    bodies: Mutex<Vec<Value>>,
+}
+
+impl RecordingTransport {
+    fn take_bodies(&self) -> Vec<Value> {
+        std::mem::take(&mut *self.bodies.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn send(&self, request: ZBRequest) -> ZBResult<ZBResponse> {
+        let is_download = request.url.ends_with("/getfile");
+        let mut response = self.inner.send(request)?;
+        if is_download || !response.is_success() {
+            return Ok(response);
+        }
+
+        let mut body = Vec::new();
+        response.body.read_to_end(&mut body)?;
+        if let Ok(value) = serde_json::from_slice::<Value>(&body) {
+            self.bodies.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(value);
+        }
+        response.body = Box::new(Cursor::new(body));
+        Ok(response)
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Run a command; returns false when it only partially succeeded.
+fn run(cli: Cli) -> ZBResult<bool> {
+    let api_key = cli.api_key.clone()
+        .or_else(|| std::env::var("ZEROBOUNCE_API_KEY").ok())
+        .or_else(|| std::env::var("ZERO_BOUNCE_API_KEY").ok())
+        .ok_or_else(|| ZBError::validation("set the API key with --api-key or ZEROBOUNCE_API_KEY"))?;
+
+    let client = reqwest::blocking::Client::builder().user_agent(SDK_USER_AGENT).build()?;
+    let transport = Arc::new(RecordingTransport {
+        inner: ReqwestTransport::new(client),
+        bodies: Mutex::new(Vec::new()),
+    });
+    let mut builder = ZeroBounce::builder()
+        .api_key(&api_key)
+        .base_url(cli.base_url.clone().unwrap_or_else(|| ApiBaseUrl::from(cli.region).into()))
+        .transport(transport.clone());
+    if let Some(bulk_url) = &cli.bulk_url {
+        builder = builder.bulk_url(bulk_url.as_str());
+    }
+    let zb = builder.build()?;
+    let output = Output { json: cli.json, transport };
+
+    match cli.command {
+        Command::Credits => {
+            let credits = zb.get_credits()?;
+            output.print(|| println!("Credits: {}", credits));
+        }
+        Command::Usage { from, to } => {
+            match (from, to) {
+                (None, None) => zb.get_api_usage_overall()?,
+                (from, to) => {
+                    let today = chrono::Utc::now().date_naive();
+                    let from = from.unwrap_or_else(|| NaiveDate::from_ymd_opt(2000, 1, 1).unwrap());
+                    zb.get_api_usage(from, to.unwrap_or(today))?
+                }
+            };
+            output.print_fields();
+        }
+        Command::Validate { email, ip } => {
+            zb.validate_email_and_ip(&email, ip.as_deref().unwrap_or_default())?;
+            output.print_fields();
+        }
+        Command::Batch { file } => {
+            let report = zb.batch_validate_all(read_batch_file(&file)?);
+            output.print_batch(&report);
+            return Ok(report.is_complete());
+        }
+        Command::Activity { email } => {
+            zb.get_activity_data(&email)?;
+            output.print_fields();
+        }
+        Command::FindEmail(args) => {
+            let mut request = zb.find_email_v2().first_name(&args.first_name);
+            if let Some(middle_name) = &args.middle_name {
+                request = request.middle_name(middle_name);
+            }
+            if let Some(last_name) = &args.last_name {
+                request = request.last_name(last_name);
+            }
+            request = match (&args.target.domain, &args.target.company) {
+                (Some(domain), _) => request.domain(domain),
+                (None, company) => request.company_name(company.as_deref().unwrap_or_default()),
+            };
+            request.call()?;
+            output.print_fields();
+        }
+        Command::DomainSearch { target } => {
+            let request = match (&target.domain, &target.company) {
+                (Some(domain), _) => zb.domain_search_v2().domain(domain),
+                (None, company) => zb.domain_search_v2().company_name(company.as_deref().unwrap_or_default()),
+            };
+            request.call()?;
+            output.print_fields();
+        }
+        Command::Bulk(command) => run_file_command(&zb, &output, command, false)?,
+        Command::Scoring(command) => run_file_command(&zb, &output, command, true)?,
+    }
+    Ok(true)
+}
+
+fn run_file_command(zb: &ZeroBounce, output: &Output, command: FileCommand, scoring: bool) -> ZBResult<()> {
+    match command {
+        FileCommand::Submit(args) => {
+            let zb_file = ZBFile::from_path(args.file.to_string_lossy().into_owned())
+                .set_has_header_row(!args.no_header)
+                .set_email_address_column(args.email_column)
+                .set_remove_duplicate(args.remove_duplicate)
+                .set_return_url(args.return_url);
+            if scoring {
+                zb.ai_scoring_file_submit(&zb_file)?;
+            } else {
+                zb.bulk_validation_file_submit(&zb_file)?;
+            }
+        }
+        FileCommand::Status { file_id } => {
+            if scoring {
+                zb.ai_scoring_file_status_check(&file_id)?;
+            } else {
+                zb.bulk_validation_file_status_check(&file_id)?;
+            }
+        }
+        FileCommand::Fetch { file_id, output: path } => {
+            let mut writer: Box<dyn Write> = match &path {
+                Some(path) => Box::new(File::create(path)?),
+                None => Box::new(io::stdout().lock()),
+            };
+            let download = if scoring {
+                zb.ai_scoring_result_download_to(&file_id, &mut writer, None)?
+            } else {
+                zb.bulk_validation_result_download_to(&file_id, &mut writer, None)?
+            };
+            if let Some(path) = path {
+                eprintln!("{} bytes written to {}", download.bytes_written, path.display());
+            }
+            return Ok(());
+        }
+        FileCommand::Delete { file_id } => {
+            if scoring {
+                zb.ai_scoring_result_delete(&file_id)?;
+            } else {
+                zb.bulk_validation_result_delete(&file_id)?;
+            }
+        }
+    }
+    output.print_fields();
+    Ok(())
+}
+
+// Pairs of email and IP address, skipping blank lines and a header line.
+fn read_batch_file(path: &Path) -> ZBResult<Vec<(String, String)>> {
+    let mut emails_and_ip_addresses = Vec::new();
+    for (index, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+        let line = line?;
+        let (email, ip_address) = line.split_once(',').unwrap_or((&line, ""));
+        let email = email.trim().trim_matches('"');
+        if email.is_empty() || (index == 0 && !email.contains('@')) {
+            continue;
+        }
+        emails_and_ip_addresses.push((email.to_string(), ip_address.trim().trim_matches('"').to_string()));
+    }
+    Ok(emails_and_ip_addresses)
+}
+
+struct Output {
+    json: bool,
+    transport: Arc<RecordingTransport>,
+}
+
+impl Output {
+    fn print(&self, print_table: impl FnOnce()) {
+        match self.transport.take_bodies().pop() {
+            Some(body) if self.json => print_json(&body),
+            _ => print_table(),
+        }
+    }
+
+    // Print the last response as a table of its fields.
+    fn print_fields(&self) {
+        let Some(body) = self.transport.take_bodies().pop() else {
+            return;
+        };
+        if self.json {
+            return print_json(&body);
+        }
+        let rows = match &body {
+            Value::Object(fields) => fields.iter()
+                .map(|(name, value)| vec![name.clone(), display_value(value)])
+                .collect(),
+            value => vec![vec![display_value(value)]],
+        };
+        print_table(&[], &rows);
+    }
+
+    fn print_batch(&self, report: &ZBBatchValidationReport) {
+        if self.json {
+            // Merge the responses of every chunk, in input order
+            let mut email_batch = Vec::new();
+            let mut errors = Vec::new();
+            for mut body in self.transport.take_bodies() {
+                if let Some(Value::Array(items)) = body.get_mut("email_batch").map(Value::take) {
+                    email_batch.extend(items);
+                }
+                if let Some(Value::Array(items)) = body.get_mut("errors").map(Value::take) {
+                    errors.extend(items);
+                }
+            }
+            let position = |item: &Value| {
+                let address = item["address"].as_str().unwrap_or_default();
+                report.email_batch.iter()
+                    .position(|validation| validation.address.eq_ignore_ascii_case(address))
+                    .unwrap_or(usize::MAX)
+            };
+            email_batch.sort_by_key(position);
+            print_json(&serde_json::json!({ "email_batch": email_batch, "errors": errors }));
+        } else {
+            let rows = report.email_batch.iter().map(|validation| vec![
+                validation.address.clone(),
+                validation.status.clone(),
+                validation.sub_status.clone(),
+                validation.did_you_mean.clone().unwrap_or_default(),
+            ]).collect::<Vec<_>>();
+            print_table(&["ADDRESS", "STATUS", "SUB STATUS", "DID YOU MEAN"], &rows);
+            for error in &report.errors {
+                eprintln!("{}: {}", error.email_address, error.error);
+            }
+        }
+
+        for failure in &report.failed_chunks {
+            eprintln!(
+                "error: chunk {} ({} emails) failed: {}",
+                failure.chunk_index, failure.emails_and_ip_addresses.len(), failure.error
+            );
+        }
+    }
+}
+
+fn print_json(value: &Value) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap_or_default());
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+// Print rows with aligned columns, after the headers if any.
+fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let header_row = headers.iter().map(|header| header.to_string()).collect::<Vec<_>>();
+    let all_rows = Some(&header_row).filter(|row| !row.is_empty()).into_iter().chain(rows);
+
+    let mut widths = Vec::new();
+    for row in all_rows.clone() {
+        for (index, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            match widths.get_mut(index) {
+                Some(max) if *max < width => *max = width,
+                Some(_) => (),
+                None => widths.push(width),
+            }
+        }
+    }
+
+    let mut stdout = io::stdout().lock();
+    for row in all_rows {
+        let line = row.iter().zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        let _ = writeln!(stdout, "{}", line.trim_end());
+    }
+}
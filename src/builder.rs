@@ -0,0 +1,322 @@
+//! Builder configuring the HTTP client and URLs of a `ZeroBounce` instance.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Proxy, Url};
+
+use crate::store::ValidationStore;
+use crate::transport::{ReqwestTransport, Transport};
+use crate::utility::{ApiBaseUrl, CreditGuard, RateLimiter, RetryPolicy, ValidationCache, ZBError, ZBResult, BULK_URI};
+use crate::{ZBUrlProvider, ZeroBounce};
+
+/// User-Agent sent by clients created with `ZeroBounce::builder()`.
+pub const SDK_USER_AGENT: &str = concat!("zero-bounce-rust/", env!("CARGO_PKG_VERSION"));
+
+/// Builder for a `ZeroBounce` client, created with `ZeroBounce::builder()`.
+///
+/// The configuration is checked by `build`: an empty API key, an URL that
+/// cannot be parsed, an invalid header, or a custom `client` or `transport`
+/// combined with options of the HTTP client built by default (or with each
+/// other) are rejected with `ZBError::Validation`.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use zero_bounce::{ZeroBounce, ApiBaseUrl};
+/// use zero_bounce::utility::ZBResult;
+///
+/// # fn main() -> ZBResult<()> {
+/// let zb = ZeroBounce::builder()
+///     .api_key("your_api_key")
+///     .base_url(ApiBaseUrl::EU)
+///     .connect_timeout(Duration::from_secs(5))
+///     .read_timeout(Duration::from_secs(60))
+///     .default_header("x-request-source", "crm-sync")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ZeroBounceBuilder {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    bulk_url: Option<String>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    default_headers: Vec<(String, String)>,
+    user_agent: Option<String>,
+    client: Option<reqwest::blocking::Client>,
+    transport: Option<Arc<dyn Transport>>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    precheck: bool,
+    cache: Option<ValidationCache>,
+    store: Option<Arc<dyn ValidationStore>>,
+    credit_guard: Option<CreditGuard>,
+}
+
+impl ZeroBounceBuilder {
+    pub fn new() -> ZeroBounceBuilder {
+        Self::default()
+    }
+
+    /// Set the API key (mandatory).
+    pub fn api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+
+    /// Set the base API URL, either an `ApiBaseUrl` or a custom URL string
+    /// (default: `ApiBaseUrl::Default`).
+    pub fn base_url<T: Into<String>>(mut self, base_url: T) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set the URL of the bulk API, used by the bulk validation and AI
+    /// scoring methods (default: `https://bulkapi.zerobounce.net/v2`).
+    pub fn bulk_url<T: Into<String>>(mut self, bulk_url: T) -> Self {
+        self.bulk_url = Some(bulk_url.into());
+        self
+    }
+
+    /// Set the timeout of the connection phase of each request (default: none).
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout of each request, from connecting until the response
+    /// body is read (default: 30 seconds).
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Send every request through a proxy.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Add a header sent with every request. Can be called several times.
+    pub fn default_header(mut self, name: &str, value: &str) -> Self {
+        self.default_headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Replace the User-Agent header (default: `SDK_USER_AGENT`).
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Use an already configured HTTP client. Cannot be combined with the
+    /// timeouts, proxy, default headers and user agent of this builder.
+    pub fn client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Send the requests through a custom transport (see `Transport`). Cannot
+    /// be combined with `client` nor with the options of the HTTP client.
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Set the policy used to retry failed requests (default: no retries).
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Set the client-side rate limiter (default: no throttling).
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Check the syntax of addresses locally before validating them
+    /// (default: false). See `ZeroBounce::set_precheck`.
+    pub fn precheck(mut self, precheck: bool) -> Self {
+        self.precheck = precheck;
+        self
+    }
+
+    /// Cache validation results (default: no caching). See
+    /// `ZeroBounce::set_cache`.
+    pub fn cache(mut self, cache: ValidationCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Read and save validation results in a store (default: none). See
+    /// `ZeroBounce::set_store`.
+    pub fn store(mut self, store: Arc<dyn ValidationStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Limit the credits spent by the client (default: no limit). See
+    /// `ZeroBounce::set_credit_guard`.
+    pub fn credit_guard(mut self, credit_guard: CreditGuard) -> Self {
+        self.credit_guard = Some(credit_guard);
+        self
+    }
+
+    /// Check the configuration and create the client.
+    pub fn build(mut self) -> ZBResult<ZeroBounce> {
+        let api_key = self.api_key.take().unwrap_or_default().trim().to_string();
+        if api_key.is_empty() {
+            return Err(ZBError::validation("api_key is mandatory and cannot be empty"));
+        }
+
+        let base_url = self.base_url.take()
+            .unwrap_or_else(|| ApiBaseUrl::Default.as_str().to_string());
+        let bulk_url = self.bulk_url.take().unwrap_or_else(|| BULK_URI.to_string());
+        check_url("base_url", &base_url)?;
+        check_url("bulk_url", &bulk_url)?;
+
+        let retry_policy = self.retry_policy.take();
+        let rate_limiter = self.rate_limiter.take();
+        let precheck = self.precheck;
+        let cache = self.cache.take();
+        let store = self.store.take();
+        let credit_guard = self.credit_guard.take();
+        let transport = self.build_transport()?;
+
+        Ok(ZeroBounce {
+            api_key,
+            base_url: base_url.clone(),
+            transport,
+            url_provider: ZBUrlProvider { url: base_url, bulk_url },
+            retry_policy,
+            rate_limiter,
+            precheck,
+            cache,
+            store,
+            credit_guard,
+        })
+    }
+
+    fn has_client_options(&self) -> bool {
+        self.connect_timeout.is_some()
+            || self.read_timeout.is_some()
+            || self.proxy.is_some()
+            || !self.default_headers.is_empty()
+            || self.user_agent.is_some()
+    }
+
+    fn build_transport(mut self) -> ZBResult<Arc<dyn Transport>> {
+        match (self.transport.take(), self.client.take()) {
+            (Some(_), Some(_)) => {
+                Err(ZBError::validation("a custom transport and a custom client cannot be set together"))
+            }
+            (Some(_), None) | (None, Some(_)) if self.has_client_options() => Err(ZBError::validation(
+                "timeouts, proxy, default headers and user agent cannot be set along with a custom client or transport"
+            )),
+            (Some(transport), None) => Ok(transport),
+            (None, Some(client)) => Ok(Arc::new(ReqwestTransport::new(client))),
+            (None, None) => Ok(Arc::new(ReqwestTransport::new(self.build_client()?))),
+        }
+    }
+
+    fn build_client(self) -> ZBResult<reqwest::blocking::Client> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.default_headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| ZBError::Validation(format!("invalid header name: {}", name)))?;
+            let header_value = HeaderValue::from_str(value)
+                .map_err(|_| ZBError::Validation(format!("invalid value of header {}", name)))?;
+            headers.append(header_name, header_value);
+        }
+
+        let user_agent = self.user_agent.as_deref().unwrap_or(SDK_USER_AGENT);
+        let mut client_builder = reqwest::blocking::Client::builder()
+            .user_agent(user_agent)
+            .default_headers(headers)
+            .connect_timeout(self.connect_timeout);
+        if let Some(read_timeout) = self.read_timeout {
+            client_builder = client_builder.timeout(read_timeout);
+        }
+        if let Some(proxy) = self.proxy {
+            client_builder = client_builder.proxy(proxy);
+        }
+
+        Ok(client_builder.build()?)
+    }
+}
+
+// Only absolute http(s) URLs can be used as base of the requests.
+fn check_url(name: &str, url: &str) -> ZBResult<()> {
+    let parsed = Url::parse(url)
+        .map_err(|error| ZBError::Validation(format!("invalid {} {:?}: {}", name, url, error)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(ZBError::Validation(format!("{} must be an http(s) URL, got {:?}", name, url)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_rejects_invalid_configuration() {
+        let error = ZeroBounceBuilder::new().build().err().unwrap();
+        assert!(matches!(error, ZBError::Validation(_)), "{:?}", error);
+
+        let error = ZeroBounceBuilder::new().api_key("  ").build().err().unwrap();
+        assert!(matches!(error, ZBError::Validation(_)), "{:?}", error);
+
+        let error = ZeroBounceBuilder::new().api_key("key").base_url("not a url").build().err().unwrap();
+        assert!(matches!(error, ZBError::Validation(ref msg) if msg.contains("base_url")), "{:?}", error);
+
+        let error = ZeroBounceBuilder::new().api_key("key").bulk_url("ftp://example.com").build().err().unwrap();
+        assert!(matches!(error, ZBError::Validation(ref msg) if msg.contains("bulk_url")), "{:?}", error);
+
+        let error = ZeroBounceBuilder::new().api_key("key").default_header("bad header", "x").build().err().unwrap();
+        assert!(matches!(error, ZBError::Validation(_)), "{:?}", error);
+
+        let error = ZeroBounceBuilder::new()
+            .api_key("key")
+            .client(reqwest::blocking::Client::new())
+            .read_timeout(Duration::from_secs(1))
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(error, ZBError::Validation(_)), "{:?}", error);
+
+        let error = ZeroBounceBuilder::new()
+            .api_key("key")
+            .client(reqwest::blocking::Client::new())
+            .transport(Arc::new(ReqwestTransport::default()))
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(error, ZBError::Validation(_)), "{:?}", error);
+    }
+
+    #[test]
+    fn test_build_defaults() {
+        let zb = ZeroBounceBuilder::new().api_key("key").build().unwrap();
+        assert_eq!(zb.api_key, "key");
+        assert_eq!(zb.base_url, ApiBaseUrl::Default.as_str());
+        assert_eq!(zb.url_provider.bulk_url, BULK_URI);
+        assert!(zb.retry_policy.is_none());
+        assert!(zb.rate_limiter.is_none());
+
+        let zb = ZeroBounceBuilder::new()
+            .api_key("key")
+            .base_url(ApiBaseUrl::USA)
+            .retry_policy(RetryPolicy::new(2))
+            .build()
+            .unwrap();
+        assert_eq!(zb.url_provider.url, ApiBaseUrl::USA.as_str());
+        assert_eq!(zb.retry_policy, Some(RetryPolicy::new(2)));
+    }
+}
@@ -1,19 +1,44 @@
 pub mod api;
+#[cfg(feature = "async")]
+pub mod async_api;
+pub mod builder;
+pub mod store;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod transport;
 pub mod utility;
+#[cfg(feature = "webhook")]
+pub mod webhook;
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
-pub use crate::utility::{ZBError, ZBResult, ApiBaseUrl};
+use reqwest::header::RETRY_AFTER;
+
+pub use crate::utility::{ZBError, ZBResult, ApiBaseUrl, RetryPolicy};
+pub use crate::utility::{EndpointFamily, RateLimit, RateLimitMode, RateLimiter};
+pub use crate::utility::{CacheStats, CreditGuard, ValidationCache};
+use crate::utility::retry::parse_retry_after;
+use crate::store::ValidationStore;
+use crate::transport::{ReqwestTransport, Transport, ZBRequest, ZBResponse};
 pub use crate::utility::structures::{ActivityData, ApiUsage};
 pub use crate::utility::structures::bulk::{
-    zb_download_type, ZBFile, ZBFileFeedback, ZBFileStatus, ZBBulkResponse, ZBGetFileOptions,
+    zb_download_type, ZBFile, ZBFileFeedback, ZBFileStatus, ZBTypedFileStatus, ZBBulkResponse, ZBGetFileOptions,
 };
+pub use crate::utility::structures::bulk_inspection::{ZBFileInspection, ZBFileIssue};
+pub use crate::utility::structures::bulk_split::ZBSplitLimits;
+pub use crate::utility::structures::bulk_records::{ZBFileBuilder, ZBFileRecord};
 pub use crate::utility::{format_get_file_error_message, get_file_json_indicates_error};
-pub use crate::utility::structures::validate_enums::{ZBValidateStatus, ZBValidateSubStatus};
+pub use crate::utility::structures::validate_enums::{ZBConfidence, ZBFileProcessingStatus, ZBValidateStatus, ZBValidateSubStatus};
 pub use crate::api::{FindEmailV2Builder, DomainSearchV2Builder};
+pub use crate::api::bulk_job::{BulkJob, BulkJobKind, BulkJobOutcome, BulkJobState, BulkSplitOutcome};
+pub use crate::builder::ZeroBounceBuilder;
+#[cfg(feature = "async")]
+pub use crate::async_api::{ZeroBounceAsync, FindEmailV2AsyncBuilder, DomainSearchV2AsyncBuilder};
 
 // Structure meant to generate the URLs to be accessed with the HTTP requests
 // based on the base API URLs (for the base API and bulk API).
+#[derive(Clone, Debug)]
 pub struct ZBUrlProvider {
     pub url: String,
     pub bulk_url: String,
@@ -37,12 +62,30 @@ impl Default for ZBUrlProvider {
     }
 }
 
-// Client offering methods for different API methods and functionalities
+// Client offering methods for different API methods and functionalities.
+//
+// Clones share the same transport (and its connection pool) and rate limiter.
+#[derive(Clone)]
 pub struct ZeroBounce {
     pub api_key: String,
     pub base_url: String,
-    pub client: reqwest::blocking::Client,
+    /// Sends the HTTP requests; `ReqwestTransport` unless configured otherwise.
+    pub transport: Arc<dyn Transport>,
     pub url_provider: ZBUrlProvider,
+    /// Retry policy of every request; `None` sends each request once.
+    pub retry_policy: Option<RetryPolicy>,
+    /// Client-side rate limiter; `None` does not throttle requests.
+    pub rate_limiter: Option<RateLimiter>,
+    /// Check the syntax of addresses locally before validating them (see
+    /// `utility::precheck`).
+    pub precheck: bool,
+    /// Cache of validation results; `None` always sends the request.
+    pub cache: Option<ValidationCache>,
+    /// Store of validation results shared with other clients and processes
+    /// (see `store`); `None` always sends the request.
+    pub store: Option<Arc<dyn ValidationStore>>,
+    /// Budget of the credits spent by the client; `None` does not limit them.
+    pub credit_guard: Option<CreditGuard>,
 }
 
 // More method implementations of this class can be found throughout
@@ -89,24 +132,170 @@ impl ZeroBounce {
     {
         let base_url_string = base_url.into();
         
-        let mut url_provider = ZBUrlProvider::default();
-        url_provider.url = base_url_string.clone();
+        let url_provider = ZBUrlProvider {
+            url: base_url_string.clone(),
+            ..Default::default()
+        };
         
         ZeroBounce {
             api_key: api_key.to_string(),
             base_url: base_url_string,
-            client: reqwest::blocking::Client::default(),
+            transport: Arc::new(ReqwestTransport::default()),
             url_provider,
+            retry_policy: None,
+            rate_limiter: None,
+            precheck: false,
+            cache: None,
+            store: None,
+            credit_guard: None,
+        }
+    }
+
+    /// Start configuring a client: timeouts, proxy, headers, custom HTTP
+    /// client, etc. See `ZeroBounceBuilder`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use zero_bounce::ZeroBounce;
+    /// use zero_bounce::utility::ZBResult;
+    ///
+    /// # fn main() -> ZBResult<()> {
+    /// let zb = ZeroBounce::builder()
+    ///     .api_key("your_api_key")
+    ///     .read_timeout(Duration::from_secs(10))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> ZeroBounceBuilder {
+        ZeroBounceBuilder::new()
+    }
+
+    /// Set the policy used to retry failed requests (optional; default `None`, no retries).
+    pub fn set_retry_policy(mut self, retry_policy: Option<RetryPolicy>) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the client-side rate limiter (optional; default `None`, no throttling).
+    pub fn set_rate_limiter(mut self, rate_limiter: Option<RateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Check the syntax of addresses before `validate_email` and
+    /// `batch_validate` (default: false). Addresses failing the check get a
+    /// local `ZBValidation` (see `ZBValidation::failed_syntax_check`) and no
+    /// request, hence no credit, is spent on them.
+    pub fn set_precheck(mut self, precheck: bool) -> Self {
+        self.precheck = precheck;
+        self
+    }
+
+    /// Answer `validate_email` and `batch_validate` from a cache of previous
+    /// results when possible (optional; default `None`, no caching). See
+    /// `ValidationCache`.
+    pub fn set_cache(mut self, cache: Option<ValidationCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Read validation results from a store before sending requests, and
+    /// save the results received into it (optional; default `None`). The
+    /// cache, when set, is looked up first. See `store::ValidationStore`.
+    pub fn set_store(mut self, store: Option<Arc<dyn ValidationStore>>) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Refuse the calls that would exceed a credit budget (optional; default
+    /// `None`, no limit). See `CreditGuard`.
+    pub fn set_credit_guard(mut self, credit_guard: Option<CreditGuard>) -> Self {
+        self.credit_guard = credit_guard;
+        self
+    }
+
+    // Take a token from the rate limiter, when the request belongs to a
+    // throttled endpoint family.
+    fn acquire_rate_limit(&self, family: Option<EndpointFamily>) -> ZBResult<()> {
+        match (&self.rate_limiter, family) {
+            (Some(rate_limiter), Some(family)) => rate_limiter.acquire(family),
+            _ => Ok(()),
+        }
+    }
+
+    // Reserve the credits of a call with the credit guard before making it,
+    // and give them back when it fails.
+    fn spend_credits<T, F>(&self, cost: u64, call: F) -> ZBResult<T>
+    where
+        F: FnOnce() -> ZBResult<T>,
+    {
+        let Some(credit_guard) = &self.credit_guard else {
+            return call();
+        };
+        credit_guard.reserve(cost, || self.get_credits())?;
+        call().inspect_err(|_| credit_guard.release(cost))
+    }
+
+    // Send the request produced by `build_request`, retrying according to the
+    // retry policy. The request is rebuilt on each attempt, so that bodies
+    // that can only be sent once (e.g. streamed files) are regenerated.
+    //
+    // Only successful responses are returned; any other status is turned into
+    // the matching `ZBError`. Each attempt counts against the rate budget of
+    // the endpoint family.
+    fn send_request<F>(&self, family: Option<EndpointFamily>, build_request: F) -> ZBResult<ZBResponse>
+    where
+        F: Fn() -> ZBResult<ZBRequest>,
+    {
+        let policy = match &self.retry_policy {
+            Some(policy) if policy.max_attempts > 1 => policy,
+            _ => return self.send_request_once(family, build_request()?),
+        };
+
+        let mut failures = Vec::new();
+        for attempt in 1..=policy.max_attempts {
+            self.acquire_rate_limit(family)?;
+            let error = match self.transport.send(build_request()?) {
+                Ok(response) => match error_for_status(response) {
+                    Ok(response) => return Ok(response),
+                    Err(error) => error,
+                },
+                Err(error) => error,
+            };
+
+            if !policy.should_retry(&error) {
+                return Err(error);
+            }
+            let retry_after = error.retry_after();
+            failures.push(error);
+
+            if attempt < policy.max_attempts {
+                std::thread::sleep(policy.backoff(attempt, retry_after));
+            }
         }
+
+        Err(ZBError::RetriesExhausted(failures))
+    }
+
+    // Send a request without retrying it, e.g. when its body cannot be
+    // built again.
+    fn send_request_once(&self, family: Option<EndpointFamily>, request: ZBRequest) -> ZBResult<ZBResponse> {
+        self.acquire_rate_limit(family)?;
+        error_for_status(self.transport.send(request)?)
     }
 
-    fn generic_get_request<'a>(&'a self, url: String, mut query_args: HashMap<&'a str, &'a str>) -> ZBResult<String> {
+    fn generic_get_request<'a>(
+        &'a self,
+        url: String,
+        mut query_args: HashMap<&'a str, &'a str>,
+        family: Option<EndpointFamily>,
+    ) -> ZBResult<String> {
         // Automatically add api_key to query arguments
         query_args.insert("api_key", self.api_key.as_str());
         
-        let response = self.client.get(url).query(&query_args).send()?;
-
-        let response_ok = response.status().is_success();
+        let response = self.send_request(family, || Ok(ZBRequest::get(&url).query(&query_args)))?;
         let response_content = response.text()?;
 
         // Debug: Print raw response to examine structure in debug mode
@@ -115,10 +304,25 @@ impl ZeroBounce {
             eprintln!("Raw API response: {}", response_content);
         }
 
-        if !response_ok {
-            return Err(ZBError::ExplicitError(response_content));
-        }
-
         Ok(response_content)
     }
 }
+
+// Turn a response with an unsuccessful status into the matching error.
+fn error_for_status(response: ZBResponse) -> ZBResult<ZBResponse> {
+    if response.is_success() {
+        return Ok(response);
+    }
+
+    let status = response.status;
+    let retry_after = parse_retry_after(response.headers.get(RETRY_AFTER));
+    let body = response.text().unwrap_or_default();
+
+    // Debug: Print raw response to examine structure in debug mode
+    #[cfg(debug_assertions)]
+    {
+        eprintln!("Raw API response: {}", body);
+    }
+
+    Err(ZBError::from_response(status, body, retry_after))
+}
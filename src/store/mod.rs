@@ -0,0 +1,30 @@
+//! Stores of validation results shared beyond one client.
+//!
+//! A `ValidationStore` attached with `ZeroBounce::set_store` is read before
+//! `validate_email` and `batch_validate` send a request, and receives every
+//! result they get from the API. Unlike `ValidationCache`, a store is meant
+//! to outlive the process: `SqliteStore` (cargo feature `sqlite`) keeps the
+//! results in a SQLite database that several processes can use at once.
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+use crate::utility::structures::validation::ZBValidation;
+use crate::utility::ZBResult;
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+
+/// Read-through store of validation results.
+///
+/// Failures should be reported as `ZBError::StoreError`; they fail the
+/// validation call that hit them.
+pub trait ValidationStore: Send + Sync {
+    /// Result of an address recent enough to be reused instead of validating
+    /// it again, if any.
+    fn get(&self, address: &str) -> ZBResult<Option<ZBValidation>>;
+
+    /// Save results received from the API, replacing older results of the
+    /// same addresses.
+    fn put(&self, validations: &[ZBValidation]) -> ZBResult<()>;
+}
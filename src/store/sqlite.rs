@@ -0,0 +1,366 @@
+//! `ValidationStore` backed by a SQLite database.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::store::ValidationStore;
+use crate::utility::structures::validation::ZBValidation;
+use crate::utility::ZBResult;
+
+/// Default age limit of the results returned by `ValidationStore::get`.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 3600);
+
+// How long a statement waits for another connection (possibly of another
+// process) to release its lock before failing.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Sortable as text, so that the `processed_at` column can be compared and
+// indexed as is
+const PROCESSED_AT_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.6f";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS zb_validations (
+        email_key TEXT PRIMARY KEY NOT NULL,
+        address TEXT NOT NULL,
+        status TEXT NOT NULL,
+        sub_status TEXT NOT NULL,
+        free_email INTEGER NOT NULL,
+        did_you_mean TEXT,
+        account TEXT,
+        domain TEXT,
+        domain_age_days TEXT,
+        smtp_provider TEXT,
+        catchall_domain INTEGER,
+        mx_record TEXT,
+        mx_found TEXT,
+        firstname TEXT,
+        lastname TEXT,
+        gender TEXT,
+        country TEXT,
+        region TEXT,
+        city TEXT,
+        zipcode TEXT,
+        processed_at TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS zb_validations_processed_at ON zb_validations (processed_at);
+";
+
+const COLUMNS: &str = "address, status, sub_status, free_email, did_you_mean, account, domain, \
+    domain_age_days, smtp_provider, catchall_domain, mx_record, mx_found, firstname, lastname, \
+    gender, country, region, city, zipcode, processed_at";
+
+// Results of the API are only replaced by more recent ones, whatever the
+// order in which concurrent writers save them
+const UPSERT: &str = "
+    INSERT INTO zb_validations (email_key, address, status, sub_status, free_email, did_you_mean,
+        account, domain, domain_age_days, smtp_provider, catchall_domain, mx_record, mx_found,
+        firstname, lastname, gender, country, region, city, zipcode, processed_at)
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)
+    ON CONFLICT (email_key) DO UPDATE SET
+        address = excluded.address, status = excluded.status, sub_status = excluded.sub_status,
+        free_email = excluded.free_email, did_you_mean = excluded.did_you_mean,
+        account = excluded.account, domain = excluded.domain,
+        domain_age_days = excluded.domain_age_days, smtp_provider = excluded.smtp_provider,
+        catchall_domain = excluded.catchall_domain, mx_record = excluded.mx_record,
+        mx_found = excluded.mx_found, firstname = excluded.firstname,
+        lastname = excluded.lastname, gender = excluded.gender, country = excluded.country,
+        region = excluded.region, city = excluded.city, zipcode = excluded.zipcode,
+        processed_at = excluded.processed_at
+    WHERE excluded.processed_at >= zb_validations.processed_at
+";
+
+/// Validation results saved in a SQLite database, keyed on the normalized
+/// address (trimmed and lowercased), along with their `processed_at` date.
+///
+/// The database is opened in WAL mode: any number of processes can read it
+/// while one of them writes, and writers wait for each other (up to 30
+/// seconds) instead of failing. Clones share the same connection.
+///
+/// As a `ValidationStore`, it returns the results processed less than
+/// `max_age` ago (default: `DEFAULT_MAX_AGE`, 30 days).
+///
+/// # Example
+/// ```no_run
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use zero_bounce::ZeroBounce;
+/// use zero_bounce::store::SqliteStore;
+/// use zero_bounce::utility::ZBResult;
+///
+/// # fn main() -> ZBResult<()> {
+/// let store = SqliteStore::open("validations.sqlite")?
+///     .set_max_age(Duration::from_secs(7 * 24 * 3600));
+/// let zb = ZeroBounce::new("your_api_key").set_store(Some(Arc::new(store.clone())));
+///
+/// let validation = zb.validate_email("valid@example.com")?;
+/// let last_day = chrono::Utc::now().naive_utc() - chrono::Duration::days(1);
+/// println!("{} results since yesterday", store.validated_since(last_day)?.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SqliteStore {
+    connection: Arc<Mutex<Connection>>,
+    max_age: Duration,
+}
+
+impl SqliteStore {
+    /// Open the database at `path`, creating it if needed.
+    pub fn open<P: AsRef<Path>>(path: P) -> ZBResult<SqliteStore> {
+        let connection = Connection::open(path)?;
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+        // Returns the resulting mode; in-memory databases stay in "memory"
+        connection.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+        connection.pragma_update(None, "synchronous", "NORMAL")?;
+        Self::from_connection(connection)
+    }
+
+    /// Open a private database that only lives as long as the store.
+    pub fn open_in_memory() -> ZBResult<SqliteStore> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(connection: Connection) -> ZBResult<SqliteStore> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(SqliteStore {
+            connection: Arc::new(Mutex::new(connection)),
+            max_age: DEFAULT_MAX_AGE,
+        })
+    }
+
+    /// Set the age limit of the results reused by the client.
+    pub fn set_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Result of an address, whatever its age.
+    pub fn get_latest(&self, address: &str) -> ZBResult<Option<ZBValidation>> {
+        let query = format!("SELECT {} FROM zb_validations WHERE email_key = ?1", COLUMNS);
+        let connection = self.lock_connection();
+        let mut statement = connection.prepare_cached(&query)?;
+        Ok(statement.query_row([normalize(address)], read_validation).optional()?)
+    }
+
+    /// Result of an address processed at or after `since`.
+    pub fn get_since(&self, address: &str, since: NaiveDateTime) -> ZBResult<Option<ZBValidation>> {
+        let query = format!(
+            "SELECT {} FROM zb_validations WHERE email_key = ?1 AND processed_at >= ?2", COLUMNS
+        );
+        let connection = self.lock_connection();
+        let mut statement = connection.prepare_cached(&query)?;
+        let validation = statement
+            .query_row(params![normalize(address), format_processed_at(&since)], read_validation)
+            .optional()?;
+        Ok(validation)
+    }
+
+    /// Every result processed at or after `since`, oldest first.
+    pub fn validated_since(&self, since: NaiveDateTime) -> ZBResult<Vec<ZBValidation>> {
+        let query = format!(
+            "SELECT {} FROM zb_validations WHERE processed_at >= ?1 ORDER BY processed_at", COLUMNS
+        );
+        let connection = self.lock_connection();
+        let mut statement = connection.prepare_cached(&query)?;
+        let rows = statement.query_map([format_processed_at(&since)], read_validation)?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Save results in one transaction. A result older than the one already
+    /// stored for its address is ignored, and so are the results of the
+    /// syntax pre-check.
+    pub fn insert(&self, validations: &[ZBValidation]) -> ZBResult<()> {
+        let mut connection = self.lock_connection();
+        let transaction = connection.transaction()?;
+        {
+            let mut statement = transaction.prepare_cached(UPSERT)?;
+            for validation in validations.iter().filter(|validation| !validation.is_precheck_result()) {
+                statement.execute(params![
+                    normalize(&validation.address),
+                    validation.address,
+                    validation.status,
+                    validation.sub_status,
+                    validation.free_email,
+                    validation.did_you_mean,
+                    validation.account,
+                    validation.domain,
+                    validation.domain_age_days,
+                    validation.smtp_provider,
+                    validation.catchall_domain,
+                    validation.mx_record,
+                    validation.mx_found,
+                    validation.firstname,
+                    validation.lastname,
+                    validation.gender,
+                    validation.country,
+                    validation.region,
+                    validation.city,
+                    validation.zipcode,
+                    format_processed_at(&validation.processed_at),
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Remove the result of an address; returns whether one was stored.
+    pub fn remove(&self, address: &str) -> ZBResult<bool> {
+        let connection = self.lock_connection();
+        let removed = connection.execute("DELETE FROM zb_validations WHERE email_key = ?1", [normalize(address)])?;
+        Ok(removed > 0)
+    }
+
+    /// Remove the results processed before `before`; returns how many were.
+    pub fn purge_before(&self, before: NaiveDateTime) -> ZBResult<usize> {
+        let connection = self.lock_connection();
+        let removed = connection.execute(
+            "DELETE FROM zb_validations WHERE processed_at < ?1", [format_processed_at(&before)]
+        )?;
+        Ok(removed)
+    }
+
+    /// Number of stored results.
+    pub fn len(&self) -> ZBResult<usize> {
+        let connection = self.lock_connection();
+        let count = connection.query_row("SELECT COUNT(*) FROM zb_validations", [], |row| row.get::<_, i64>(0))?;
+        Ok(count as usize)
+    }
+
+    pub fn is_empty(&self) -> ZBResult<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    fn lock_connection(&self) -> MutexGuard<'_, Connection> {
+        // transactions roll back when dropped, a panic leaves nothing half written
+        self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ValidationStore for SqliteStore {
+    fn get(&self, address: &str) -> ZBResult<Option<ZBValidation>> {
+        let max_age = chrono::Duration::from_std(self.max_age).unwrap_or(chrono::Duration::MAX);
+        match Utc::now().naive_utc().checked_sub_signed(max_age) {
+            Some(since) => self.get_since(address, since),
+            None => self.get_latest(address),
+        }
+    }
+
+    fn put(&self, validations: &[ZBValidation]) -> ZBResult<()> {
+        self.insert(validations)
+    }
+}
+
+fn normalize(address: &str) -> String {
+    address.trim().to_lowercase()
+}
+
+fn format_processed_at(processed_at: &NaiveDateTime) -> String {
+    processed_at.format(PROCESSED_AT_FORMAT).to_string()
+}
+
+// Columns in the order of `COLUMNS`.
+fn read_validation(row: &Row<'_>) -> rusqlite::Result<ZBValidation> {
+    let processed_at = row.get::<_, String>(19)?;
+    let processed_at = NaiveDateTime::parse_from_str(&processed_at, PROCESSED_AT_FORMAT)
+        .map_err(|error| rusqlite::Error::FromSqlConversionFailure(19, rusqlite::types::Type::Text, Box::new(error)))?;
+
+    Ok(ZBValidation {
+        address: row.get(0)?,
+        status: row.get(1)?,
+        sub_status: row.get(2)?,
+        free_email: row.get(3)?,
+        did_you_mean: row.get(4)?,
+        account: row.get(5)?,
+        domain: row.get(6)?,
+        domain_age_days: row.get(7)?,
+        smtp_provider: row.get(8)?,
+        catchall_domain: row.get(9)?,
+        mx_record: row.get(10)?,
+        mx_found: row.get(11)?,
+        firstname: row.get(12)?,
+        lastname: row.get(13)?,
+        gender: row.get(14)?,
+        country: row.get(15)?,
+        region: row.get(16)?,
+        city: row.get(17)?,
+        zipcode: row.get(18)?,
+        processed_at,
+        precheck_error: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utility::mock_constants::VALIDATION_RESPONSE_VALID;
+    use crate::utility::precheck::PrecheckError;
+
+    fn validation(address: &str, status: &str, processed_at: &str) -> ZBValidation {
+        let mut validation = serde_json::from_str::<ZBValidation>(VALIDATION_RESPONSE_VALID).unwrap();
+        validation.address = address.to_string();
+        validation.status = status.to_string();
+        validation.processed_at = NaiveDateTime::parse_from_str(processed_at, "%Y-%m-%d %H:%M:%S").unwrap();
+        validation
+    }
+
+    #[test]
+    fn test_round_trip_and_newest_result_wins() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        let mut first = validation("John@Example.com", "valid", "2024-01-02 03:04:05");
+        first.country = Some("United States".to_string());
+        first.catchall_domain = Some(false);
+        store.insert(&[first.clone()]).unwrap();
+
+        let stored = store.get_latest(" john@example.COM").unwrap().unwrap();
+        assert_eq!(stored.address, "John@Example.com");
+        assert_eq!(stored.country, first.country);
+        assert_eq!(stored.catchall_domain, Some(false));
+        assert_eq!(stored.free_email, first.free_email);
+        assert_eq!(stored.processed_at, first.processed_at);
+
+        // an older result does not replace a newer one
+        store.insert(&[validation("john@example.com", "invalid", "2023-12-31 00:00:00")]).unwrap();
+        assert_eq!(store.get_latest("john@example.com").unwrap().unwrap().status, "valid");
+        store.insert(&[validation("john@example.com", "invalid", "2024-02-01 00:00:00")]).unwrap();
+        assert_eq!(store.get_latest("john@example.com").unwrap().unwrap().status, "invalid");
+        assert_eq!(store.len().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_validated_since() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        store.insert(&[
+            validation("new@example.com", "valid", "2024-03-01 00:00:00"),
+            validation("old@example.com", "valid", "2024-01-01 00:00:00"),
+            ZBValidation::failed_syntax_check("not-an-email", PrecheckError::MissingAt),
+        ]).unwrap();
+        assert_eq!(store.len().unwrap(), 2);
+
+        let since = NaiveDateTime::parse_from_str("2024-02-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let recent = store.validated_since(since).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].address, "new@example.com");
+        assert!(store.get_since("old@example.com", since).unwrap().is_none());
+        assert!(store.get_latest("old@example.com").unwrap().is_some());
+
+        assert_eq!(store.purge_before(since).unwrap(), 1);
+        assert!(store.remove("new@example.com").unwrap());
+        assert!(store.is_empty().unwrap());
+    }
+
+    #[test]
+    fn test_get_honors_max_age() {
+        let store = SqliteStore::open_in_memory().unwrap().set_max_age(Duration::from_secs(3600));
+        let mut recent = validation("recent@example.com", "valid", "2024-01-01 00:00:00");
+        recent.processed_at = Utc::now().naive_utc() - chrono::Duration::minutes(5);
+        store.insert(&[recent, validation("old@example.com", "valid", "2024-01-01 00:00:00")]).unwrap();
+
+        assert!(ValidationStore::get(&store, "recent@example.com").unwrap().is_some());
+        assert!(ValidationStore::get(&store, "old@example.com").unwrap().is_none());
+    }
+}
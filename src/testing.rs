@@ -0,0 +1,791 @@
+//! Fake ZeroBounce API for the tests of applications (cargo feature
+//! `testing`).
+//!
+//! `FakeZeroBounce` is a local HTTP server answering every endpoint called
+//! by the crate, with a deterministic state: an account balance spent by the
+//! validations, files progressing from `Queued` to `Complete` over time, and
+//! the sandbox addresses of the API returning their documented statuses.
+//! Other addresses are `valid` when their syntax is, `invalid` /
+//! `failed_syntax_check` otherwise.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use reqwest::Url;
+use serde_json::{json, Value};
+use tiny_http::{Header, Request, Response, Server};
+
+use crate::utility::precheck::check_email;
+use crate::utility::{ZBError, ZBResult, BATCH_VALIDATE_MAX_EMAILS, CONTENT_TYPE_JSON, CONTENT_TYPE_STREAM};
+use crate::utility::{ENDPOINT_ACTIVITY_DATA, ENDPOINT_API_USAGE, ENDPOINT_BATCH_VALIDATE, ENDPOINT_CREDITS};
+use crate::utility::{ENDPOINT_EMAIL_FINDER, ENDPOINT_VALIDATE};
+use crate::utility::{ENDPOINT_FILE_DELETE, ENDPOINT_FILE_RESULT, ENDPOINT_FILE_SEND, ENDPOINT_FILE_STATUS};
+use crate::utility::{ENDPOINT_SCORING_DELETE, ENDPOINT_SCORING_RESULT, ENDPOINT_SCORING_SEND, ENDPOINT_SCORING_STATUS};
+use crate::utility::{S_ABUSE, S_CATCH_ALL, S_DO_NOT_MAIL, S_INVALID, S_SPAMTRAP, S_UNKNOWN, S_VALID};
+use crate::utility::{SS_ALIAS_ADDRESS, SS_ANTISPAM_SYSTEM, SS_DISPOSABLE, SS_DOES_NOT_ACCEPT_MAIL};
+use crate::utility::{SS_EXCEPTION_OCCURRED, SS_FAILED_SMTP_CONNECTION, SS_FAILED_SYNTAX_CHECK, SS_FORCIBLE_DISCONNECT};
+use crate::utility::{SS_GLOBAL_SUPPRESSION, SS_GREYLISTED, SS_LEADING_PERIOD_REMOVED, SS_MAILBOX_NOT_FOUND};
+use crate::utility::{SS_MAILBOX_QUOTA_EXCEEDED, SS_MAIL_SERVER_DID_NOT_RESPOND, SS_MAIL_SERVER_TEMPORARY_ERROR};
+use crate::utility::{SS_NO_DNS_ENTRIES, SS_POSSIBLE_TRAP, SS_POSSIBLE_TYPO, SS_ROLE_BASED, SS_ROLE_BASED_CATCH_ALL};
+use crate::utility::{SS_TIMEOUT_EXCEEDED, SS_TOXIC, SS_UNROUTABLE_IP_ADDRESS};
+use crate::ZeroBounce;
+
+/// API key accepted by a `FakeZeroBounce`; requests made with any other key
+/// fail with `ZBError::InvalidApiKey`.
+pub const FAKE_API_KEY: &str = "fake-api-key";
+
+/// Balance of a new `FakeZeroBounce`.
+pub const DEFAULT_FAKE_CREDITS: i64 = 100_000;
+
+/// Time a `FakeZeroBounce` takes by default to process a file.
+pub const DEFAULT_PROCESSING_TIME: Duration = Duration::from_secs(1);
+
+/// Sandbox addresses of the API with their status and sub-status. Validating
+/// them costs no credit.
+pub const SANDBOX_ADDRESSES: &[(&str, &str, &str)] = &[
+    ("valid@example.com", S_VALID, ""),
+    ("invalid@example.com", S_INVALID, SS_MAILBOX_NOT_FOUND),
+    ("catch_all@example.com", S_CATCH_ALL, ""),
+    ("unknown@example.com", S_UNKNOWN, SS_MAIL_SERVER_TEMPORARY_ERROR),
+    ("spamtrap@example.com", S_SPAMTRAP, ""),
+    ("abuse@example.com", S_ABUSE, ""),
+    ("donotmail@example.com", S_DO_NOT_MAIL, SS_ROLE_BASED),
+    ("free_email@example.com", S_VALID, ""),
+    ("alias@example.com", S_VALID, SS_ALIAS_ADDRESS),
+    ("leading_period_removed@example.com", S_VALID, SS_LEADING_PERIOD_REMOVED),
+    ("antispam_system@example.com", S_UNKNOWN, SS_ANTISPAM_SYSTEM),
+    ("exception_occurred@example.com", S_UNKNOWN, SS_EXCEPTION_OCCURRED),
+    ("failed_smtp_connection@example.com", S_UNKNOWN, SS_FAILED_SMTP_CONNECTION),
+    ("forcible_disconnect@example.com", S_UNKNOWN, SS_FORCIBLE_DISCONNECT),
+    ("greylisted@example.com", S_UNKNOWN, SS_GREYLISTED),
+    ("mail_server_did_not_respond@example.com", S_UNKNOWN, SS_MAIL_SERVER_DID_NOT_RESPOND),
+    ("mail_server_temporary_error@example.com", S_UNKNOWN, SS_MAIL_SERVER_TEMPORARY_ERROR),
+    ("timeout_exceeded@example.com", S_UNKNOWN, SS_TIMEOUT_EXCEEDED),
+    ("does_not_accept_mail@example.com", S_INVALID, SS_DOES_NOT_ACCEPT_MAIL),
+    ("failed_syntax_check@example.com", S_INVALID, SS_FAILED_SYNTAX_CHECK),
+    ("mailbox_not_found@example.com", S_INVALID, SS_MAILBOX_NOT_FOUND),
+    ("mailbox_quota_exceeded@example.com", S_INVALID, SS_MAILBOX_QUOTA_EXCEEDED),
+    ("no_dns_entries@example.com", S_INVALID, SS_NO_DNS_ENTRIES),
+    ("possible_typo@example.com", S_INVALID, SS_POSSIBLE_TYPO),
+    ("unroutable_ip_address@example.com", S_INVALID, SS_UNROUTABLE_IP_ADDRESS),
+    ("disposable@example.com", S_DO_NOT_MAIL, SS_DISPOSABLE),
+    ("global_suppression@example.com", S_DO_NOT_MAIL, SS_GLOBAL_SUPPRESSION),
+    ("possible_trap@example.com", S_DO_NOT_MAIL, SS_POSSIBLE_TRAP),
+    ("role_based@example.com", S_DO_NOT_MAIL, SS_ROLE_BASED),
+    ("role_based_catch_all@example.com", S_DO_NOT_MAIL, SS_ROLE_BASED_CATCH_ALL),
+    ("toxic@example.com", S_DO_NOT_MAIL, SS_TOXIC),
+];
+
+// Status, sub-status and cost of an address.
+fn status_of(address: &str) -> (&'static str, &'static str, i64) {
+    let normalized = address.trim().to_lowercase();
+    if let Some((_, status, sub_status)) = SANDBOX_ADDRESSES.iter().find(|(sandbox, ..)| *sandbox == normalized) {
+        return (status, sub_status, 0);
+    }
+    match check_email(address.trim()) {
+        Ok(()) => (S_VALID, "", 1),
+        Err(_) => (S_INVALID, SS_FAILED_SYNTAX_CHECK, 1),
+    }
+}
+
+// Answer of the fake server, turned into a `tiny_http::Response`.
+struct FakeResponse {
+    status: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl FakeResponse {
+    fn json(value: Value) -> FakeResponse {
+        FakeResponse { status: 200, content_type: CONTENT_TYPE_JSON, body: value.to_string().into_bytes() }
+    }
+
+    fn error(message: &str) -> FakeResponse {
+        FakeResponse { status: 400, ..Self::json(json!({ "error": message })) }
+    }
+
+    fn file_error(message: &str) -> FakeResponse {
+        FakeResponse { status: 400, ..Self::json(json!({ "success": false, "message": message })) }
+    }
+
+    fn into_response(self) -> Response<Cursor<Vec<u8>>> {
+        let header = Header::from_bytes("Content-Type", self.content_type).expect("valid header");
+        Response::from_data(self.body).with_status_code(self.status).with_header(header)
+    }
+}
+
+// Request received by the fake server.
+struct FakeRequest {
+    // endpoint without the `/v2` or `/bulk/v2` prefix, e.g. "/validate"
+    endpoint: String,
+    query: HashMap<String, String>,
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+impl FakeRequest {
+    fn read(request: &mut Request) -> ZBResult<FakeRequest> {
+        let url = Url::parse(&format!("http://localhost{}", request.url()))
+            .map_err(|error| ZBError::ExplicitError(error.to_string()))?;
+        let mut path = url.path().to_string();
+        while path.contains("//") {
+            path = path.replace("//", "/");
+        }
+        let endpoint = ["/bulk/v2", "/v2"].iter()
+            .find_map(|prefix| path.strip_prefix(prefix))
+            .unwrap_or(&path)
+            .to_string();
+
+        let content_type = request.headers().iter()
+            .find(|header| header.field.equiv("Content-Type"))
+            .map(|header| header.value.as_str().to_string());
+        let mut body = Vec::new();
+        request.as_reader().read_to_end(&mut body)?;
+
+        Ok(FakeRequest {
+            endpoint,
+            query: url.query_pairs().map(|(name, value)| (name.into_owned(), value.into_owned())).collect(),
+            content_type,
+            body,
+        })
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.query.get(name).map(String::as_str).filter(|value| !value.is_empty())
+    }
+}
+
+#[derive(Debug)]
+struct FakeFile {
+    scoring: bool,
+    file_name: String,
+    rows: Vec<Vec<String>>,
+    header: Option<Vec<String>>,
+    email_index: usize,
+    remove_duplicate: bool,
+    return_url: Option<String>,
+    uploaded_at: DateTime<Utc>,
+    submitted_at: Instant,
+    processing_time: Duration,
+    reverse_results: bool,
+    deleted: bool,
+}
+
+impl FakeFile {
+    // Status and completion percentage: queued during the first fifth of
+    // the processing time, then processing.
+    fn progress(&self) -> (&'static str, u32) {
+        if self.deleted {
+            return ("Deleted", 0);
+        }
+        let elapsed = self.submitted_at.elapsed();
+        if elapsed >= self.processing_time {
+            return ("Complete", 100);
+        }
+        let percentage = (elapsed.as_secs_f64() / self.processing_time.as_secs_f64() * 100.) as u32;
+        match percentage {
+            0..=19 => ("Queued", 0),
+            _ => ("Processing", percentage.min(99)),
+        }
+    }
+
+    fn email(row: &[String], email_index: usize) -> &str {
+        row.get(email_index).map(String::as_str).unwrap_or_default()
+    }
+
+    // Result file: the input columns followed by the ones of the API.
+    fn results(&self) -> ZBResult<Vec<u8>> {
+        let input_width = self.rows.iter().map(Vec::len)
+            .chain(self.header.iter().map(Vec::len))
+            .max()
+            .unwrap_or(1);
+        let mut header = self.header.clone().unwrap_or_else(|| (1..=input_width)
+            .map(|index| match index - 1 == self.email_index {
+                true => "email".to_string(),
+                false => format!("column {}", index),
+            })
+            .collect());
+        header.resize(input_width, String::new());
+        match self.scoring {
+            true => header.push("ZeroBounce Score".to_string()),
+            false => header.extend(["ZB Status", "ZB Sub Status", "ZB Free Email"].map(String::from)),
+        }
+
+        let mut writer = csv::WriterBuilder::new().quote_style(csv::QuoteStyle::Always).from_writer(Vec::new());
+        writer.write_record(&header)?;
+        let mut seen = HashSet::new();
+        let rows: Box<dyn Iterator<Item = &Vec<String>>> = match self.reverse_results {
+            true => Box::new(self.rows.iter().rev()),
+            false => Box::new(self.rows.iter()),
+        };
+        for row in rows {
+            let email = Self::email(row, self.email_index);
+            if self.remove_duplicate && !seen.insert(email.trim().to_lowercase()) {
+                continue;
+            }
+            let (status, sub_status, _) = status_of(email);
+            let mut record = row.clone();
+            record.resize(input_width, String::new());
+            match self.scoring {
+                true => record.push(score_of(status).to_string()),
+                false => record.extend([
+                    status.to_string(),
+                    sub_status.to_string(),
+                    is_free_email(email).to_string(),
+                ]),
+            }
+            writer.write_record(&record)?;
+        }
+        writer.into_inner().map_err(|error| ZBError::ExplicitError(error.to_string()))
+    }
+}
+
+fn score_of(status: &str) -> u8 {
+    match status {
+        S_VALID => 10,
+        S_CATCH_ALL => 6,
+        S_UNKNOWN => 4,
+        _ => 0,
+    }
+}
+
+fn is_free_email(address: &str) -> bool {
+    let address = address.trim().to_lowercase();
+    address == "free_email@example.com"
+        || ["@gmail.com", "@yahoo.com", "@hotmail.com", "@outlook.com"].iter().any(|domain| address.ends_with(domain))
+}
+
+#[derive(Debug)]
+struct FakeState {
+    credits: i64,
+    processing_time: Duration,
+    reverse_results: bool,
+    files: HashMap<String, FakeFile>,
+    next_file: u64,
+    // status and sub-status of the addresses validated, by day
+    validations: Vec<(NaiveDate, &'static str, &'static str)>,
+    activity: HashMap<String, u32>,
+    requests: HashMap<String, usize>,
+}
+
+impl Default for FakeState {
+    fn default() -> Self {
+        FakeState {
+            credits: DEFAULT_FAKE_CREDITS,
+            processing_time: DEFAULT_PROCESSING_TIME,
+            reverse_results: false,
+            files: HashMap::new(),
+            next_file: 1,
+            validations: Vec::new(),
+            activity: HashMap::new(),
+            requests: HashMap::new(),
+        }
+    }
+}
+
+impl FakeState {
+    fn handle(&mut self, request: &FakeRequest) -> FakeResponse {
+        *self.requests.entry(request.endpoint.clone()).or_default() += 1;
+
+        let result = match request.endpoint.as_str() {
+            ENDPOINT_BATCH_VALIDATE => self.batch_validate(request),
+            ENDPOINT_FILE_SEND => self.submit_file(request, false),
+            ENDPOINT_SCORING_SEND => self.submit_file(request, true),
+            endpoint => {
+                if request.param("api_key") != Some(FAKE_API_KEY) {
+                    // as the API does, the balance of an unknown key is -1
+                    return match endpoint {
+                        ENDPOINT_CREDITS => FakeResponse::json(json!({ "Credits": "-1" })),
+                        _ => FakeResponse::error("Invalid API key"),
+                    };
+                }
+                match endpoint {
+                    ENDPOINT_CREDITS => Ok(FakeResponse::json(json!({ "Credits": self.credits.to_string() }))),
+                    ENDPOINT_API_USAGE => self.api_usage(request),
+                    ENDPOINT_ACTIVITY_DATA => self.activity_data(request),
+                    ENDPOINT_VALIDATE => self.validate(request),
+                    ENDPOINT_EMAIL_FINDER => self.find_email(request),
+                    ENDPOINT_FILE_STATUS => self.file_status(request, false),
+                    ENDPOINT_SCORING_STATUS => self.file_status(request, true),
+                    ENDPOINT_FILE_RESULT => self.file_result(request, false),
+                    ENDPOINT_SCORING_RESULT => self.file_result(request, true),
+                    ENDPOINT_FILE_DELETE => self.delete_file(request, false),
+                    ENDPOINT_SCORING_DELETE => self.delete_file(request, true),
+                    _ => Ok(FakeResponse { status: 404, ..FakeResponse::error("Not found") }),
+                }
+            }
+        };
+        result.unwrap_or_else(|response| response)
+    }
+
+    fn spend(&mut self, cost: i64) -> Result<(), FakeResponse> {
+        if cost > self.credits {
+            return Err(FakeResponse::error("Insufficient credits to process the request"));
+        }
+        self.credits -= cost;
+        Ok(())
+    }
+
+    fn validation(&mut self, address: &str) -> Value {
+        let (status, sub_status, _) = status_of(address);
+        let now = Utc::now();
+        self.validations.push((now.date_naive(), status, sub_status));
+
+        let (account, domain) = address.trim().rsplit_once('@').unwrap_or((address, ""));
+        json!({
+            "address": address,
+            "status": status,
+            "sub_status": sub_status,
+            "free_email": is_free_email(address),
+            "did_you_mean": None::<String>,
+            "account": account,
+            "domain": domain,
+            "domain_age_days": "9692",
+            "smtp_provider": "example",
+            "catchall_domain": status == S_CATCH_ALL,
+            "mx_found": (sub_status != SS_NO_DNS_ENTRIES && sub_status != SS_FAILED_SYNTAX_CHECK).to_string(),
+            "mx_record": "mx.example.com",
+            "firstname": None::<String>,
+            "lastname": None::<String>,
+            "gender": None::<String>,
+            "country": None::<String>,
+            "region": None::<String>,
+            "city": None::<String>,
+            "zipcode": None::<String>,
+            "processed_at": now.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+        })
+    }
+
+    fn validate(&mut self, request: &FakeRequest) -> Result<FakeResponse, FakeResponse> {
+        let email = request.param("email").ok_or_else(|| FakeResponse::error("Missing parameter: email."))?;
+        self.spend(status_of(email).2)?;
+        Ok(FakeResponse::json(self.validation(email)))
+    }
+
+    fn batch_validate(&mut self, request: &FakeRequest) -> Result<FakeResponse, FakeResponse> {
+        let body = serde_json::from_slice::<Value>(&request.body)
+            .map_err(|_| FakeResponse::error("Invalid JSON body"))?;
+        if body["api_key"].as_str() != Some(FAKE_API_KEY) {
+            return Err(FakeResponse::error("Invalid API key"));
+        }
+        let emails = body["email_batch"].as_array()
+            .ok_or_else(|| FakeResponse::error("Missing parameter: email_batch."))?
+            .iter()
+            .map(|item| item["email_address"].as_str().unwrap_or_default().to_string())
+            .collect::<Vec<_>>();
+        if emails.len() > BATCH_VALIDATE_MAX_EMAILS {
+            return Err(FakeResponse::error("The batch cannot contain more than 200 emails"));
+        }
+
+        self.spend(emails.iter().map(|email| status_of(email).2).sum())?;
+        let mut email_batch = Vec::new();
+        let mut errors = Vec::new();
+        for email in emails {
+            match email.trim().is_empty() {
+                true => errors.push(json!({ "error": "Missing email address", "email_address": email })),
+                false => email_batch.push(self.validation(&email)),
+            }
+        }
+        Ok(FakeResponse::json(json!({ "email_batch": email_batch, "errors": errors })))
+    }
+
+    fn api_usage(&self, request: &FakeRequest) -> Result<FakeResponse, FakeResponse> {
+        let date = |name: &str| request.param(name)
+            .and_then(|value| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok())
+            .ok_or_else(|| FakeResponse::error(&format!("Missing parameter: {}.", name)));
+        let (start_date, end_date) = (date("start_date")?, date("end_date")?);
+
+        let mut usage = serde_json::Map::new();
+        let fields = crate::utility::mock_constants::API_USAGE_RESPONSE;
+        let template = serde_json::from_str::<serde_json::Map<String, Value>>(fields).expect("valid template");
+        for name in template.keys().filter(|name| !name.ends_with("_date")) {
+            usage.insert(name.clone(), json!(0));
+        }
+        for (_, status, sub_status) in self.validations.iter()
+            .filter(|(date, ..)| (start_date..=end_date).contains(date))
+        {
+            let mut count = |name: String| if let Some(Value::Number(count)) = usage.get(&name) {
+                let count = count.as_u64().unwrap_or_default() + 1;
+                usage.insert(name, json!(count));
+            };
+            count("total".to_string());
+            count(format!("status_{}", status.replace('-', "_")));
+            count(format!("sub_status_{}", sub_status));
+        }
+        usage.insert("start_date".to_string(), json!(start_date.format("%-m/%-d/%Y").to_string()));
+        usage.insert("end_date".to_string(), json!(end_date.format("%-m/%-d/%Y").to_string()));
+        Ok(FakeResponse::json(Value::Object(usage)))
+    }
+
+    fn activity_data(&self, request: &FakeRequest) -> Result<FakeResponse, FakeResponse> {
+        let email = request.param("email").ok_or_else(|| FakeResponse::error("Missing parameter: email."))?;
+        Ok(FakeResponse::json(match self.activity.get(&email.trim().to_lowercase()) {
+            Some(days) => json!({ "found": true, "active_in_days": days.to_string() }),
+            None => json!({ "found": false, "active_in_days": null }),
+        }))
+    }
+
+    // Answer of `guessformat`, readable as the response of `find_email`,
+    // `find_email_v2` and `domain_search_v2`: addresses follow the
+    // `first.last` format, and companies own the `.com` domain of their name.
+    fn find_email(&self, request: &FakeRequest) -> Result<FakeResponse, FakeResponse> {
+        let company_name = request.param("company_name").unwrap_or_default();
+        let domain = match request.param("domain") {
+            Some(domain) => domain.to_lowercase(),
+            None if !company_name.is_empty() => {
+                let name = company_name.to_lowercase().chars().filter(char::is_ascii_alphanumeric).collect::<String>();
+                format!("{}.com", name)
+            }
+            None => return Err(FakeResponse::error("Missing parameter: domain or company_name.")),
+        };
+        let email = match request.param("first_name") {
+            Some(first_name) => {
+                let last_name = request.param("last_name").map(|last_name| format!(".{}", last_name)).unwrap_or_default();
+                format!("{}{}@{}", first_name, last_name, domain).to_lowercase()
+            }
+            None => String::new(),
+        };
+
+        Ok(FakeResponse::json(json!({
+            "email": email,
+            "email_confidence": "high",
+            "domain": domain,
+            "company_name": company_name,
+            "format": "first.last",
+            "status": S_VALID,
+            "sub_status": "",
+            "confidence": "high",
+            "did_you_mean": "",
+            "failure_reason": "",
+            "other_domain_formats": [{ "format": "first", "confidence": "medium" }],
+        })))
+    }
+
+    fn submit_file(&mut self, request: &FakeRequest, scoring: bool) -> Result<FakeResponse, FakeResponse> {
+        let mut fields = request.content_type.as_deref()
+            .and_then(|content_type| parse_multipart(content_type, &request.body))
+            .ok_or_else(|| FakeResponse::file_error("Invalid multipart body"))?;
+        let text = |fields: &MultipartParts, name: &str| fields.get(name)
+            .map(|(_, value)| String::from_utf8_lossy(value).trim().to_string());
+
+        if text(&fields, "api_key").as_deref() != Some(FAKE_API_KEY) {
+            return Err(FakeResponse::error("Invalid API key"));
+        }
+        let (file_name, content) = fields.remove("file")
+            .ok_or_else(|| FakeResponse::file_error("Missing parameter: file."))?;
+        let email_column = text(&fields, "email_address_column")
+            .and_then(|column| column.parse::<usize>().ok())
+            .filter(|column| *column >= 1)
+            .ok_or_else(|| FakeResponse::file_error("Missing parameter: email_address_column."))?;
+        let has_header_row = text(&fields, "has_header_row").is_some_and(|value| value == "true");
+
+        let mut rows = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(&content[..])
+            .records()
+            .map(|record| record.map(|record| record.iter().map(String::from).collect::<Vec<_>>()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| FakeResponse::file_error(&error.to_string()))?;
+        let header = match has_header_row && !rows.is_empty() {
+            true => Some(rows.remove(0)),
+            false => None,
+        };
+
+        let email_index = email_column - 1;
+        let malformed = rows.iter()
+            .filter(|row| check_email(FakeFile::email(row, email_index).trim()).is_err())
+            .count();
+        if rows.is_empty() || malformed * 2 > rows.len() {
+            return Err(FakeResponse::file_error(
+                "Over 50% of the file you uploaded contains emails in a wrong format, this is usually because \
+                you are uploading the wrong file, the file is in the wrong format or wrong column for email has been selected."
+            ));
+        }
+        self.spend(rows.iter().map(|row| status_of(FakeFile::email(row, email_index)).2).sum())?;
+
+        let file_id = format!("fake-{:08}-{}", self.next_file, if scoring { "scoring" } else { "validation" });
+        self.next_file += 1;
+        let file_name = file_name.unwrap_or_else(|| "file.csv".to_string());
+        self.files.insert(file_id.clone(), FakeFile {
+            scoring,
+            file_name: file_name.clone(),
+            rows,
+            header,
+            email_index,
+            remove_duplicate: text(&fields, "remove_duplicate").is_some_and(|value| value == "true"),
+            return_url: text(&fields, "return_url"),
+            uploaded_at: Utc::now(),
+            submitted_at: Instant::now(),
+            processing_time: self.processing_time,
+            reverse_results: self.reverse_results,
+            deleted: false,
+        });
+
+        Ok(FakeResponse::json(json!({
+            "success": true,
+            "message": "File Accepted",
+            "file_name": file_name,
+            "file_id": file_id,
+        })))
+    }
+
+    fn file(&self, request: &FakeRequest, scoring: bool) -> Result<(&str, &FakeFile), FakeResponse> {
+        let file_id = request.param("file_id").ok_or_else(|| FakeResponse::file_error("Missing parameter: file_id."))?;
+        self.files.get_key_value(file_id)
+            .filter(|(_, file)| file.scoring == scoring)
+            .map(|(file_id, file)| (file_id.as_str(), file))
+            .ok_or_else(|| FakeResponse::file_error("File cannot be found."))
+    }
+
+    fn file_status(&self, request: &FakeRequest, scoring: bool) -> Result<FakeResponse, FakeResponse> {
+        let (file_id, file) = self.file(request, scoring)?;
+        let (file_status, complete_percentage) = file.progress();
+        Ok(FakeResponse::json(json!({
+            "success": true,
+            "file_id": file_id,
+            "file_name": file.file_name,
+            "upload_date": file.uploaded_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            "file_status": file_status,
+            "complete_percentage": format!("{}%", complete_percentage),
+            "error_reason": None::<String>,
+            "return_url": file.return_url,
+        })))
+    }
+
+    fn file_result(&self, request: &FakeRequest, scoring: bool) -> Result<FakeResponse, FakeResponse> {
+        let (_, file) = self.file(request, scoring)?;
+        match file.progress() {
+            ("Deleted", _) => Err(FakeResponse::file_error("File deleted.")),
+            ("Complete", _) => {
+                let body = file.results().map_err(|error| FakeResponse::file_error(&error.to_string()))?;
+                Ok(FakeResponse { status: 200, content_type: CONTENT_TYPE_STREAM, body })
+            }
+            _ => Err(FakeResponse::file_error("File is not ready yet.")),
+        }
+    }
+
+    fn delete_file(&mut self, request: &FakeRequest, scoring: bool) -> Result<FakeResponse, FakeResponse> {
+        let (file_id, file) = self.file(request, scoring)?;
+        if file.deleted {
+            return Err(FakeResponse::file_error("File cannot be found."));
+        }
+        let (file_id, file_name) = (file_id.to_string(), file.file_name.clone());
+        if let Some(file) = self.files.get_mut(&file_id) {
+            file.deleted = true;
+        }
+        Ok(FakeResponse::json(json!({
+            "success": true,
+            "message": "File Deleted",
+            "file_name": file_name,
+            "file_id": file_id,
+        })))
+    }
+}
+
+// Parts of a `multipart/form-data` body, by name: file name and content.
+type MultipartParts = HashMap<String, (Option<String>, Vec<u8>)>;
+
+fn parse_multipart(content_type: &str, body: &[u8]) -> Option<MultipartParts> {
+    let boundary = content_type.split(';')
+        .find_map(|parameter| parameter.trim().strip_prefix("boundary="))?
+        .trim_matches('"');
+    let delimiter = format!("--{}", boundary).into_bytes();
+
+    let mut parts = HashMap::new();
+    let mut rest = &body[find(body, &delimiter)? + delimiter.len()..];
+    while !rest.starts_with(b"--") {
+        let end = find(rest, &delimiter)?;
+        let part = rest[..end].strip_prefix(b"\r\n")?.strip_suffix(b"\r\n")?;
+        rest = &rest[end + delimiter.len()..];
+
+        let header_end = find(part, b"\r\n\r\n")?;
+        let headers = String::from_utf8_lossy(&part[..header_end]);
+        let attribute = |name: &str| headers.split(';')
+            .find_map(|parameter| parameter.trim().strip_prefix(&format!("{}=", name)))
+            .map(|value| value.lines().next().unwrap_or_default().trim_matches('"').to_string());
+        parts.insert(attribute("name")?, (attribute("filename"), part[header_end + 4..].to_vec()));
+    }
+    Some(parts)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+/// Local fake of the ZeroBounce API, running in a background thread until
+/// dropped.
+///
+/// `client` returns a `ZeroBounce` calling it with `FAKE_API_KEY`. The
+/// account starts with `DEFAULT_FAKE_CREDITS`; each validated address costs
+/// a credit, except the `SANDBOX_ADDRESSES`, in files as well. Files are `Queued`, then `Processing`, and `Complete`
+/// once their processing time (`set_processing_time`) has elapsed; their
+/// results follow the same rules as single validations. Files where most
+/// addresses are malformed are rejected, as the API does.
+///
+/// # Example
+/// ```no_run
+/// use zero_bounce::testing::FakeZeroBounce;
+/// use zero_bounce::utility::ZBResult;
+///
+/// # fn main() -> ZBResult<()> {
+/// let fake = FakeZeroBounce::start()?;
+/// let zb = fake.client();
+///
+/// assert_eq!(zb.validate_email("toxic@example.com")?.sub_status, "toxic");
+/// zb.validate_email("john.doe@my-company.com")?;
+/// assert_eq!(zb.get_credits()?, 99_999);
+/// # Ok(())
+/// # }
+/// ```
+pub struct FakeZeroBounce {
+    server: Arc<Server>,
+    addr: SocketAddr,
+    state: Arc<Mutex<FakeState>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FakeZeroBounce {
+    /// Start a fake API on a free local port.
+    pub fn start() -> ZBResult<FakeZeroBounce> {
+        let server = Arc::new(Server::http("127.0.0.1:0").map_err(ZBError::TransportError)?);
+        let addr = server.server_addr().to_ip()
+            .ok_or_else(|| ZBError::explicit("fake API not listening on an IP address"))?;
+        let state = Arc::new(Mutex::new(FakeState::default()));
+
+        let thread_server = server.clone();
+        let thread_state = state.clone();
+        let thread = thread::spawn(move || {
+            for mut request in thread_server.incoming_requests() {
+                let response = match FakeRequest::read(&mut request) {
+                    Ok(fake_request) => lock(&thread_state).handle(&fake_request),
+                    Err(error) => FakeResponse::error(&error.to_string()),
+                };
+                let _ = request.respond(response.into_response());
+            }
+        });
+
+        Ok(FakeZeroBounce { server, addr, state, thread: Some(thread) })
+    }
+
+    /// Client of the fake API.
+    pub fn client(&self) -> ZeroBounce {
+        let mut zb = ZeroBounce::with_base_url(FAKE_API_KEY, self.url());
+        zb.url_provider.bulk_url = self.bulk_url();
+        zb
+    }
+
+    /// Base URL of the fake API, for clients configured otherwise (e.g. with
+    /// `ZeroBounce::builder`).
+    pub fn url(&self) -> String {
+        format!("http://{}/v2", self.addr)
+    }
+
+    /// Base URL of the fake bulk API.
+    pub fn bulk_url(&self) -> String {
+        format!("http://{}/bulk/v2", self.addr)
+    }
+
+    /// Current balance of the account.
+    pub fn credits(&self) -> i64 {
+        lock(&self.state).credits
+    }
+
+    pub fn set_credits(&self, credits: i64) {
+        lock(&self.state).credits = credits;
+    }
+
+    /// Time the files submitted from now on take to complete (default:
+    /// `DEFAULT_PROCESSING_TIME`).
+    pub fn set_processing_time(&self, processing_time: Duration) {
+        lock(&self.state).processing_time = processing_time;
+    }
+
+    /// Return the rows of the result files of the files submitted from now
+    /// on in reverse order, as the API does not promise to keep the order of
+    /// the submitted rows (default: false).
+    pub fn set_reverse_results(&self, reverse_results: bool) {
+        lock(&self.state).reverse_results = reverse_results;
+    }
+
+    /// Make `activity` report an address as active `days` ago; other
+    /// addresses are not found.
+    pub fn set_activity(&self, email: &str, days: u32) {
+        lock(&self.state).activity.insert(email.trim().to_lowercase(), days);
+    }
+
+    /// Number of requests received on an endpoint, e.g. `ENDPOINT_VALIDATE`.
+    pub fn request_count(&self, endpoint: &str) -> usize {
+        lock(&self.state).requests.get(endpoint).copied().unwrap_or_default()
+    }
+}
+
+impl Drop for FakeZeroBounce {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn lock(state: &Mutex<FakeState>) -> MutexGuard<'_, FakeState> {
+    // requests are answered one at a time, a panic cannot leave one half applied
+    state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multipart() {
+        let body = b"--XyZ\r\nContent-Disposition: form-data; name=\"file\"; filename=\"emails.csv\"\r\n\
+            Content-Type: text/csv\r\n\r\nemail\r\na@example.com\r\n--XyZ\r\n\
+            Content-Disposition: form-data; name=\"has_header_row\"\r\n\r\ntrue\r\n--XyZ--\r\n";
+        let parts = parse_multipart("multipart/form-data; boundary=XyZ", body).unwrap();
+        assert_eq!(parts["file"], (Some("emails.csv".to_string()), b"email\r\na@example.com".to_vec()));
+        assert_eq!(parts["has_header_row"], (None, b"true".to_vec()));
+
+        assert!(parse_multipart("multipart/form-data", body).is_none());
+    }
+
+    #[test]
+    fn test_status_of() {
+        assert_eq!(status_of(" Toxic@Example.com"), (S_DO_NOT_MAIL, SS_TOXIC, 0));
+        assert_eq!(status_of("john@my-company.com"), (S_VALID, "", 1));
+        assert_eq!(status_of("john@"), (S_INVALID, SS_FAILED_SYNTAX_CHECK, 1));
+    }
+
+    #[test]
+    fn test_file_progress() {
+        let mut file = FakeFile {
+            scoring: false,
+            file_name: "emails.csv".to_string(),
+            rows: vec![vec!["valid@example.com".to_string(), "x".to_string()]],
+            header: None,
+            email_index: 0,
+            remove_duplicate: false,
+            return_url: None,
+            uploaded_at: Utc::now(),
+            submitted_at: Instant::now(),
+            processing_time: Duration::from_secs(3600),
+            reverse_results: false,
+            deleted: false,
+        };
+        assert_eq!(file.progress(), ("Queued", 0));
+        file.submitted_at -= Duration::from_secs(1800);
+        assert_eq!(file.progress(), ("Processing", 50));
+        file.submitted_at -= Duration::from_secs(1800);
+        assert_eq!(file.progress(), ("Complete", 100));
+
+        let results = String::from_utf8(file.results().unwrap()).unwrap();
+        assert_eq!(results, "\"email\",\"column 2\",\"ZB Status\",\"ZB Sub Status\",\"ZB Free Email\"\n\
+            \"valid@example.com\",\"x\",\"valid\",\"\",\"false\"\n");
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+use std::io::Cursor;
+use std::sync::{Mutex, MutexGuard};
+
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+
+use crate::transport::{Transport, ZBMethod, ZBRequest, ZBResponse};
+use crate::utility::{ZBError, ZBResult, CONTENT_TYPE_JSON};
+
+/// Canned response served by an `InMemoryTransport`.
+#[derive(Clone, Debug)]
+pub struct InMemoryResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl InMemoryResponse {
+    pub fn new(status: u16, body: impl Into<Bytes>) -> InMemoryResponse {
+        InMemoryResponse {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Response with an `application/json` content type.
+    pub fn json(status: u16, body: &str) -> InMemoryResponse {
+        Self::new(status, body.to_string()).with_header(CONTENT_TYPE.as_str(), CONTENT_TYPE_JSON)
+    }
+
+    /// Add a header. Invalid header names or values are ignored.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+            self.headers.append(name, value);
+        }
+        self
+    }
+}
+
+struct Route {
+    method: ZBMethod,
+    endpoint: String,
+    responses: VecDeque<InMemoryResponse>,
+}
+
+/// Transport answering requests with canned responses, without any network
+/// access. Meant for unit tests; every received request is recorded.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use zero_bounce::ZeroBounce;
+/// use zero_bounce::transport::{InMemoryResponse, InMemoryTransport, ZBMethod};
+/// use zero_bounce::utility::ENDPOINT_CREDITS;
+///
+/// let transport = Arc::new(InMemoryTransport::new());
+/// transport.push_response(ZBMethod::Get, ENDPOINT_CREDITS, InMemoryResponse::json(200, r#"{"Credits": "42"}"#));
+///
+/// let zb = ZeroBounce::builder()
+///     .api_key("mock_api_key")
+///     .transport(transport.clone())
+///     .build()
+///     .unwrap();
+/// assert_eq!(zb.get_credits().unwrap(), 42);
+///
+/// let requests = transport.take_requests();
+/// assert_eq!(requests[0].query_value("api_key"), Some("mock_api_key"));
+/// ```
+#[derive(Default)]
+pub struct InMemoryTransport {
+    routes: Mutex<Vec<Route>>,
+    requests: Mutex<Vec<ZBRequest>>,
+}
+
+impl InMemoryTransport {
+    pub fn new() -> InMemoryTransport {
+        Self::default()
+    }
+
+    /// Queue a response for the requests whose URL ends with `endpoint`
+    /// (e.g. `ENDPOINT_VALIDATE`). Responses of an endpoint are served in
+    /// order, the last one being served again for any later request. When
+    /// several endpoints match a URL, the longest one is used.
+    pub fn push_response(&self, method: ZBMethod, endpoint: &str, response: InMemoryResponse) -> &Self {
+        let mut routes = lock(&self.routes);
+        match routes.iter_mut().find(|route| route.method == method && route.endpoint == endpoint) {
+            Some(route) => route.responses.push_back(response),
+            None => routes.push(Route {
+                method,
+                endpoint: endpoint.to_string(),
+                responses: VecDeque::from([response]),
+            }),
+        }
+        self
+    }
+
+    /// Requests received so far, removed from the transport.
+    pub fn take_requests(&self) -> Vec<ZBRequest> {
+        std::mem::take(&mut *lock(&self.requests))
+    }
+
+    pub fn request_count(&self) -> usize {
+        lock(&self.requests).len()
+    }
+
+    fn next_response(&self, request: &ZBRequest) -> Option<InMemoryResponse> {
+        let mut routes = lock(&self.routes);
+        let route = routes.iter_mut()
+            .filter(|route| route.method == request.method && request.url.ends_with(&route.endpoint))
+            .max_by_key(|route| route.endpoint.len())?;
+
+        if route.responses.len() > 1 {
+            route.responses.pop_front()
+        } else {
+            route.responses.front().cloned()
+        }
+    }
+}
+
+impl Transport for InMemoryTransport {
+    fn send(&self, request: ZBRequest) -> ZBResult<ZBResponse> {
+        let response = self.next_response(&request);
+        let description = format!("{:?} {}", request.method, request.url);
+        lock(&self.requests).push(request);
+
+        let response = response.ok_or_else(|| {
+            ZBError::TransportError(format!("no in-memory response for {}", description).into())
+        })?;
+        Ok(ZBResponse {
+            status: response.status,
+            headers: response.headers,
+            body: Box::new(Cursor::new(response.body)),
+        })
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::ZBRequestBody;
+    use crate::utility::mock_constants::{BATCH_VALIDATION_NO_ERROR, VALIDATION_RESPONSE_VALID};
+    use crate::utility::{ENDPOINT_BATCH_VALIDATE, ENDPOINT_FILE_RESULT, ENDPOINT_SCORING_RESULT, ENDPOINT_VALIDATE};
+
+    #[test]
+    fn test_responses_are_served_in_order() {
+        let transport = InMemoryTransport::new();
+        transport
+            .push_response(ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::new(500, ""))
+            .push_response(ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, VALIDATION_RESPONSE_VALID));
+
+        let statuses = (0..3)
+            .map(|_| transport.send(ZBRequest::get("http://api/v2/validate")).unwrap().status)
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, vec![500, 200, 200]);
+        assert_eq!(transport.request_count(), 3);
+    }
+
+    #[test]
+    fn test_longest_endpoint_wins() {
+        let transport = InMemoryTransport::new();
+        transport
+            .push_response(ZBMethod::Get, ENDPOINT_FILE_RESULT, InMemoryResponse::new(200, "validation"))
+            .push_response(ZBMethod::Get, ENDPOINT_SCORING_RESULT, InMemoryResponse::new(200, "scoring"));
+
+        let response = transport.send(ZBRequest::get("http://bulk/v2/scoring/getfile")).unwrap();
+        assert_eq!(response.text().unwrap(), "scoring");
+        let response = transport.send(ZBRequest::get("http://bulk/v2/getfile")).unwrap();
+        assert_eq!(response.text().unwrap(), "validation");
+    }
+
+    #[test]
+    fn test_unknown_endpoint_is_a_transport_error() {
+        let transport = InMemoryTransport::new();
+        transport.push_response(ZBMethod::Post, ENDPOINT_BATCH_VALIDATE, InMemoryResponse::json(200, BATCH_VALIDATION_NO_ERROR));
+
+        let request = ZBRequest::get("http://api/v2/validatebatch")
+            .body(ZBRequestBody::Empty);
+        let error = transport.send(request).unwrap_err();
+        assert!(matches!(error, ZBError::TransportError(_)), "{:?}", error);
+        assert_eq!(transport.take_requests().len(), 1);
+        assert_eq!(transport.request_count(), 0);
+    }
+}
@@ -0,0 +1,240 @@
+//! HTTP layer of the blocking client.
+//!
+//! `ZeroBounce` describes each API call as a `ZBRequest` and hands it to a
+//! `Transport`, which returns the status, headers and body of the response.
+//! `ReqwestTransport` is used by default; `InMemoryTransport` serves canned
+//! responses for tests, and any other HTTP stack can be plugged in by
+//! implementing the trait.
+
+pub mod in_memory;
+pub mod reqwest_transport;
+
+use std::fmt;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+
+use crate::utility::ZBResult;
+
+pub use in_memory::{InMemoryResponse, InMemoryTransport};
+pub use reqwest_transport::ReqwestTransport;
+
+/// Sends the requests of a `ZeroBounce` client.
+///
+/// Responses are returned whatever their status; the client turns
+/// unsuccessful ones into errors. Failures to get a response at all should be
+/// reported as `ZBError::TransportError` (or `ZBError::RequestError` for
+/// reqwest based stacks), which the `RetryPolicy` treats as transport failures.
+///
+/// # Example
+/// ```no_run
+/// use std::sync::Arc;
+/// use zero_bounce::ZeroBounce;
+/// use zero_bounce::transport::{Transport, ReqwestTransport, ZBRequest, ZBResponse};
+/// use zero_bounce::utility::ZBResult;
+///
+/// // Logs every request before sending it with reqwest
+/// struct LoggingTransport(ReqwestTransport);
+///
+/// impl Transport for LoggingTransport {
+///     fn send(&self, request: ZBRequest) -> ZBResult<ZBResponse> {
+///         println!("{:?} {}", request.method, request.url);
+///         self.0.send(request)
+///     }
+/// }
+///
+/// # fn main() -> ZBResult<()> {
+/// let zb = ZeroBounce::builder()
+///     .api_key("your_api_key")
+///     .transport(Arc::new(LoggingTransport(ReqwestTransport::default())))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Transport: Send + Sync {
+    fn send(&self, request: ZBRequest) -> ZBResult<ZBResponse>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZBMethod {
+    Get,
+    Post,
+}
+
+/// Content of a multipart file part.
+#[derive(Clone, Debug)]
+pub enum ZBPartContent {
+    Bytes(Vec<u8>),
+    /// File read from disk when the request is sent.
+    File(PathBuf),
+    /// Reader streamed when the request is sent.
+    Stream(ZBPartStream),
+}
+
+/// Reader used as the content of a multipart part, with its length when
+/// known. Clones share the same reader, which can only be consumed once.
+#[derive(Clone)]
+pub struct ZBPartStream {
+    reader: Arc<Mutex<Option<Box<dyn Read + Send>>>>,
+    length: Option<u64>,
+}
+
+impl ZBPartStream {
+    pub fn new(reader: impl Read + Send + 'static, length: Option<u64>) -> ZBPartStream {
+        ZBPartStream {
+            reader: Arc::new(Mutex::new(Some(Box::new(reader)))),
+            length,
+        }
+    }
+
+    pub fn length(&self) -> Option<u64> {
+        self.length
+    }
+
+    /// Take the reader out of the stream; `None` once it was taken.
+    pub fn take(&self) -> Option<Box<dyn Read + Send>> {
+        self.reader.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take()
+    }
+
+    pub fn is_consumed(&self) -> bool {
+        self.reader.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).is_none()
+    }
+}
+
+impl fmt::Debug for ZBPartStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZBPartStream")
+            .field("length", &self.length)
+            .field("consumed", &self.is_consumed())
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ZBMultipartField {
+    Text { name: String, value: String },
+    File {
+        name: String,
+        file_name: Option<String>,
+        mime: Option<String>,
+        content: ZBPartContent,
+    },
+}
+
+impl ZBMultipartField {
+    pub fn text(name: &str, value: impl Into<String>) -> ZBMultipartField {
+        ZBMultipartField::Text { name: name.to_string(), value: value.into() }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            ZBMultipartField::Text { name, .. } | ZBMultipartField::File { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ZBRequestBody {
+    Empty,
+    Bytes { content_type: String, content: Vec<u8> },
+    /// `multipart/form-data` body.
+    Multipart(Vec<ZBMultipartField>),
+}
+
+/// Description of an HTTP request issued by the client.
+#[derive(Clone, Debug)]
+pub struct ZBRequest {
+    pub method: ZBMethod,
+    /// URL without the query string.
+    pub url: String,
+    pub query: Vec<(String, String)>,
+    pub body: ZBRequestBody,
+}
+
+impl ZBRequest {
+    pub fn new(method: ZBMethod, url: impl Into<String>) -> ZBRequest {
+        ZBRequest {
+            method,
+            url: url.into(),
+            query: Vec::new(),
+            body: ZBRequestBody::Empty,
+        }
+    }
+
+    pub fn get(url: impl Into<String>) -> ZBRequest {
+        Self::new(ZBMethod::Get, url)
+    }
+
+    pub fn post(url: impl Into<String>) -> ZBRequest {
+        Self::new(ZBMethod::Post, url)
+    }
+
+    /// Append query arguments.
+    pub fn query<K, V>(mut self, args: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.query.extend(
+            args.into_iter().map(|(name, value)| (name.as_ref().to_string(), value.as_ref().to_string()))
+        );
+        self
+    }
+
+    pub fn body(mut self, body: ZBRequestBody) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// Value of a query argument.
+    pub fn query_value(&self, name: &str) -> Option<&str> {
+        self.query.iter()
+            .find(|(arg_name, _)| arg_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Response returned by a `Transport`. The body is read by the client.
+pub struct ZBResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Box<dyn Read + Send>,
+}
+
+impl ZBResponse {
+    pub fn new(status: u16, headers: HeaderMap, body: Box<dyn Read + Send>) -> ZBResponse {
+        ZBResponse { status, headers, body }
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Value of a header, when present and valid text.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)?.to_str().ok()
+    }
+
+    pub fn bytes(mut self) -> ZBResult<Bytes> {
+        let mut content = Vec::new();
+        self.body.read_to_end(&mut content)?;
+        Ok(Bytes::from(content))
+    }
+
+    pub fn text(self) -> ZBResult<String> {
+        let content = self.bytes()?;
+        Ok(String::from_utf8_lossy(&content).into_owned())
+    }
+}
+
+impl fmt::Debug for ZBResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZBResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,86 @@
+use reqwest::blocking::multipart::{Form, Part};
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+
+use crate::transport::{Transport, ZBMethod, ZBMultipartField, ZBPartContent, ZBRequest, ZBRequestBody, ZBResponse};
+use crate::utility::{ZBError, ZBResult};
+
+/// Default transport, sending requests with a blocking `reqwest` client.
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> ReqwestTransport {
+        ReqwestTransport { client }
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: ZBRequest) -> ZBResult<ZBResponse> {
+        let mut request_builder = match request.method {
+            ZBMethod::Get => self.client.get(&request.url),
+            ZBMethod::Post => self.client.post(&request.url),
+        };
+
+        if !request.query.is_empty() {
+            request_builder = request_builder.query(&request.query);
+        }
+
+        request_builder = match request.body {
+            ZBRequestBody::Empty => request_builder,
+            ZBRequestBody::Bytes { content_type, content } => request_builder
+                .header(CONTENT_TYPE, content_type)
+                .body(content),
+            ZBRequestBody::Multipart(fields) => request_builder.multipart(multipart_form(fields)?),
+        };
+
+        let response = request_builder.send()?;
+        Ok(ZBResponse {
+            status: response.status().as_u16(),
+            headers: response.headers().clone(),
+            body: Box::new(response),
+        })
+    }
+}
+
+// Build the reqwest form of a multipart body. Files on disk and readers are
+// streamed by reqwest instead of being loaded in memory; a reader without a
+// known length is sent with a chunked body.
+pub(crate) fn multipart_form(fields: Vec<ZBMultipartField>) -> ZBResult<Form> {
+    let mut form = Form::new();
+
+    for field in fields {
+        form = match field {
+            ZBMultipartField::Text { name, value } => form.text(name, value),
+            ZBMultipartField::File { name, file_name, mime, content } => {
+                let mut part = match content {
+                    ZBPartContent::Bytes(content) => Part::bytes(content),
+                    ZBPartContent::File(path) => Part::file(path)?,
+                    ZBPartContent::Stream(stream) => {
+                        let reader = stream.take()
+                            .ok_or_else(|| ZBError::validation("streamed file content can only be sent once"))?;
+                        match stream.length() {
+                            Some(length) => Part::reader_with_length(reader, length),
+                            None => Part::reader(reader),
+                        }
+                    }
+                };
+                if let Some(file_name) = file_name {
+                    part = part.file_name(file_name);
+                }
+                if let Some(mime) = mime {
+                    part = part.mime_str(&mime)?;
+                }
+                form.part(name, part)
+            }
+        };
+    }
+
+    Ok(form)
+}
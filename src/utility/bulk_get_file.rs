@@ -1,7 +1,15 @@
 //! Helpers for bulk getfile responses (v2): JSON error detection and messages.
 
+use std::io::{Read, Write};
+
+use reqwest::header::HeaderValue;
 use serde_json::Value;
 
+#[cfg(feature = "async")]
+use crate::utility::structures::bulk::ZBBulkResponse;
+use crate::utility::structures::bulk::{ZBFileDownload, ZBGetFileOptions};
+use crate::utility::{ZBError, ZBResult};
+
 /// Whether a getfile response body looks like a JSON error payload (including HTTP 200).
 pub fn get_file_json_indicates_error(body: &str) -> bool {
     let t = body.trim_start();
@@ -98,6 +106,102 @@ pub(crate) fn should_treat_get_file_body_as_error(body: &str, content_type: &str
     get_file_json_indicates_error(body)
 }
 
+// Query arguments of a getfile request (validation or scoring). `activity_data`
+// is only meaningful for validation and is never sent for scoring.
+pub(crate) fn get_file_query(
+    api_key: &str,
+    file_id: &str,
+    options: Option<&ZBGetFileOptions>,
+    is_scoring: bool,
+) -> Vec<(&'static str, String)> {
+    let mut query: Vec<(&str, String)> = vec![
+        ("api_key", api_key.to_string()),
+        ("file_id", file_id.to_string()),
+    ];
+    if let Some(opts) = options {
+        if let Some(dt) = &opts.download_type {
+            query.push(("download_type", dt.clone()));
+        }
+        if !is_scoring {
+            if let Some(ad) = opts.activity_data {
+                query.push(("activity_data", if ad { "true".into() } else { "false".into() }));
+            }
+        }
+    }
+    query
+}
+
+// Getfile bodies are only checked for a JSON error payload when they are
+// shorter than this; longer bodies are result files and streamed as they are.
+pub(crate) const GET_FILE_SNIFF_LIMIT: u64 = 8 * 1024;
+
+fn get_file_content_type(content_type: Option<&HeaderValue>) -> ZBResult<String> {
+    Ok(content_type
+        .ok_or_else(|| ZBError::explicit("content type not specified in response"))?
+        .to_str()
+        .map_err(|e| ZBError::ExplicitError(e.to_string()))?
+        .to_string())
+}
+
+fn get_file_error(body_str: String) -> ZBError {
+    // Debug: Print raw response to examine structure in debug mode
+    #[cfg(debug_assertions)]
+    {
+        eprintln!("Raw API response: {}", body_str);
+    }
+
+    ZBError::from_response(200, body_str, None)
+}
+
+// Turn the successful response of a getfile request into either the file
+// content or an error, regardless of the client (blocking or async) that
+// performed the request. Unsuccessful statuses are handled by the clients.
+#[cfg(feature = "async")]
+pub(crate) fn get_file_response(
+    content_type: Option<&HeaderValue>,
+    body_bytes: bytes::Bytes,
+) -> ZBResult<ZBBulkResponse> {
+    let content_type = get_file_content_type(content_type)?;
+    let body_str = String::from_utf8_lossy(&body_bytes).into_owned();
+
+    if should_treat_get_file_body_as_error(&body_str, &content_type) {
+        return Err(get_file_error(body_str));
+    }
+
+    Ok(ZBBulkResponse::Content(body_bytes))
+}
+
+// Streaming version of `get_file_response`: copy the body of a successful
+// getfile response into `writer`. Only the first `GET_FILE_SNIFF_LIMIT`
+// bytes are held in memory, to look for a JSON error payload.
+pub(crate) fn write_get_file_response<W: Write + ?Sized>(
+    content_type: Option<&HeaderValue>,
+    mut body: impl Read,
+    writer: &mut W,
+) -> ZBResult<ZBFileDownload> {
+    let content_type = get_file_content_type(content_type)?;
+
+    let mut prefix = Vec::new();
+    body.by_ref().take(GET_FILE_SNIFF_LIMIT).read_to_end(&mut prefix)?;
+
+    let is_whole_body = (prefix.len() as u64) < GET_FILE_SNIFF_LIMIT;
+    if is_whole_body || content_type_includes_application_json(&content_type) {
+        let prefix_str = String::from_utf8_lossy(&prefix).into_owned();
+        if should_treat_get_file_body_as_error(&prefix_str, &content_type) {
+            return Err(get_file_error(prefix_str));
+        }
+    }
+
+    writer.write_all(&prefix)?;
+    let copied = std::io::copy(&mut body, writer)?;
+    writer.flush()?;
+
+    Ok(ZBFileDownload {
+        bytes_written: prefix.len() as u64 + copied,
+        content_type,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,4 +211,25 @@ mod tests {
         assert!(get_file_json_indicates_error(r#"{"success":false,"message":""}"#));
         assert!(!get_file_json_indicates_error(r#"{"file_id":"x"}"#));
     }
+
+    #[test]
+    fn write_get_file_response_streams_past_the_prefix() {
+        let content_type = HeaderValue::from_static("application/octet-stream");
+        let content = "email,ZB Status\n".repeat(2 * GET_FILE_SNIFF_LIMIT as usize);
+
+        let mut written = Vec::new();
+        let download = write_get_file_response(Some(&content_type), content.as_bytes(), &mut written).unwrap();
+        assert_eq!(download.bytes_written, content.len() as u64);
+        assert_eq!(download.content_type, "application/octet-stream");
+        assert_eq!(written, content.as_bytes());
+
+        let body = r#"{"success": false, "message": "File deleted."}"#;
+        let mut written = Vec::new();
+        let error = write_get_file_response(Some(&content_type), body.as_bytes(), &mut written).unwrap_err();
+        assert!(matches!(error, ZBError::Http { status: 200, .. }), "{:?}", error);
+        assert!(written.is_empty());
+
+        let error = write_get_file_response(None, "a,b".as_bytes(), &mut Vec::new()).unwrap_err();
+        assert!(matches!(error, ZBError::ExplicitError(_)), "{:?}", error);
+    }
 }
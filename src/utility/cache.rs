@@ -0,0 +1,296 @@
+//! In-memory cache of validation results, with expiry per status.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::utility::structures::validate_enums::{ZBValidateStatus, ZBValidateSubStatus};
+use crate::utility::structures::validation::ZBValidation;
+
+/// Counters of a `ValidationCache`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    /// Lookups of absent or expired addresses.
+    pub misses: u64,
+    /// Entries removed to make room for new ones.
+    pub evictions: u64,
+    /// Entries currently stored, expired ones included until looked up or
+    /// evicted.
+    pub entries: usize,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    validation: ZBValidation,
+    // `None` when the TTL is too long to be represented: never expires
+    expires_at: Option<Instant>,
+    generation: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    // Keys in insertion order; an item is stale when its generation is not
+    // the one of the stored entry anymore.
+    insertion_order: VecDeque<(String, u64)>,
+    next_generation: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl CacheState {
+    // Remove the oldest entry still stored.
+    fn evict_oldest(&mut self) {
+        while let Some((key, generation)) = self.insertion_order.pop_front() {
+            if self.entries.get(&key).is_some_and(|entry| entry.generation == generation) {
+                self.entries.remove(&key);
+                self.evictions += 1;
+                return;
+            }
+        }
+    }
+
+    // Drop the stale items of the insertion order once they outnumber the
+    // stored entries.
+    fn compact(&mut self) {
+        if self.insertion_order.len() <= 2 * self.entries.len() + 16 {
+            return;
+        }
+        let entries = &self.entries;
+        self.insertion_order
+            .retain(|(key, generation)| entries.get(key).is_some_and(|entry| entry.generation == *generation));
+    }
+}
+
+/// Cache of `ZBValidation` results keyed on the normalized address (trimmed
+/// and lowercased), attached to a client with `ZeroBounce::set_cache`.
+///
+/// Each result is kept for the TTL of its sub-status if one is set, else of
+/// its status if one is set, else for the default TTL; a zero TTL disables
+/// caching, and a TTL too long to be represented (e.g. `Duration::MAX`)
+/// never expires. Once `max_entries` results are stored, the oldest one is evicted
+/// to make room. Results produced by the syntax pre-check are not cached.
+///
+/// Clones share the same entries and statistics.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use zero_bounce::{ValidationCache, ZeroBounce, ZBValidateStatus, ZBValidateSubStatus};
+///
+/// let hour = Duration::from_secs(3600);
+/// let cache = ValidationCache::new(24 * hour, 100_000)
+///     .set_status_ttl(ZBValidateStatus::Unknown, hour)
+///     .set_sub_status_ttl(ZBValidateSubStatus::Greylisted, Duration::from_secs(600));
+///
+/// let zb = ZeroBounce::new("your_api_key").set_cache(Some(cache.clone()));
+/// // ...
+/// println!("{:?}", cache.stats());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ValidationCache {
+    default_ttl: Duration,
+    max_entries: usize,
+    status_ttls: HashMap<ZBValidateStatus, Duration>,
+    sub_status_ttls: HashMap<ZBValidateSubStatus, Duration>,
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl ValidationCache {
+    pub fn new(default_ttl: Duration, max_entries: usize) -> ValidationCache {
+        ValidationCache {
+            default_ttl,
+            max_entries,
+            status_ttls: HashMap::new(),
+            sub_status_ttls: HashMap::new(),
+            state: Arc::new(Mutex::new(CacheState::default())),
+        }
+    }
+
+    /// Keep the results with this status for `ttl`.
+    pub fn set_status_ttl(mut self, status: ZBValidateStatus, ttl: Duration) -> Self {
+        self.status_ttls.insert(status, ttl);
+        self
+    }
+
+    /// Keep the results with this sub-status for `ttl`, whatever their status.
+    pub fn set_sub_status_ttl(mut self, sub_status: ZBValidateSubStatus, ttl: Duration) -> Self {
+        self.sub_status_ttls.insert(sub_status, ttl);
+        self
+    }
+
+    /// How long a result is kept.
+    pub fn ttl_of(&self, validation: &ZBValidation) -> Duration {
+        self.sub_status_ttls.get(&validation.sub_status_enum())
+            .or_else(|| self.status_ttls.get(&validation.status_enum()))
+            .copied()
+            .unwrap_or(self.default_ttl)
+    }
+
+    /// Cached result of an address, when present and not expired.
+    pub fn get(&self, address: &str) -> Option<ZBValidation> {
+        let key = normalize(address);
+        let mut state = self.lock_state();
+
+        let cached = match state.entries.get(&key) {
+            Some(entry) if entry.expires_at.is_none_or(|expires_at| expires_at > Instant::now()) => Some(entry.validation.clone()),
+            Some(_) => {
+                state.entries.remove(&key);
+                None
+            }
+            None => None,
+        };
+        match cached {
+            Some(_) => state.hits += 1,
+            None => state.misses += 1,
+        }
+        cached
+    }
+
+    /// Store a result under its address.
+    pub fn insert(&self, validation: &ZBValidation) {
+        let ttl = self.ttl_of(validation);
+        if ttl.is_zero() || self.max_entries == 0 || validation.is_precheck_result() {
+            return;
+        }
+
+        let key = normalize(&validation.address);
+        let mut state = self.lock_state();
+        if !state.entries.contains_key(&key) {
+            while state.entries.len() >= self.max_entries {
+                state.evict_oldest();
+            }
+        }
+
+        let generation = state.next_generation;
+        state.next_generation += 1;
+        state.insertion_order.push_back((key.clone(), generation));
+        state.entries.insert(key, CacheEntry {
+            validation: validation.clone(),
+            expires_at: Instant::now().checked_add(ttl),
+            generation,
+        });
+        state.compact();
+    }
+
+    /// Remove the result of an address; returns whether one was stored.
+    pub fn invalidate(&self, address: &str) -> bool {
+        self.lock_state().entries.remove(&normalize(address)).is_some()
+    }
+
+    /// Remove every result. Statistics are kept.
+    pub fn clear(&self) {
+        let mut state = self.lock_state();
+        state.entries.clear();
+        state.insertion_order.clear();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock_state();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            evictions: state.evictions,
+            entries: state.entries.len(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock_state().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, CacheState> {
+        // entries are replaced as a whole, a panic cannot leave one half updated
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn normalize(address: &str) -> String {
+    address.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utility::mock_constants::VALIDATION_RESPONSE_VALID;
+    use crate::utility::precheck::PrecheckError;
+
+    fn validation(address: &str, status: &str, sub_status: &str) -> ZBValidation {
+        let mut validation = serde_json::from_str::<ZBValidation>(VALIDATION_RESPONSE_VALID).unwrap();
+        validation.address = address.to_string();
+        validation.status = status.to_string();
+        validation.sub_status = sub_status.to_string();
+        validation
+    }
+
+    #[test]
+    fn test_hits_misses_and_normalization() {
+        let cache = ValidationCache::new(Duration::from_secs(60), 10);
+        assert!(cache.get("valid@example.com").is_none());
+
+        cache.insert(&validation("Valid@Example.com", "valid", ""));
+        assert!(cache.get("  valid@EXAMPLE.com ").is_some());
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1, evictions: 0, entries: 1 });
+
+        assert!(cache.invalidate("VALID@example.com"));
+        assert!(!cache.invalidate("valid@example.com"));
+        assert!(cache.get("valid@example.com").is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_ttl_per_status() {
+        let cache = ValidationCache::new(Duration::from_secs(60), 10)
+            .set_status_ttl(ZBValidateStatus::Unknown, Duration::from_millis(20))
+            .set_sub_status_ttl(ZBValidateSubStatus::Greylisted, Duration::ZERO);
+
+        let unknown = validation("unknown@example.com", "unknown", "");
+        let greylisted = validation("greylisted@example.com", "unknown", "greylisted");
+        assert_eq!(cache.ttl_of(&unknown), Duration::from_millis(20));
+        assert_eq!(cache.ttl_of(&validation("a@example.com", "valid", "")), Duration::from_secs(60));
+
+        cache.insert(&unknown);
+        cache.insert(&greylisted);
+        cache.insert(&ZBValidation::failed_syntax_check("bad", PrecheckError::MissingAt));
+        assert_eq!(cache.len(), 1);
+
+        assert!(cache.get("unknown@example.com").is_some());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cache.get("unknown@example.com").is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_unbounded_ttl_never_expires() {
+        let cache = ValidationCache::new(Duration::MAX, 10);
+        cache.insert(&validation("a@example.com", "valid", ""));
+        assert!(cache.get("a@example.com").is_some());
+    }
+
+    #[test]
+    fn test_oldest_entries_are_evicted() {
+        let cache = ValidationCache::new(Duration::from_secs(60), 2);
+        cache.insert(&validation("a@example.com", "valid", ""));
+        cache.insert(&validation("b@example.com", "valid", ""));
+        // re-inserting an address refreshes it
+        cache.insert(&validation("a@example.com", "invalid", ""));
+        cache.insert(&validation("c@example.com", "valid", ""));
+
+        assert!(cache.get("b@example.com").is_none());
+        assert_eq!(cache.get("a@example.com").unwrap().status, "invalid");
+        assert!(cache.get("c@example.com").is_some());
+        assert_eq!(cache.stats().evictions, 1);
+
+        for index in 0..100 {
+            cache.insert(&validation(&format!("user{}@example.com", index), "valid", ""));
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(cache.lock_state().insertion_order.len() <= 2 * 2 + 16 + 1);
+    }
+}
@@ -0,0 +1,240 @@
+//! Client-side credit budget, checked before the calls spending credits.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::utility::{ZBError, ZBResult};
+
+/// Default delay after which the balance is fetched again with `get_credits`.
+pub const DEFAULT_BALANCE_REFRESH: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default)]
+struct GuardState {
+    spent: u64,
+    // Last balance received, and the credits spent since then
+    balance: Option<(i64, Instant)>,
+    spent_since_balance: u64,
+}
+
+/// Budget of the credits a client may spend, attached with
+/// `ZeroBounce::set_credit_guard`.
+///
+/// The calls spending credits (`validate_email`, `batch_validate`, and the
+/// bulk validation and AI scoring submissions) reserve their cost first: one
+/// credit per address sent, and one per row for a file. Calls that would
+/// spend more than `max_spend` credits in total, or bring the balance of the
+/// account below `floor`, fail with `ZBError::CreditBudgetExceeded` without
+/// sending a request. Addresses answered by the pre-check, the cache or the
+/// store cost nothing, and failed calls give their credits back.
+///
+/// The cost of a file is its `ZBFile::row_count`: files created with
+/// `ZBFile::from_stream` have no known row count, and their submission is
+/// refused with `ZBError::Validation` while a guard is set. Load them with
+/// `ZBFile::from_reader` or `ZBFile::from_path` instead.
+///
+/// The balance checked against the floor is fetched with `get_credits`,
+/// then estimated from the credits spent until `refresh_interval` elapses.
+///
+/// Clones share the same budget: give a clone of one guard to several
+/// clients to cap the spending of a whole run, and call `reset` to start a
+/// new one.
+///
+/// # Example
+/// ```no_run
+/// use zero_bounce::{CreditGuard, ZBError, ZeroBounce};
+///
+/// let guard = CreditGuard::new().set_floor(1_000).set_max_spend(50_000);
+/// let zb = ZeroBounce::new("your_api_key").set_credit_guard(Some(guard.clone()));
+///
+/// match zb.validate_email("valid@example.com") {
+///     Err(ZBError::CreditBudgetExceeded { cost, available }) => {
+///         println!("{} credits needed, {} allowed", cost, available)
+///     }
+///     result => println!("{:?}", result),
+/// }
+/// println!("{} credits spent", guard.spent());
+/// ```
+#[derive(Clone, Debug)]
+pub struct CreditGuard {
+    floor: Option<i64>,
+    max_spend: Option<u64>,
+    refresh_interval: Duration,
+    state: Arc<Mutex<GuardState>>,
+}
+
+impl Default for CreditGuard {
+    fn default() -> Self {
+        CreditGuard {
+            floor: None,
+            max_spend: None,
+            refresh_interval: DEFAULT_BALANCE_REFRESH,
+            state: Arc::new(Mutex::new(GuardState::default())),
+        }
+    }
+}
+
+impl CreditGuard {
+    /// Guard without limits; set them with `set_floor` and `set_max_spend`.
+    pub fn new() -> CreditGuard {
+        Self::default()
+    }
+
+    /// Refuse the calls that would bring the account balance below `floor`.
+    pub fn set_floor(mut self, floor: i64) -> Self {
+        self.floor = Some(floor);
+        self
+    }
+
+    /// Refuse the calls that would spend more than `max_spend` credits in
+    /// total (since the creation of the guard or the last `reset`).
+    pub fn set_max_spend(mut self, max_spend: u64) -> Self {
+        self.max_spend = Some(max_spend);
+        self
+    }
+
+    /// Set how long a balance received from `get_credits` is trusted
+    /// (default: `DEFAULT_BALANCE_REFRESH`).
+    pub fn set_refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// Credits spent so far.
+    pub fn spent(&self) -> u64 {
+        self.lock_state().spent
+    }
+
+    /// Credits left before reaching `max_spend`, if set.
+    pub fn remaining(&self) -> Option<u64> {
+        let spent = self.spent();
+        self.max_spend.map(|max_spend| max_spend.saturating_sub(spent))
+    }
+
+    /// Start a new run: forget the credits spent and the balance.
+    pub fn reset(&self) {
+        *self.lock_state() = GuardState::default();
+    }
+
+    // Reserve the credits of a call, fetching the balance with
+    // `fetch_balance` when a floor is set and the last one is too old. The
+    // balance is fetched without holding the lock, so that other calls are
+    // not blocked behind the request.
+    pub(crate) fn reserve<F>(&self, cost: u64, fetch_balance: F) -> ZBResult<()>
+    where
+        F: FnOnce() -> ZBResult<i64>,
+    {
+        let fetched = match self.floor {
+            Some(_) if !self.has_fresh_balance(&self.lock_state()) => {
+                let spent_before = self.lock_state().spent;
+                let fetched_at = Instant::now();
+                Some((fetch_balance()?, fetched_at, spent_before))
+            }
+            _ => None,
+        };
+
+        let mut state = self.lock_state();
+        // Keep the balance of a call that fetched it meanwhile; otherwise
+        // count the credits reserved during the request as spent after it
+        if let Some((balance, fetched_at, spent_before)) = fetched {
+            if !self.has_fresh_balance(&state) {
+                state.balance = Some((balance, fetched_at));
+                state.spent_since_balance = state.spent.saturating_sub(spent_before);
+            }
+        }
+
+        let mut available = self.max_spend.map(|max_spend| max_spend.saturating_sub(state.spent));
+        if let (Some(floor), Some((balance, _))) = (self.floor, state.balance) {
+            let balance = balance - state.spent_since_balance as i64;
+            let above_floor = balance.saturating_sub(floor).max(0) as u64;
+            available = Some(available.map_or(above_floor, |available| available.min(above_floor)));
+        }
+
+        match available {
+            Some(available) if cost > available => Err(ZBError::CreditBudgetExceeded { cost, available }),
+            _ => {
+                state.spent += cost;
+                state.spent_since_balance += cost;
+                Ok(())
+            }
+        }
+    }
+
+    fn has_fresh_balance(&self, state: &GuardState) -> bool {
+        state.balance.is_some_and(|(_, fetched_at)| fetched_at.elapsed() < self.refresh_interval)
+    }
+
+    // Give back the credits of a call that failed.
+    pub(crate) fn release(&self, cost: u64) {
+        let mut state = self.lock_state();
+        state.spent = state.spent.saturating_sub(cost);
+        state.spent_since_balance = state.spent_since_balance.saturating_sub(cost);
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, GuardState> {
+        // counters are updated together, a panic cannot leave them half updated
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_balance() -> ZBResult<i64> {
+        panic!("balance fetched without floor")
+    }
+
+    #[test]
+    fn test_max_spend() {
+        let guard = CreditGuard::new().set_max_spend(10);
+        guard.reserve(6, no_balance).unwrap();
+        let error = guard.reserve(5, no_balance).unwrap_err();
+        assert!(matches!(error, ZBError::CreditBudgetExceeded { cost: 5, available: 4 }), "{:?}", error);
+
+        guard.release(6);
+        guard.reserve(10, no_balance).unwrap();
+        assert_eq!(guard.remaining(), Some(0));
+
+        // clones share the budget, until reset
+        let clone = guard.clone();
+        assert!(clone.reserve(1, no_balance).is_err());
+        clone.reset();
+        assert_eq!(guard.spent(), 0);
+    }
+
+    #[test]
+    fn test_floor_uses_estimated_balance() {
+        let guard = CreditGuard::new().set_floor(100).set_max_spend(1_000);
+        guard.reserve(20, || Ok(150)).unwrap();
+        // the balance is estimated until the refresh interval elapses
+        guard.reserve(30, || panic!("balance fetched again")).unwrap();
+        let error = guard.reserve(1, || panic!("balance fetched again")).unwrap_err();
+        assert!(matches!(error, ZBError::CreditBudgetExceeded { cost: 1, available: 0 }), "{:?}", error);
+        assert_eq!(guard.spent(), 50);
+    }
+
+    #[test]
+    fn test_balance_is_refreshed() {
+        let guard = CreditGuard::new().set_floor(0).set_refresh_interval(Duration::ZERO);
+        guard.reserve(5, || Ok(5)).unwrap();
+        guard.reserve(5, || Ok(5)).unwrap();
+        assert!(guard.reserve(1, || Ok(0)).is_err());
+        assert!(matches!(guard.reserve(1, || Err(ZBError::InvalidApiKey)), Err(ZBError::InvalidApiKey)));
+        assert_eq!(guard.spent(), 10);
+    }
+
+    #[test]
+    fn test_balance_is_fetched_without_lock() {
+        let guard = CreditGuard::new().set_floor(0);
+        let clone = guard.clone();
+        guard.reserve(5, || {
+            // a call fetching the balance and reserving credits meanwhile
+            clone.reserve(3, || Ok(12))?;
+            Ok(10)
+        }).unwrap();
+        // the balance fetched last is kept, with the 8 credits reserved since
+        assert_eq!(guard.spent(), 8);
+        let error = guard.reserve(5, no_balance).unwrap_err();
+        assert!(matches!(error, ZBError::CreditBudgetExceeded { cost: 5, available: 4 }), "{:?}", error);
+    }
+}
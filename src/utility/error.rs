@@ -0,0 +1,290 @@
+//! Error type returned by every client method.
+
+use std::error::Error;
+use std::fmt;
+use std::io::Error as IOError;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+
+use crate::utility::bulk_get_file::{format_get_file_error_message, get_file_json_indicates_error};
+use crate::utility::rate_limit::EndpointFamily;
+use crate::utility::structures::bulk::ZBFileStatus;
+
+#[derive(Debug)]
+pub enum ZBError {
+    ExplicitError(String),
+    /// The API answered with an error that has no dedicated variant. `body`
+    /// is the raw response body, usually a JSON object with an error message.
+    Http { status: u16, body: String },
+    /// The API rejected the API key. The API reports a missing key and an
+    /// exhausted account with the same message, both end up here.
+    InvalidApiKey,
+    /// The account does not have enough credits for the request.
+    InsufficientCredits,
+    /// The API answered `429 Too Many Requests`, with the delay it asked for
+    /// when a `Retry-After` header was sent.
+    RateLimited { retry_after: Option<Duration> },
+    /// The request was rejected by the client before being sent (e.g. a
+    /// builder missing a mandatory argument).
+    Validation(String),
+    /// The response body could not be parsed into the expected structure.
+    Deserialize { body: String, source: serde_json::Error },
+    JsonError(serde_json::Error),
+    IntParseError(std::num::ParseIntError),
+    RequestError(reqwest::Error),
+    /// A custom `Transport` could not get a response.
+    TransportError(Box<dyn Error + Send + Sync>),
+    IOError(IOError),
+    /// Every attempt allowed by the `RetryPolicy` failed; holds the failure
+    /// of each attempt, in order.
+    RetriesExhausted(Vec<ZBError>),
+    /// The request would exceed the client-side rate budget of its endpoint
+    /// family (`RateLimitMode::NonBlocking` only).
+    RateLimitExceeded { family: EndpointFamily, retry_in: Duration },
+    /// A `BulkJob` timed out while the file was still being processed;
+    /// holds the last status received.
+    BulkJobTimeout { file_id: String, status: Box<ZBFileStatus> },
+    /// A bulk results file could not be parsed; `line` is the line of the
+    /// faulty record, when known.
+    CsvError { line: Option<u64>, message: String },
+    /// A `ValidationStore` could not read or save results.
+    StoreError(Box<dyn Error + Send + Sync>),
+    /// The call would spend `cost` credits while the `CreditGuard` only
+    /// allows `available` more; no request was sent.
+    CreditBudgetExceeded { cost: u64, available: u64 },
+}
+
+pub type ZBResult<T> = Result<T, ZBError>;
+
+impl ZBError {
+    pub fn explicit(string: &str) -> ZBError {
+        ZBError::ExplicitError(string.to_string())
+    }
+
+    pub fn validation(string: &str) -> ZBError {
+        ZBError::Validation(string.to_string())
+    }
+
+    // Classify an error response of the API. The message extracted from the
+    // JSON body decides between the API key and credits variants, any other
+    // error keeps its status and body.
+    pub(crate) fn from_response(status: u16, body: String, retry_after: Option<Duration>) -> ZBError {
+        if status == 429 {
+            return ZBError::RateLimited { retry_after };
+        }
+
+        let message = format_get_file_error_message(body.trim()).to_ascii_lowercase();
+        if (message.contains("api key") || message.contains("api_key")) && message.contains("invalid") {
+            return ZBError::InvalidApiKey;
+        }
+        let lacks_credits = ["not enough", "enough credits", "insufficient", "ran out", "out of credits", "no credits"]
+            .iter()
+            .any(|pattern| message.contains(pattern));
+        if message.contains("credit") && lacks_credits {
+            return ZBError::InsufficientCredits;
+        }
+
+        ZBError::Http { status, body }
+    }
+
+    /// Whether sending the same request again later may succeed: transport
+    /// failures, `5xx` responses and rate limiting (API or client-side).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ZBError::RequestError(error) => is_transport_error(error),
+            ZBError::TransportError(_) => true,
+            ZBError::Http { status, .. } => (500..=599).contains(status),
+            ZBError::RateLimited { .. } | ZBError::RateLimitExceeded { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the request was rejected because of its credentials.
+    pub fn is_auth(&self) -> bool {
+        matches!(self, ZBError::InvalidApiKey | ZBError::Http { status: 401 | 403, .. })
+    }
+
+    /// Delay to wait before sending the request again, when known.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ZBError::RateLimited { retry_after } => *retry_after,
+            ZBError::RateLimitExceeded { retry_in, .. } => Some(*retry_in),
+            _ => None,
+        }
+    }
+}
+
+// Failures happening before a response is received; requests that could
+// not be built (e.g. invalid URL) are not part of them.
+pub(crate) fn is_transport_error(error: &reqwest::Error) -> bool {
+    !error.is_builder()
+        && (error.is_connect() || error.is_timeout() || error.is_request() || error.is_body())
+}
+
+// Deserialize the body of a successful response. Some endpoints report errors
+// with a 200 status; such a body is turned into the matching error instead of
+// a parsing failure.
+pub(crate) fn parse_response<T: DeserializeOwned>(body: &str) -> ZBResult<T> {
+    serde_json::from_str::<T>(body).map_err(|source| {
+        if get_file_json_indicates_error(body) {
+            ZBError::from_response(200, body.to_string(), None)
+        } else {
+            ZBError::Deserialize { body: body.to_string(), source }
+        }
+    })
+}
+
+impl fmt::Display for ZBError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZBError::ExplicitError(message) => write!(f, "{}", message),
+            ZBError::Http { status, body } if body.trim().is_empty() => write!(f, "HTTP {}", status),
+            ZBError::Http { status, body } => {
+                write!(f, "HTTP {}: {}", status, format_get_file_error_message(body.trim()))
+            }
+            ZBError::InvalidApiKey => write!(f, "invalid API key"),
+            ZBError::InsufficientCredits => write!(f, "not enough credits"),
+            ZBError::RateLimited { retry_after: Some(delay) } => {
+                write!(f, "rate limited by the API, retry after {:?}", delay)
+            }
+            ZBError::RateLimited { retry_after: None } => write!(f, "rate limited by the API"),
+            ZBError::Validation(message) => write!(f, "invalid request: {}", message),
+            ZBError::Deserialize { source, .. } => write!(f, "unexpected response body: {}", source),
+            ZBError::JsonError(error) => write!(f, "JSON error: {}", error),
+            ZBError::IntParseError(error) => write!(f, "invalid integer: {}", error),
+            ZBError::RequestError(error) => write!(f, "request failed: {}", error),
+            ZBError::TransportError(error) => write!(f, "transport failed: {}", error),
+            ZBError::IOError(error) => write!(f, "I/O error: {}", error),
+            ZBError::RetriesExhausted(failures) => match failures.last() {
+                Some(last) => write!(f, "all {} attempts failed, last error: {}", failures.len(), last),
+                None => write!(f, "all attempts failed"),
+            },
+            ZBError::RateLimitExceeded { family, retry_in } => write!(
+                f, "client-side rate limit of the {} endpoints exceeded, retry in {:?}", family, retry_in
+            ),
+            ZBError::BulkJobTimeout { file_id, status } => write!(
+                f, "file {} still processing ({}% complete) when the job timed out", file_id, status.complete_percentage
+            ),
+            ZBError::CsvError { line: Some(line), message } => write!(f, "invalid CSV at line {}: {}", line, message),
+            ZBError::CsvError { line: None, message } => write!(f, "invalid CSV: {}", message),
+            ZBError::StoreError(error) => write!(f, "validation store failed: {}", error),
+            ZBError::CreditBudgetExceeded { cost, available } => write!(
+                f, "credit budget exceeded: {} credits needed, {} available", cost, available
+            ),
+        }
+    }
+}
+
+impl Error for ZBError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ZBError::Deserialize { source, .. } => Some(source),
+            ZBError::JsonError(error) => Some(error),
+            ZBError::IntParseError(error) => Some(error),
+            ZBError::RequestError(error) => Some(error),
+            ZBError::TransportError(error) => Some(error.as_ref()),
+            ZBError::StoreError(error) => Some(error.as_ref()),
+            ZBError::IOError(error) => Some(error),
+            ZBError::RetriesExhausted(failures) => failures.last().map(|last| last as &(dyn Error + 'static)),
+            _ => None,
+        }
+    }
+}
+
+// Implementation made in order to automatically convert errors
+// generated by the reqwuest library into a ZBError instance.
+//
+// This automatic conversion is expected when using the client
+// and issuing http requests.
+impl From<reqwest::Error> for ZBError {
+    fn from(value: reqwest::Error) -> ZBError {
+        ZBError::RequestError(value)
+    }
+}
+
+// Implementation made in order to automatically convert errors
+// generated by the serde_json library into a ZBError instance.
+//
+// This automatic conversion is expected when serializing request
+// bodies; response bodies are parsed with `parse_response`.
+impl From<serde_json::Error> for ZBError {
+    fn from(value: serde_json::Error) -> ZBError {
+        ZBError::JsonError(value)
+    }
+}
+
+// Implementation made in order to automatically convert errors
+// generated by the csv library while reading bulk results.
+impl From<csv::Error> for ZBError {
+    fn from(value: csv::Error) -> ZBError {
+        let line = value.position().map(|position| position.line());
+        let message = value.to_string();
+        match value.into_kind() {
+            csv::ErrorKind::Io(error) => ZBError::IOError(error),
+            _ => ZBError::CsvError { line, message },
+        }
+    }
+}
+
+// Implementation made in order to automatically convert errors of the
+// SQLite database backing a `SqliteStore`.
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for ZBError {
+    fn from(value: rusqlite::Error) -> ZBError {
+        ZBError::StoreError(Box::new(value))
+    }
+}
+
+impl From<IOError> for ZBError {
+    fn from(value: IOError) -> Self {
+        ZBError::IOError(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_messages_are_classified() {
+        let body = r#"{"error": "Invalid API key or your account ran out of credits"}"#;
+        assert!(matches!(ZBError::from_response(200, body.into(), None), ZBError::InvalidApiKey));
+
+        let body = r#"{"success": false, "message": "You do not have enough credits to process this file."}"#;
+        assert!(matches!(ZBError::from_response(400, body.into(), None), ZBError::InsufficientCredits));
+
+        let body = r#"{"error": "Missing parameter: api_key."}"#;
+        let error = ZBError::from_response(400, body.into(), None);
+        assert!(matches!(error, ZBError::Http { status: 400, .. }));
+        assert_eq!(error.to_string(), "HTTP 400: Missing parameter: api_key.");
+
+        let error = ZBError::from_response(429, String::new(), Some(Duration::from_secs(2)));
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_classifiers() {
+        assert!(ZBError::Http { status: 503, body: String::new() }.is_retryable());
+        assert!(ZBError::RateLimited { retry_after: None }.is_retryable());
+        assert!(!ZBError::Http { status: 400, body: String::new() }.is_retryable());
+        assert!(!ZBError::InvalidApiKey.is_retryable());
+
+        assert!(ZBError::InvalidApiKey.is_auth());
+        assert!(ZBError::Http { status: 401, body: String::new() }.is_auth());
+        assert!(!ZBError::Http { status: 500, body: String::new() }.is_auth());
+        assert!(!ZBError::validation("first_name cannot be empty").is_auth());
+    }
+
+    #[test]
+    fn test_parse_response_detects_error_payloads() {
+        let error = parse_response::<serde_json::Map<String, serde_json::Value>>("[]").unwrap_err();
+        assert!(matches!(error, ZBError::Deserialize { ref body, .. } if body == "[]"));
+
+        let error = parse_response::<Vec<u8>>(r#"{"success": false, "message": "File deleted."}"#).unwrap_err();
+        assert!(matches!(error, ZBError::Http { status: 200, .. }), "{:?}", error);
+
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(error);
+        assert_eq!(boxed.to_string(), "HTTP 200: File deleted.");
+    }
+}
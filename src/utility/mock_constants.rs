@@ -286,6 +286,20 @@ pub const BULK_VALIDATION_DELETE_NOT_FOUND: &str = r#"
 }
 "#;
 
+pub const BULK_VALIDATION_RESULT_CSV: &str = "\
+\"email\",\"first name\",\"ZB Status\",\"ZB Sub Status\",\"ZB Account\",\"ZB Domain\",\"ZB First Name\",\"ZB Last Name\",\"ZB Gender\",\"ZB Free Email\",\"ZB MX Found\",\"ZB MX Record\",\"ZB SMTP Provider\",\"ZB Did You Mean\",\"ZB Domain Age Days\",\"ZB Last Known Activity\",\"ZB Activity Data Count\",\"ZB Activity Data Types\",\"ZB Activity Data Channels\"
+\"valid@example.com\",\"John\",\"valid\",\"\",\"valid\",\"example.com\",\"john\",\"doe\",\"male\",\"False\",\"true\",\"mx.example.com\",\"example\",\"\",\"9692\",\"2024-01-15\",\"12\",\"login,purchase\",\"email\"
+\"invalid@example.com\",\"\",\"invalid\",\"mailbox_not_found\",\"invalid\",\"example.com\",\"\",\"\",\"\",\"False\",\"true\",\"mx.example.com\",\"example\",\"\",\"9692\",\"\",\"\",\"\",\"\"
+\"user@gmial.com\",\"Jane\",\"invalid\",\"possible_typo\",\"user\",\"gmial.com\",\"\",\"\",\"\",\"True\",\"false\",\"\",\"\",\"user@gmail.com\",\"\",\"\",\"\",\"\",\"\"
+";
+
+pub const AI_SCORING_RESULT_CSV: &str = "\
+\"email\",\"ZeroBounce Score\"
+\"valid@example.com\",\"10\"
+\"invalid@example.com\",\"0\"
+\"unknown@example.com\",\"\"
+";
+
 pub const MOCK_FIND_MAIL_INVALID: &str = r#"{
     "email": "",
     "domain": "example.in",
@@ -1,10 +1,19 @@
-use std::io::Error as IOError;
-
 pub mod structures;
 pub mod mock_constants;
 pub mod bulk_get_file;
+pub mod cache;
+pub mod credit_guard;
+pub mod error;
+pub mod precheck;
+pub mod rate_limit;
+pub mod retry;
 
 pub use bulk_get_file::{format_get_file_error_message, get_file_json_indicates_error};
+pub use cache::{CacheStats, ValidationCache};
+pub use credit_guard::CreditGuard;
+pub use error::{ZBError, ZBResult};
+pub use rate_limit::{EndpointFamily, RateLimit, RateLimitMode, RateLimiter};
+pub use retry::RetryPolicy;
 
 pub const CONTENT_TYPE_JSON: &str = "application/json";
 pub const CONTENT_TYPE_STREAM: &str = "application/octet-stream";
@@ -59,6 +68,9 @@ pub const ENDPOINT_SCORING_RESULT: &str = "/scoring/getfile";
 pub const ENDPOINT_SCORING_DELETE: &str = "/scoring/deletefile";
 pub const ENDPOINT_EMAIL_FINDER: &str = "/guessformat";
 
+/// Maximum number of emails accepted by a single `/validatebatch` request.
+pub const BATCH_VALIDATE_MAX_EMAILS: usize = 200;
+
 // validation statuses
 pub const S_VALID: &str = "valid";
 pub const S_INVALID: &str = "invalid";
@@ -93,47 +105,3 @@ pub const SS_ROLE_BASED_CATCH_ALL: &str = "role_based_catch_all";
 pub const SS_DISPOSABLE: &str = "disposable";
 pub const SS_TOXIC: &str = "toxic";
 pub const SS_ACCEPT_ALL: &str = "accept_all";
-
-#[derive(Debug)]
-pub enum ZBError {
-    ExplicitError(String),
-    JsonError(serde_json::Error),
-    IntParseError(std::num::ParseIntError),
-    RequestError(reqwest::Error),
-    IOError(IOError),
-}
-
-pub type ZBResult<T> = Result<T, ZBError>;
-
-impl ZBError {
-    pub fn explicit(string: &str) -> ZBError {
-        ZBError::ExplicitError(string.to_string())
-    }
-}
-
-// Implementation made in order to automatically convert errors
-// generated by the reqwuest library into a ZBError instance.
-//
-// This automatic conversion is expected when using the client
-// and issuing http requests.
-impl From<reqwest::Error> for ZBError {
-    fn from(value: reqwest::Error) -> ZBError {
-        ZBError::RequestError(value)
-    }
-}
-
-// Implementation made in order to automatically convert errors
-// generated by the serde_json library into a ZBError instance.
-//
-// This automatic conversion is expected when deserializing.
-impl From<serde_json::Error> for ZBError {
-    fn from(value: serde_json::Error) -> ZBError {
-        ZBError::JsonError(value)
-    }
-}
-
-impl From<IOError> for ZBError {
-    fn from(value: IOError) -> Self {
-        ZBError::IOError(value)
-    }
-}
\ No newline at end of file
@@ -0,0 +1,273 @@
+//! Offline syntax check of email addresses (RFC 5321 / RFC 5322).
+//!
+//! Addresses failing these rules are rejected by the API anyway, as
+//! `invalid` / `failed_syntax_check`, but each of them costs a credit. With
+//! the pre-check enabled (`ZeroBounce::set_precheck`), the client answers for
+//! them without sending a request.
+//!
+//! The check is purely syntactic: obsolete syntax, comments and folding
+//! white space are not accepted, and domains need at least two labels.
+
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Maximum length of an address, from the 256 octets of a SMTP path minus
+/// the enclosing angle brackets.
+pub const MAX_ADDRESS_LENGTH: usize = 254;
+pub const MAX_LOCAL_PART_LENGTH: usize = 64;
+pub const MAX_DOMAIN_LENGTH: usize = 253;
+pub const MAX_LABEL_LENGTH: usize = 63;
+
+/// Reason an address failed the syntax check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrecheckError {
+    Empty,
+    MissingAt,
+    AddressTooLong,
+    EmptyLocalPart,
+    LocalPartTooLong,
+    /// The local part starts or ends with a dot, or has two dots in a row.
+    DotPlacement,
+    InvalidLocalPartCharacter(char),
+    /// A quoted local part is not closed, or contains an unescaped quote,
+    /// backslash or control character.
+    InvalidQuotedLocalPart,
+    EmptyDomain,
+    DomainTooLong,
+    /// The domain has a single label, e.g. `localhost`.
+    MissingTopLevelDomain,
+    EmptyLabel,
+    LabelTooLong,
+    /// A domain label starts or ends with a hyphen.
+    LabelHyphen,
+    InvalidDomainCharacter(char),
+    /// The top-level domain is made of digits only.
+    NumericTopLevelDomain,
+    InvalidIpLiteral,
+}
+
+impl fmt::Display for PrecheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrecheckError::Empty => write!(f, "empty address"),
+            PrecheckError::MissingAt => write!(f, "missing @"),
+            PrecheckError::AddressTooLong => write!(f, "address longer than {} characters", MAX_ADDRESS_LENGTH),
+            PrecheckError::EmptyLocalPart => write!(f, "empty local part"),
+            PrecheckError::LocalPartTooLong => {
+                write!(f, "local part longer than {} characters", MAX_LOCAL_PART_LENGTH)
+            }
+            PrecheckError::DotPlacement => write!(f, "misplaced dot in the local part"),
+            PrecheckError::InvalidLocalPartCharacter(c) => write!(f, "invalid character {:?} in the local part", c),
+            PrecheckError::InvalidQuotedLocalPart => write!(f, "invalid quoted local part"),
+            PrecheckError::EmptyDomain => write!(f, "empty domain"),
+            PrecheckError::DomainTooLong => write!(f, "domain longer than {} characters", MAX_DOMAIN_LENGTH),
+            PrecheckError::MissingTopLevelDomain => write!(f, "domain without top-level domain"),
+            PrecheckError::EmptyLabel => write!(f, "empty domain label"),
+            PrecheckError::LabelTooLong => write!(f, "domain label longer than {} characters", MAX_LABEL_LENGTH),
+            PrecheckError::LabelHyphen => write!(f, "domain label starting or ending with a hyphen"),
+            PrecheckError::InvalidDomainCharacter(c) => write!(f, "invalid character {:?} in the domain", c),
+            PrecheckError::NumericTopLevelDomain => write!(f, "numeric top-level domain"),
+            PrecheckError::InvalidIpLiteral => write!(f, "invalid IP address literal"),
+        }
+    }
+}
+
+impl std::error::Error for PrecheckError {}
+
+/// Check the syntax of an address. Surrounding white space is ignored.
+///
+/// # Example
+/// ```
+/// use zero_bounce::utility::precheck::{check_email, PrecheckError};
+///
+/// assert!(check_email("john.doe@example.com").is_ok());
+/// assert!(check_email("\"john doe\"@[192.168.0.1]").is_ok());
+/// assert_eq!(check_email("john.doe.example.com"), Err(PrecheckError::MissingAt));
+/// assert_eq!(check_email("john..doe@example.com"), Err(PrecheckError::DotPlacement));
+/// ```
+pub fn check_email(address: &str) -> Result<(), PrecheckError> {
+    let address = address.trim();
+    if address.is_empty() {
+        return Err(PrecheckError::Empty);
+    }
+    if address.len() > MAX_ADDRESS_LENGTH {
+        return Err(PrecheckError::AddressTooLong);
+    }
+
+    // A quoted local part may contain "@", the domain cannot
+    let (local_part, domain) = address.rsplit_once('@').ok_or(PrecheckError::MissingAt)?;
+    check_local_part(local_part)?;
+    check_domain(domain)
+}
+
+/// Whether an address passes `check_email`.
+pub fn is_valid_syntax(address: &str) -> bool {
+    check_email(address).is_ok()
+}
+
+fn check_local_part(local_part: &str) -> Result<(), PrecheckError> {
+    if local_part.is_empty() {
+        return Err(PrecheckError::EmptyLocalPart);
+    }
+    if local_part.len() > MAX_LOCAL_PART_LENGTH {
+        return Err(PrecheckError::LocalPartTooLong);
+    }
+    if local_part.starts_with('"') {
+        return check_quoted_local_part(local_part);
+    }
+
+    if local_part.starts_with('.') || local_part.ends_with('.') || local_part.contains("..") {
+        return Err(PrecheckError::DotPlacement);
+    }
+    match local_part.chars().find(|c| !is_atext(*c) && *c != '.') {
+        Some(c) => Err(PrecheckError::InvalidLocalPartCharacter(c)),
+        None => Ok(()),
+    }
+}
+
+// Printable characters allowed unquoted in a local part (RFC 5322 atext),
+// plus non-ASCII characters of internationalized addresses (RFC 6531).
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c) || !c.is_ascii()
+}
+
+fn check_quoted_local_part(local_part: &str) -> Result<(), PrecheckError> {
+    let inner = local_part.strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .filter(|_| local_part.len() >= 2)
+        .ok_or(PrecheckError::InvalidQuotedLocalPart)?;
+
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        let valid = match c {
+            // quoted-pair: any printable character or space
+            '\\' => chars.next().is_some_and(|escaped| escaped == ' ' || escaped.is_ascii_graphic()),
+            '"' => false,
+            c => c == ' ' || c.is_ascii_graphic() || !c.is_ascii(),
+        };
+        if !valid {
+            return Err(PrecheckError::InvalidQuotedLocalPart);
+        }
+    }
+    Ok(())
+}
+
+fn check_domain(domain: &str) -> Result<(), PrecheckError> {
+    if domain.is_empty() {
+        return Err(PrecheckError::EmptyDomain);
+    }
+    if let Some(literal) = domain.strip_prefix('[') {
+        return check_ip_literal(literal.strip_suffix(']').ok_or(PrecheckError::InvalidIpLiteral)?);
+    }
+    if domain.len() > MAX_DOMAIN_LENGTH {
+        return Err(PrecheckError::DomainTooLong);
+    }
+
+    let labels = domain.split('.').collect::<Vec<_>>();
+    for label in &labels {
+        check_label(label)?;
+    }
+    match labels.last() {
+        _ if labels.len() < 2 => Err(PrecheckError::MissingTopLevelDomain),
+        Some(tld) if tld.chars().all(|c| c.is_ascii_digit()) => Err(PrecheckError::NumericTopLevelDomain),
+        _ => Ok(()),
+    }
+}
+
+fn check_label(label: &str) -> Result<(), PrecheckError> {
+    if label.is_empty() {
+        return Err(PrecheckError::EmptyLabel);
+    }
+    if label.len() > MAX_LABEL_LENGTH {
+        return Err(PrecheckError::LabelTooLong);
+    }
+    if label.starts_with('-') || label.ends_with('-') {
+        return Err(PrecheckError::LabelHyphen);
+    }
+    // Non-ASCII characters are allowed for internationalized domain names
+    match label.chars().find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || !c.is_ascii())) {
+        Some(c) => Err(PrecheckError::InvalidDomainCharacter(c)),
+        None => Ok(()),
+    }
+}
+
+// Address literal of RFC 5321: an IPv4 address, or an IPv6 address with an
+// "IPv6:" tag.
+fn check_ip_literal(literal: &str) -> Result<(), PrecheckError> {
+    let valid = match literal.get(..5) {
+        Some(tag) if tag.eq_ignore_ascii_case("ipv6:") => literal[5..].parse::<Ipv6Addr>().is_ok(),
+        _ => literal.parse::<Ipv4Addr>().is_ok(),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(PrecheckError::InvalidIpLiteral)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_addresses() {
+        let addresses = [
+            "simple@example.com",
+            "very.common@example.com",
+            "disposable.style.email.with+symbol@example.com",
+            "other.email-with-hyphen@sub.example.co.uk",
+            "x@example.com",
+            "user%example.com@example.org",
+            "#!$%&'*+-/=?^_`{}|~@example.org",
+            "\"john..doe\"@example.org",
+            "\"very.(),:;<>[]\\\".VERY.\\\"very@\\\\ \\\"very\\\".unusual\"@strange.example.com",
+            "\"a@b\"@example.com",
+            "user@[192.168.2.1]",
+            "user@[IPv6:2001:db8::1]",
+            "josé@exämple.com",
+            "  padded@example.com  ",
+        ];
+        for address in addresses {
+            assert_eq!(check_email(address), Ok(()), "{}", address);
+        }
+    }
+
+    #[test]
+    fn test_invalid_addresses() {
+        let long_local = format!("{}@example.com", "a".repeat(65));
+        let long_label = format!("user@{}.com", "a".repeat(64));
+        let long_address = format!("user@{}.com", ["abcdefghij"; 25].join("."));
+
+        let cases = [
+            ("", PrecheckError::Empty),
+            ("plainaddress", PrecheckError::MissingAt),
+            ("@example.com", PrecheckError::EmptyLocalPart),
+            ("user@", PrecheckError::EmptyDomain),
+            (long_local.as_str(), PrecheckError::LocalPartTooLong),
+            (long_label.as_str(), PrecheckError::LabelTooLong),
+            (long_address.as_str(), PrecheckError::AddressTooLong),
+            (".user@example.com", PrecheckError::DotPlacement),
+            ("user.@example.com", PrecheckError::DotPlacement),
+            ("us..er@example.com", PrecheckError::DotPlacement),
+            ("a\"b(c)d,e:f;g<h>i[j\\k]l@example.com", PrecheckError::InvalidLocalPartCharacter('"')),
+            ("john doe@example.com", PrecheckError::InvalidLocalPartCharacter(' ')),
+            ("a@b@example.com", PrecheckError::InvalidLocalPartCharacter('@')),
+            ("\"unterminated@example.com", PrecheckError::InvalidQuotedLocalPart),
+            ("\"a\"b\"@example.com", PrecheckError::InvalidQuotedLocalPart),
+            ("user@localhost", PrecheckError::MissingTopLevelDomain),
+            ("user@example..com", PrecheckError::EmptyLabel),
+            ("user@example.com.", PrecheckError::EmptyLabel),
+            ("user@-example.com", PrecheckError::LabelHyphen),
+            ("user@example-.com", PrecheckError::LabelHyphen),
+            ("user@exa_mple.com", PrecheckError::InvalidDomainCharacter('_')),
+            ("user@1.2.3.4", PrecheckError::NumericTopLevelDomain),
+            ("user@[300.1.1.1]", PrecheckError::InvalidIpLiteral),
+            ("user@[2001:db8::1]", PrecheckError::InvalidIpLiteral),
+            ("user@[IPv6:2001:db8::g]", PrecheckError::InvalidIpLiteral),
+            ("user@[192.168.2.1", PrecheckError::InvalidIpLiteral),
+        ];
+        for (address, expected) in cases {
+            assert_eq!(check_email(address), Err(expected), "{}", address);
+        }
+    }
+}
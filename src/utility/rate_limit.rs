@@ -0,0 +1,234 @@
+//! Client-side token-bucket rate limiting, with one budget per endpoint family.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::utility::{ZBError, ZBResult};
+
+/// Group of endpoints sharing the same rate budget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EndpointFamily {
+    /// Single email validation (`/validate`).
+    Validate,
+    /// Batch validation (`/validatebatch`).
+    BatchValidate,
+    /// Every request sent to the bulk API URL (validation and AI scoring files).
+    Bulk,
+}
+
+impl fmt::Display for EndpointFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EndpointFamily::Validate => write!(f, "validate"),
+            EndpointFamily::BatchValidate => write!(f, "batch validate"),
+            EndpointFamily::Bulk => write!(f, "bulk"),
+        }
+    }
+}
+
+/// Amount of requests allowed over a period. Up to `requests` calls can be
+/// made in a burst, after which tokens are refilled evenly over `period`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, period: Duration) -> RateLimit {
+        RateLimit { requests, period }
+    }
+
+    pub fn per_second(requests: u32) -> RateLimit {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(requests: u32) -> RateLimit {
+        Self::new(requests, Duration::from_secs(60))
+    }
+}
+
+/// What to do when a request would exceed its family's budget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Wait until a token is available.
+    Block,
+    /// Fail right away with `ZBError::RateLimitExceeded`.
+    NonBlocking,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> TokenBucket {
+        TokenBucket {
+            limit,
+            tokens: limit.requests as f64,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    fn tokens_per_second(&self) -> f64 {
+        self.limit.requests as f64 / self.limit.period.as_secs_f64()
+    }
+
+    // Take a token if one is available, otherwise return how long to wait
+    // for the next one.
+    fn try_take(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.tokens_per_second())
+            .min(self.limit.requests as f64);
+        self.refilled_at = now;
+
+        if self.tokens >= 1. {
+            self.tokens -= 1.;
+            return Ok(());
+        }
+
+        let missing = 1. - self.tokens;
+        Err(Duration::from_secs_f64(missing / self.tokens_per_second()))
+    }
+}
+
+/// Token-bucket limiter holding a separate budget per `EndpointFamily`.
+/// Families without a configured limit are not throttled.
+///
+/// Clones share the same buckets, so a limiter attached to a `ZeroBounce`
+/// client is shared by all the clones of that client.
+///
+/// # Example
+/// ```no_run
+/// use zero_bounce::ZeroBounce;
+/// use zero_bounce::utility::rate_limit::{EndpointFamily, RateLimit, RateLimitMode, RateLimiter};
+///
+/// let limiter = RateLimiter::new(RateLimitMode::Block)
+///     .set_limit(EndpointFamily::Validate, RateLimit::per_second(10))
+///     .set_limit(EndpointFamily::BatchValidate, RateLimit::per_minute(5));
+/// let zb = ZeroBounce::new("your_api_key")
+///     .set_rate_limiter(Some(limiter));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    mode: RateLimitMode,
+    buckets: Arc<Mutex<HashMap<EndpointFamily, TokenBucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(mode: RateLimitMode) -> RateLimiter {
+        RateLimiter {
+            mode,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Set (or replace) the budget of an endpoint family. A limit with no
+    /// requests or an empty period is ignored.
+    pub fn set_limit(self, family: EndpointFamily, limit: RateLimit) -> Self {
+        if limit.requests > 0 && !limit.period.is_zero() {
+            self.lock_buckets().insert(family, TokenBucket::new(limit));
+        }
+        self
+    }
+
+    pub fn mode(&self) -> RateLimitMode {
+        self.mode
+    }
+
+    /// Take a token from the family's budget without waiting. On failure,
+    /// returns how long it takes for the next token to become available.
+    pub fn try_acquire(&self, family: EndpointFamily) -> Result<(), Duration> {
+        match self.lock_buckets().get_mut(&family) {
+            Some(bucket) => bucket.try_take(),
+            None => Ok(()),
+        }
+    }
+
+    /// Take a token from the family's budget, waiting for it or failing
+    /// depending on the limiter's mode.
+    pub fn acquire(&self, family: EndpointFamily) -> ZBResult<()> {
+        loop {
+            let wait = match self.try_acquire(family) {
+                Ok(()) => return Ok(()),
+                Err(wait) => wait,
+            };
+
+            if self.mode == RateLimitMode::NonBlocking {
+                return Err(ZBError::RateLimitExceeded { family, retry_in: wait });
+            }
+            std::thread::sleep(wait);
+        }
+    }
+
+    fn lock_buckets(&self) -> std::sync::MutexGuard<'_, HashMap<EndpointFamily, TokenBucket>> {
+        // a panic while holding the lock cannot leave a bucket half updated
+        self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_blocking_fails_once_budget_is_spent() {
+        let limiter = RateLimiter::new(RateLimitMode::NonBlocking)
+            .set_limit(EndpointFamily::Validate, RateLimit::per_minute(2));
+
+        assert!(limiter.acquire(EndpointFamily::Validate).is_ok());
+        assert!(limiter.acquire(EndpointFamily::Validate).is_ok());
+
+        let error = limiter.acquire(EndpointFamily::Validate).unwrap_err();
+        let ZBError::RateLimitExceeded { family, retry_in } = error else {
+            panic!("unexpected error: {:#?}", error);
+        };
+        assert_eq!(family, EndpointFamily::Validate);
+        assert!(retry_in > Duration::from_secs(20), "{:?}", retry_in);
+        assert!(retry_in <= Duration::from_secs(30), "{:?}", retry_in);
+    }
+
+    #[test]
+    fn test_families_have_separate_budgets() {
+        let limiter = RateLimiter::new(RateLimitMode::NonBlocking)
+            .set_limit(EndpointFamily::Validate, RateLimit::per_minute(1))
+            .set_limit(EndpointFamily::Bulk, RateLimit::per_minute(1));
+
+        assert!(limiter.try_acquire(EndpointFamily::Validate).is_ok());
+        assert!(limiter.try_acquire(EndpointFamily::Validate).is_err());
+        assert!(limiter.try_acquire(EndpointFamily::Bulk).is_ok());
+
+        // no limit configured
+        for _ in 0..100 {
+            assert!(limiter.try_acquire(EndpointFamily::BatchValidate).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_clones_share_budget() {
+        let limiter = RateLimiter::new(RateLimitMode::NonBlocking)
+            .set_limit(EndpointFamily::BatchValidate, RateLimit::per_minute(1));
+        let clone = limiter.clone();
+
+        assert!(clone.try_acquire(EndpointFamily::BatchValidate).is_ok());
+        assert!(limiter.try_acquire(EndpointFamily::BatchValidate).is_err());
+    }
+
+    #[test]
+    fn test_blocking_waits_for_refill() {
+        let limiter = RateLimiter::new(RateLimitMode::Block)
+            .set_limit(EndpointFamily::Validate, RateLimit::new(1, Duration::from_millis(50)));
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire(EndpointFamily::Validate).unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(90), "{:?}", start.elapsed());
+    }
+}
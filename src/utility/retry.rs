@@ -0,0 +1,223 @@
+//! Retry policy applied to the HTTP requests issued by the `ZeroBounce` client.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::utility::error::{is_transport_error, ZBError};
+
+/// Controls how many times, and how far apart, a failed request is re-sent.
+///
+/// Transport failures (connection resets, timeouts), `5xx` responses and
+/// `429 Too Many Requests` are retried depending on the flags below; other
+/// errors, in particular authentication errors (see `ZBError::is_auth`), are
+/// never retried. The delay before attempt `n + 1` is
+/// `initial_backoff * backoff_multiplier^(n - 1)`, capped at `max_backoff`,
+/// and raised to the server's `Retry-After` value when one is sent.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use zero_bounce::{ZeroBounce, RetryPolicy};
+///
+/// let policy = RetryPolicy {
+///     max_attempts: 5,
+///     initial_backoff: Duration::from_millis(200),
+///     ..Default::default()
+/// };
+/// let zb = ZeroBounce::new("your_api_key")
+///     .set_retry_policy(Some(policy));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, the first one included.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of a single delay.
+    pub max_backoff: Duration,
+    /// Factor applied to the delay after each failed attempt.
+    pub backoff_multiplier: f64,
+    /// Randomize each delay between half and the full computed value.
+    pub jitter: bool,
+    /// Retry connection errors, timeouts and other transport failures.
+    pub retry_transport_errors: bool,
+    /// Retry `5xx` responses.
+    pub retry_server_errors: bool,
+    /// Retry `429 Too Many Requests` responses.
+    pub retry_rate_limited: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            backoff_multiplier: 2.,
+            jitter: true,
+            retry_transport_errors: true,
+            retry_server_errors: true,
+            retry_rate_limited: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Default policy with a different amount of attempts.
+    pub fn new(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    /// Whether a response with this HTTP status should be sent again.
+    pub fn should_retry_status(&self, status: u16) -> bool {
+        match status {
+            429 => self.retry_rate_limited,
+            500..=599 => self.retry_server_errors,
+            _ => false,
+        }
+    }
+
+    /// Whether a request that failed before receiving a response should be
+    /// sent again. Requests that could not be built (e.g. invalid URL) are
+    /// never retried.
+    pub fn should_retry_transport(&self, error: &reqwest::Error) -> bool {
+        self.retry_transport_errors && is_transport_error(error)
+    }
+
+    /// Whether the request that failed with this error should be sent again.
+    pub fn should_retry(&self, error: &ZBError) -> bool {
+        if error.is_auth() {
+            return false;
+        }
+        match error {
+            ZBError::RequestError(error) => self.should_retry_transport(error),
+            ZBError::TransportError(_) => self.retry_transport_errors,
+            ZBError::Http { status, .. } => self.should_retry_status(*status),
+            ZBError::RateLimited { .. } => self.retry_rate_limited,
+            _ => false,
+        }
+    }
+
+    /// Delay to wait after the given failed attempt (starting at 1).
+    pub fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let computed = self.initial_backoff.as_secs_f64() * self.backoff_multiplier.powi(exponent);
+        let capped = computed.min(self.max_backoff.as_secs_f64()).max(0.);
+
+        let mut delay = if self.jitter {
+            Duration::from_secs_f64(capped / 2. + capped / 2. * random_fraction())
+        } else {
+            Duration::from_secs_f64(capped)
+        };
+
+        if let Some(retry_after) = retry_after {
+            delay = delay.max(retry_after.min(self.max_backoff));
+        }
+
+        delay
+    }
+}
+
+// Value in [0, 1) derived from the randomly seeded std hasher, which avoids
+// pulling a random number generator dependency just for jitter.
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+// Parse the `Retry-After` header, only in its delay-seconds form.
+pub(crate) fn parse_retry_after(value: Option<&reqwest::header::HeaderValue>) -> Option<Duration> {
+    value?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    fn no_jitter() -> RetryPolicy {
+        RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            jitter: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_exponentially_up_to_max() {
+        let policy = no_jitter();
+        assert_eq!(policy.backoff(1, None), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2, None), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3, None), Duration::from_millis(400));
+        assert_eq!(policy.backoff(5, None), Duration::from_secs(1));
+        assert_eq!(policy.backoff(u32::MAX, None), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_backoff_jitter_stays_within_bounds() {
+        let policy = RetryPolicy { jitter: true, ..no_jitter() };
+        for _ in 0..100 {
+            let delay = policy.backoff(2, None);
+            assert!(delay >= Duration::from_millis(100), "{:?}", delay);
+            assert!(delay <= Duration::from_millis(200), "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn test_backoff_honors_retry_after() {
+        let policy = no_jitter();
+        assert_eq!(policy.backoff(1, Some(Duration::from_millis(700))), Duration::from_millis(700));
+        assert_eq!(policy.backoff(1, Some(Duration::from_secs(60))), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_status_classes() {
+        let policy = RetryPolicy::default();
+        assert!(policy.should_retry_status(429));
+        assert!(policy.should_retry_status(500));
+        assert!(policy.should_retry_status(503));
+        assert!(!policy.should_retry_status(400));
+        assert!(!policy.should_retry_status(401));
+        assert!(!policy.should_retry_status(403));
+        assert!(!policy.should_retry_status(200));
+
+        let policy = RetryPolicy { retry_server_errors: false, ..Default::default() };
+        assert!(!policy.should_retry_status(502));
+        assert!(policy.should_retry_status(429));
+    }
+
+    #[test]
+    fn test_error_classes() {
+        let policy = RetryPolicy::default();
+        assert!(policy.should_retry(&ZBError::Http { status: 502, body: String::new() }));
+        assert!(policy.should_retry(&ZBError::RateLimited { retry_after: None }));
+        assert!(!policy.should_retry(&ZBError::InvalidApiKey));
+        assert!(!policy.should_retry(&ZBError::Http { status: 403, body: String::new() }));
+        assert!(!policy.should_retry(&ZBError::validation("domain cannot be empty")));
+
+        let policy = RetryPolicy { retry_rate_limited: false, ..Default::default() };
+        assert!(!policy.should_retry(&ZBError::RateLimited { retry_after: None }));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let value = HeaderValue::from_static("3");
+        assert_eq!(parse_retry_after(Some(&value)), Some(Duration::from_secs(3)));
+
+        let value = HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT");
+        assert_eq!(parse_retry_after(Some(&value)), None);
+        assert_eq!(parse_retry_after(None), None);
+    }
+}
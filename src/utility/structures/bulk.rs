@@ -1,18 +1,29 @@
 use std::fmt::Debug;
 use std::io::Read;
+use std::path::PathBuf;
+use std::sync::OnceLock;
 
 use bytes::Bytes;
 use chrono::{DateTime, FixedOffset};
-use reqwest::blocking::multipart::{Form, Part};
+use reqwest::blocking::multipart::Form;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::transport::{ZBMultipartField, ZBPartContent, ZBPartStream};
+use crate::transport::reqwest_transport::multipart_form;
 use crate::utility::{ZBResult, ZBError};
-use crate::utility::structures::custom_deserialize::deserialize_date_rfc;
-use crate::utility::structures::custom_deserialize::deserialize_percentage_float;
-
-
-#[derive(Clone, Debug, Deserialize)]
+use crate::utility::precheck::check_email;
+use crate::utility::structures::bulk_content::{convert_content, needs_conversion, SNIFF_LENGTH};
+use crate::utility::structures::bulk_results::join_content;
+use crate::utility::structures::bulk_split::{split_records, ZBSplitLimits};
+use crate::utility::structures::bulk_inspection::{detect_delimiter, inspect_content, ZBFileInspection, ZBFileIssue};
+use crate::utility::structures::validation::ZBValidation;
+use crate::utility::structures::validate_enums::ZBFileProcessingStatus;
+use crate::utility::structures::custom_deserialize::{deserialize_date_rfc, serialize_date_rfc};
+use crate::utility::structures::custom_deserialize::{deserialize_percentage_float, serialize_percentage_float};
+
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ZBFileFeedback {
     pub success: bool,
     pub message: String,
@@ -21,24 +32,52 @@ pub struct ZBFileFeedback {
 }
 
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct ZBFileStatus {
+/// Status of a bulk validation or AI scoring file. `ZBTypedFileStatus`
+/// reads `file_status` and `file_phase_2_status` directly into
+/// `ZBFileProcessingStatus`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(bound(deserialize = "F: Deserialize<'de>"))]
+pub struct ZBFileStatus<F = String> {
     pub success: bool,
     pub file_id: String,
     pub file_name: String,
-    pub file_status: String,
+    pub file_status: F,
     pub error_reason: Option<String>,
-    #[serde(default)]
-    pub file_phase_2_status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_phase_2_status: Option<F>,
     pub return_url: Option<String>,
 
-    #[serde(deserialize_with="deserialize_date_rfc")]
+    #[serde(deserialize_with="deserialize_date_rfc", serialize_with="serialize_date_rfc")]
     pub upload_date: DateTime<FixedOffset>,
 
-    #[serde(deserialize_with="deserialize_percentage_float")]
+    #[serde(deserialize_with="deserialize_percentage_float", serialize_with="serialize_percentage_float")]
     pub complete_percentage: f32,
 }
 
+impl ZBFileStatus {
+    /// Same status with typed `file_status` and `file_phase_2_status`.
+    pub fn typed(self) -> ZBTypedFileStatus {
+        let parse = |status: String| {
+            let Ok(status) = status.parse::<ZBFileProcessingStatus>();
+            status
+        };
+        ZBFileStatus {
+            success: self.success,
+            file_id: self.file_id,
+            file_name: self.file_name,
+            file_status: parse(self.file_status),
+            error_reason: self.error_reason,
+            file_phase_2_status: self.file_phase_2_status.map(parse),
+            return_url: self.return_url,
+            upload_date: self.upload_date,
+            complete_percentage: self.complete_percentage,
+        }
+    }
+}
+
+/// `ZBFileStatus` with typed statuses.
+pub type ZBTypedFileStatus = ZBFileStatus<ZBFileProcessingStatus>;
+
 /// `download_type` query values for bulk getfile (validation and scoring).
 pub mod zb_download_type {
     pub const PHASE_1: &str = "phase_1";
@@ -72,10 +111,19 @@ impl Debug for ZBBulkResponse {
     }
 }
 
+/// Outcome of a result file streamed to a writer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZBFileDownload {
+    pub bytes_written: u64,
+    pub content_type: String,
+}
+
 #[derive(Debug, Clone)]
 pub enum ZBFileContentType {
     FilePath(String),
     RawContent(Vec<u8>),
+    /// Reader streamed during the upload (see `ZBFile::from_stream`).
+    Stream(ZBPartStream),
     Empty,
 }
 
@@ -104,6 +152,28 @@ pub struct ZBFile {
     file_name: Option<String>,
     /// Bulk validation sendfile only; not sent for AI scoring sendfile.
     pub(crate) allow_phase_2: Option<bool>,
+    /// Send the header row and email column detected by `inspect` instead of
+    /// the configured ones.
+    auto_detect: bool,
+    /// Header row and email column detected for `auto_detect`, kept so that
+    /// the file is inspected once rather than on every upload attempt.
+    detected_layout: OnceLock<(bool, Option<u32>)>,
+    /// Decompress and transcode the content to UTF-8 CSV before using it (see
+    /// `bulk_content`).
+    convert_content: bool,
+}
+
+fn count_csv_records<R: Read>(reader: R) -> ZBResult<u64> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(reader);
+    let mut record = csv::ByteRecord::new();
+    let mut count = 0;
+    while reader.read_byte_record(&mut record)? {
+        count += 1;
+    }
+    Ok(count)
 }
 
 impl Default for ZBFile {
@@ -120,10 +190,46 @@ impl Default for ZBFile {
             return_url: None,
             file_name: None,
             allow_phase_2: None,
+            auto_detect: false,
+            detected_layout: OnceLock::new(),
+            convert_content: true,
         }
     }
 }
 
+// Chunks read ahead of the upload by `async_body`, and their size.
+#[cfg(feature = "async")]
+const ASYNC_READ_AHEAD: usize = 4;
+#[cfg(feature = "async")]
+const ASYNC_CHUNK_SIZE: usize = 64 * 1024;
+
+// Body streaming a blocking reader to the async client. The reader is read on
+// its own thread, a few chunks ahead of the upload, so that the runtime is
+// never blocked; the thread stops once the request drops the body.
+#[cfg(feature = "async")]
+fn async_body(mut reader: Box<dyn Read + Send>) -> reqwest::Body {
+    let (sender, mut receiver) = tokio::sync::mpsc::channel::<std::io::Result<Bytes>>(ASYNC_READ_AHEAD);
+
+    std::thread::spawn(move || loop {
+        let mut chunk = vec![0; ASYNC_CHUNK_SIZE];
+        let chunk = match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(length) => {
+                chunk.truncate(length);
+                Ok(Bytes::from(chunk))
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => Err(error),
+        };
+        let failed = chunk.is_err();
+        if sender.blocking_send(chunk).is_err() || failed {
+            break;
+        }
+    });
+
+    reqwest::Body::wrap_stream(futures_util::stream::poll_fn(move |context| receiver.poll_recv(context)))
+}
+
 impl ZBFile {
 
     pub fn from_path(path_to_file: String) -> ZBFile {
@@ -156,45 +262,390 @@ impl ZBFile {
         Ok(Self::from_content_with_filename(content, file_name))
     }
 
-    fn file_content_multipart(&self) -> ZBResult<Part> {
-        match self.content_type.clone() {
-            ZBFileContentType::Empty => Err(ZBError::explicit("bulk content cannot be empty")),
+    /// Create a ZBFile streamed from a reader during the upload, without
+    /// loading it in memory. The body is sent with a `Content-Length` when
+    /// `length` (in bytes) is given, chunked otherwise.
+    ///
+    /// The reader can only be sent once: a submission of a streamed file is
+    /// never retried, by the blocking and the async client alike. Its row
+    /// count is unknown, so a client with a `CreditGuard` refuses it.
+    pub fn from_stream(
+        reader: impl Read + Send + 'static,
+        length: Option<u64>,
+        file_name: impl Into<String>,
+    ) -> ZBFile {
+        ZBFile {
+            content_type: ZBFileContentType::Stream(ZBPartStream::new(reader, length)),
+            file_name: Some(file_name.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Remove the rows whose email fails the syntax pre-check (see
+    /// `utility::precheck`), so that no credit is spent on them. Returns the
+    /// file to submit and a local `ZBValidation` for each removed row.
+    ///
+    /// The content is parsed as CSV, using `email_address_column` and
+    /// `has_header_row`; a file given by path is loaded in memory. Streamed
+    /// files cannot be pre-checked.
+    pub fn precheck(self) -> ZBResult<(ZBFile, Vec<ZBValidation>)> {
+        let (content, file_name) = self.load_content("a streamed file cannot be pre-checked")?;
+
+        let email_index = (self.email_address_column as usize).saturating_sub(1);
+        let delimiter = detect_delimiter(&content) as u8;
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(delimiter)
+            .from_reader(content.as_slice());
+        let mut writer = csv::WriterBuilder::new().flexible(true).delimiter(delimiter).from_writer(Vec::new());
+        let mut prechecked = Vec::new();
+
+        for (index, record) in reader.byte_records().enumerate() {
+            let record = record?;
+            if index > 0 || !self.has_header_row {
+                let email = String::from_utf8_lossy(record.get(email_index).unwrap_or_default()).into_owned();
+                if let Err(error) = check_email(&email) {
+                    prechecked.push(ZBValidation::failed_syntax_check(&email, error));
+                    continue;
+                }
+            }
+            writer.write_byte_record(&record)?;
+        }
+
+        let content = writer.into_inner().map_err(|error| ZBError::IOError(error.into_error()))?;
+        let zb_file = ZBFile {
+            content_type: ZBFileContentType::RawContent(content),
+            file_name,
+            ..self
+        };
+        Ok((zb_file, prechecked))
+    }
+
+    // Content of the file and its name, loading a file given by path in
+    // memory. Streamed files are refused with `stream_error`.
+    // The content is converted to UTF-8 CSV unless disabled.
+    fn load_content(&self, stream_error: &str) -> ZBResult<(Vec<u8>, Option<String>)> {
+        let (content, file_name) = match &self.content_type {
+            ZBFileContentType::Empty => return Err(ZBError::validation("bulk content cannot be empty")),
+            ZBFileContentType::Stream(_) => return Err(ZBError::validation(stream_error)),
+            ZBFileContentType::RawContent(content) => (content.clone(), self.file_name.clone()),
+            ZBFileContentType::FilePath(file_path) => {
+                let path = PathBuf::from(file_path);
+                let file_name = self.file_name.clone()
+                    .or_else(|| path.file_name().map(|name| name.to_string_lossy().into_owned()));
+                (std::fs::read(&path)?, file_name)
+            }
+        };
+
+        if self.convert_content {
+            convert_content(content, file_name.as_deref())
+        } else {
+            Ok((content, file_name))
+        }
+    }
+
+    // Content to upload and its file name. Raw contents, and files given by
+    // path that are compressed or not UTF-8, are converted in memory (see
+    // `bulk_content`); other files are streamed from disk, and streams are
+    // sent as is.
+    fn upload_content(&self) -> ZBResult<(ZBFileContentType, String)> {
+        let needs_conversion = match &self.content_type {
+            ZBFileContentType::Empty => return Err(ZBError::validation("bulk content cannot be empty")),
+            ZBFileContentType::RawContent(_) => self.convert_content,
+            ZBFileContentType::FilePath(file_path) if self.convert_content => {
+                let mut prefix = Vec::new();
+                std::fs::File::open(file_path)?.take(SNIFF_LENGTH as u64).read_to_end(&mut prefix)?;
+                needs_conversion(&prefix)
+            }
+            ZBFileContentType::FilePath(_) | ZBFileContentType::Stream(_) => false,
+        };
+
+        if needs_conversion {
+            let (content, file_name) = self.load_content("")?;
+            let file_name = file_name.unwrap_or_else(|| "file.csv".to_string());
+            return Ok((ZBFileContentType::RawContent(content), file_name));
+        }
+        let file_name = match &self.content_type {
+            ZBFileContentType::FilePath(file_path) => self.file_name.clone()
+                .or_else(|| PathBuf::from(file_path).file_name().map(|name| name.to_string_lossy().into_owned())),
+            _ => self.file_name.clone(),
+        };
+        Ok((self.content_type.clone(), file_name.unwrap_or_else(|| "file.csv".to_string())))
+    }
+
+    /// Read the file locally and detect its delimiter, header row and email
+    /// column, count its rows and duplicate addresses, and report the
+    /// settings of the file that do not match its content (see
+    /// `ZBFileInspection::issues`). A file given by path is loaded in memory;
+    /// streamed files cannot be inspected.
+    pub fn inspect(&self) -> ZBResult<ZBFileInspection> {
+        let (content, _) = self.load_content("a streamed file cannot be inspected")?;
+        let mut inspection = inspect_content(&content)?;
+
+        let column_count = inspection.column_count;
+        let columns = [
+            ("email_address_column", Some(self.email_address_column)),
+            ("first_name_column", self.first_name_column),
+            ("last_name_column", self.last_name_column),
+            ("gender_column", self.gender_column),
+            ("ip_address_column", self.ip_address_column),
+        ];
+        for (setting, column) in columns {
+            match column {
+                Some(column) if column == 0 || column > column_count => {
+                    inspection.issues.push(ZBFileIssue::ColumnOutOfRange { setting, column, column_count });
+                }
+                _ => {}
+            }
+        }
+
+        match inspection.email_address_column {
+            None => inspection.issues.push(ZBFileIssue::NoEmailColumn),
+            Some(detected) if detected != self.email_address_column && (1..=column_count).contains(&self.email_address_column) => {
+                inspection.issues.push(ZBFileIssue::EmailColumnMismatch {
+                    configured: self.email_address_column,
+                    detected,
+                });
+            }
+            Some(_) => {}
+        }
+        if inspection.has_header_row != self.has_header_row {
+            inspection.issues.push(ZBFileIssue::HeaderRowMismatch {
+                configured: self.has_header_row,
+                detected: inspection.has_header_row,
+            });
+        }
+
+        Ok(inspection)
+    }
+
+    /// Split the file into chunks within `limits`, to be submitted
+    /// separately (see `BulkJob::run_split`). The header row, if any, is
+    /// repeated at the start of each chunk, the delimiter of the file is
+    /// kept, and each chunk has the settings of the file; they are named
+    /// after the file, e.g. `emails_part2.csv`.
+    ///
+    /// A file given by path is loaded in memory; streamed files cannot be
+    /// split. `remove_duplicate` only applies within each chunk.
+    pub fn split(&self, limits: ZBSplitLimits) -> ZBResult<Vec<ZBFile>> {
+        let (content, file_name) = self.load_content("a streamed file cannot be split")?;
+        let delimiter = detect_delimiter(&content) as u8;
+        let mut records = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(delimiter)
+            .from_reader(content.as_slice())
+            .into_byte_records()
+            .collect::<Result<Vec<_>, _>>()?;
+        let header = match self.has_header_row && !records.is_empty() {
+            true => Some(records.remove(0)),
+            false => None,
+        };
+
+        let chunks = split_records(records, header.as_ref(), delimiter, limits)?;
+        if chunks.is_empty() {
+            return Err(ZBError::validation("the file has no row to submit"));
+        }
+        let file_name = file_name.unwrap_or_else(|| "file.csv".to_string());
+        let (stem, extension) = file_name.rsplit_once('.').unwrap_or((&file_name, "csv"));
+
+        let zb_files = chunks.into_iter()
+            .enumerate()
+            .map(|(index, chunk)| ZBFile {
+                content_type: ZBFileContentType::RawContent(chunk),
+                has_header_row: header.is_some(),
+                remove_duplicate: self.remove_duplicate,
+                email_address_column: self.email_address_column,
+                first_name_column: self.first_name_column,
+                last_name_column: self.last_name_column,
+                gender_column: self.gender_column,
+                ip_address_column: self.ip_address_column,
+                return_url: self.return_url.clone(),
+                file_name: Some(format!("{}_part{}.{}", stem, index + 1, extension)),
+                allow_phase_2: self.allow_phase_2,
+                auto_detect: self.auto_detect,
+                detected_layout: OnceLock::new(),
+                convert_content: self.convert_content,
+            })
+            .collect();
+        Ok(zb_files)
+    }
+
+    /// Append the columns of a downloaded result file to the rows of this
+    /// file, matched by email address; see `bulk_results::join_results`.
+    /// The header row and email column of the file are used, and its
+    /// delimiter is detected. A file given by path is loaded in memory;
+    /// streamed files cannot be joined.
+    pub fn join_results(&self, results: impl Read) -> ZBResult<Vec<u8>> {
+        let (content, _) = self.load_content("a streamed file cannot be joined")?;
+        let delimiter = detect_delimiter(&content) as u8;
+        join_content(&content, delimiter, self.has_header_row, self.email_address_column, results)
+    }
+
+    /// Use the header row and email column detected by an inspection (see
+    /// `inspect`); the email column is kept when none was detected.
+    pub fn apply_inspection(mut self, inspection: &ZBFileInspection) -> Self {
+        self.has_header_row = inspection.has_header_row;
+        if let Some(column) = inspection.email_address_column {
+            self.email_address_column = column;
+        }
+        self
+    }
+
+    /// Inspect the file when it is submitted, and send the detected header
+    /// row and email column instead of the configured ones (default: false).
+    /// See `inspect` and `apply_inspection`; the file is inspected once, on
+    /// its first submission. Submitting a streamed file then fails.
+    pub fn set_auto_detect(mut self, auto_detect: bool) -> Self {
+        self.auto_detect = auto_detect;
+        self.detected_layout = OnceLock::new();
+        self
+    }
+
+    /// Decompress gzip and zip contents and transcode them to UTF-8 before
+    /// uploading, inspecting or pre-checking them (default: true). See
+    /// `bulk_content`; streamed files are always sent as is.
+    pub fn set_convert_content(mut self, convert_content: bool) -> Self {
+        self.convert_content = convert_content;
+        self.detected_layout = OnceLock::new();
+        self
+    }
+
+    /// Number of rows to process: the CSV records of the content, minus the
+    /// header row if any, once converted like the uploaded content. A file
+    /// given by path is read through; the row count of a streamed file is
+    /// unknown.
+    pub fn row_count(&self) -> ZBResult<u64> {
+        let records = match &self.content_type {
+            ZBFileContentType::Empty => return Ok(0),
+            ZBFileContentType::Stream(_) => {
+                return Err(ZBError::validation("the row count of a streamed file is unknown"))
+            }
+            ZBFileContentType::RawContent(_) | ZBFileContentType::FilePath(_) => match self.upload_content()?.0 {
+                ZBFileContentType::FilePath(file_path) => count_csv_records(std::fs::File::open(file_path)?)?,
+                ZBFileContentType::RawContent(content) => count_csv_records(content.as_slice())?,
+                _ => 0,
+            },
+        };
+        Ok(records.saturating_sub(self.has_header_row as u64))
+    }
+
+    /// Whether the content is a reader that can only be sent once.
+    pub fn is_streamed(&self) -> bool {
+        matches!(self.content_type, ZBFileContentType::Stream(_))
+    }
+
+    fn file_content_field(&self) -> ZBResult<ZBMultipartField> {
+        let (content_type, file_name) = self.upload_content()?;
+        let (mime, content) = match content_type {
+            ZBFileContentType::Empty => return Err(ZBError::validation("bulk content cannot be empty")),
+            ZBFileContentType::FilePath(file_path) => (None, ZBPartContent::File(PathBuf::from(file_path))),
+            ZBFileContentType::RawContent(value) => (Some("text/csv".to_string()), ZBPartContent::Bytes(value)),
+            ZBFileContentType::Stream(stream) => (Some("text/csv".to_string()), ZBPartContent::Stream(stream)),
+        };
+
+        Ok(ZBMultipartField::File { name: "file".to_string(), file_name: Some(file_name), mime, content })
+    }
+
+    #[cfg(feature = "async")]
+    async fn file_content_multipart_async(&self) -> ZBResult<reqwest::multipart::Part> {
+        use reqwest::multipart::Part as AsyncPart;
+
+        let (content_type, file_name) = self.upload_content()?;
+        match content_type {
+            ZBFileContentType::Empty => Err(ZBError::validation("bulk content cannot be empty")),
             ZBFileContentType::FilePath(file_path) => Ok(
-                Part::file(file_path.clone())?
+                AsyncPart::file(file_path).await?
             ),
             ZBFileContentType::RawContent(value) => {
-                let name = self.file_name.clone().unwrap_or_else(|| "file.csv".to_string());
                 Ok(
-                    Part::bytes(value)
-                        .file_name(name)
+                    AsyncPart::bytes(value)
+                        .file_name(file_name)
                         .mime_str("text/csv")?
                 )
             }
+            ZBFileContentType::Stream(stream) => {
+                let reader = stream.take()
+                    .ok_or_else(|| ZBError::validation("streamed file content can only be sent once"))?;
+                let body = async_body(reader);
+                let part = match stream.length() {
+                    Some(length) => AsyncPart::stream_with_length(body, length),
+                    None => AsyncPart::stream(body),
+                };
+
+                Ok(part.file_name(file_name).mime_str("text/csv")?)
+            }
         }
     }
 
-    pub fn generate_multipart(&self) -> ZBResult<Form> {
-        let content_part = self.file_content_multipart()?;
-        let mut multipart_form = Form::new()
-            .part("file", content_part)
-            .text("has_header_row", self.has_header_row.to_string())
-            .text("remove_duplicate", self.remove_duplicate.to_string())
-            .text("email_address_column", self.email_address_column.to_string());
+    // Header row and email column of the content, inspected on first use.
+    fn detected_layout(&self) -> ZBResult<(bool, Option<u32>)> {
+        if let Some(layout) = self.detected_layout.get() {
+            return Ok(*layout);
+        }
+        let inspection = inspect_content(&self.load_content("a streamed file cannot be inspected")?.0)?;
+        Ok(*self.detected_layout.get_or_init(|| (inspection.has_header_row, inspection.email_address_column)))
+    }
+
+    // Text fields sent along with the file part, shared by the blocking and
+    // async multipart forms.
+    fn multipart_text_fields(&self) -> ZBResult<Vec<(&'static str, String)>> {
+        let (has_header_row, email_address_column) = if self.auto_detect {
+            let (has_header_row, email_address_column) = self.detected_layout()?;
+            (has_header_row, email_address_column.unwrap_or(self.email_address_column))
+        } else {
+            (self.has_header_row, self.email_address_column)
+        };
+
+        let mut fields = vec![
+            ("has_header_row", has_header_row.to_string()),
+            ("remove_duplicate", self.remove_duplicate.to_string()),
+            ("email_address_column", email_address_column.to_string()),
+        ];
 
         if let Some(amount) = self.first_name_column {
-            multipart_form = multipart_form.text("first_name_column", amount.to_string());
+            fields.push(("first_name_column", amount.to_string()));
         }
         if let Some(amount) = self.last_name_column {
-            multipart_form = multipart_form.text("last_name_column", amount.to_string());
+            fields.push(("last_name_column", amount.to_string()));
         }
         if let Some(amount) = self.gender_column {
-            multipart_form = multipart_form.text("gender_column", amount.to_string());
+            fields.push(("gender_column", amount.to_string()));
         }
         if let Some(amount) = self.ip_address_column {
-            multipart_form = multipart_form.text("ip_address_column", amount.to_string());
+            fields.push(("ip_address_column", amount.to_string()));
         }
         if let Some(url) = &self.return_url {
-            multipart_form = multipart_form.text("return_url", url.clone());
+            fields.push(("return_url", url.clone()));
+        }
+
+        Ok(fields)
+    }
+
+    /// Fields of the multipart body sent to the sendfile endpoints: the file
+    /// part followed by the settings of the file.
+    pub fn multipart_fields(&self) -> ZBResult<Vec<ZBMultipartField>> {
+        let mut fields = vec![self.file_content_field()?];
+        for (name, value) in self.multipart_text_fields()? {
+            fields.push(ZBMultipartField::text(name, value));
+        }
+        Ok(fields)
+    }
+
+    pub fn generate_multipart(&self) -> ZBResult<Form> {
+        multipart_form(self.multipart_fields()?)
+    }
+
+    /// Same as `generate_multipart`, for the async client.
+    #[cfg(feature = "async")]
+    pub async fn generate_multipart_async(&self) -> ZBResult<reqwest::multipart::Form> {
+        let content_part = self.file_content_multipart_async().await?;
+        let mut multipart_form = reqwest::multipart::Form::new()
+            .part("file", content_part);
+
+        for (name, value) in self.multipart_text_fields()? {
+            multipart_form = multipart_form.text(name, value);
         }
 
         Ok(multipart_form)
@@ -256,7 +707,6 @@ impl ZBFile {
 
 
 #[cfg(test)]
-
 mod test {
     use chrono::{NaiveDate, NaiveTime, NaiveDateTime};
     use serde_json::{Result as SerdeResult, from_str};
@@ -275,7 +725,7 @@ mod test {
         assert!(validation.is_ok());
 
         let validation_obj = validation.unwrap();
-        assert_eq!(validation_obj.success, true);
+        assert!(validation_obj.success);
         assert!(validation_obj.file_id.is_some());
         assert!(validation_obj.file_name.is_some());
         assert_eq!(validation_obj.message, "File Accepted");
@@ -292,7 +742,7 @@ mod test {
         );
 
         let file_status_obj = file_status.unwrap();
-        assert_eq!(file_status_obj.success, true);
+        assert!(file_status_obj.success);
         assert_eq!(file_status_obj.complete_percentage, 100.);
         assert_eq!(file_status_obj.upload_date.naive_utc(), expected_date_time);
         assert!(file_status_obj.return_url.is_some());
@@ -310,7 +760,7 @@ mod test {
         );
 
         let file_status_obj = file_status.unwrap();
-        assert_eq!(file_status_obj.success, true);
+        assert!(file_status_obj.success);
         assert_eq!(file_status_obj.complete_percentage, 0.);
         assert_eq!(file_status_obj.upload_date.naive_utc(), expected_date_time);
         assert!(file_status_obj.return_url.is_none());
@@ -324,7 +774,7 @@ mod test {
         assert!(feedback.is_ok());
 
         let feedback_obj = feedback.unwrap();
-        assert_eq!(feedback_obj.success, false);
+        assert!(!feedback_obj.success);
         assert!(feedback_obj.file_id.is_none());
         assert!(feedback_obj.file_name.is_none());
     }
@@ -335,10 +785,228 @@ mod test {
         assert!(feedback.is_ok());
 
         let feedback_obj = feedback.unwrap();
-        assert_eq!(feedback_obj.success, true);
+        assert!(feedback_obj.success);
         assert!(feedback_obj.file_id.is_some());
         assert!(feedback_obj.file_name.is_some());
         assert_eq!(feedback_obj.message, "File Deleted");
     }
 
+    #[test]
+    fn test_precheck_removes_invalid_rows() {
+        let content = "name,email\njohn,john@example.com\njane,jane.example.com\n\"doe, j\",doe@example..com\n";
+        let zb_file = ZBFile::from_content(content.as_bytes().to_vec())
+            .set_email_address_column(2)
+            .set_remove_duplicate(true);
+
+        let (zb_file, prechecked) = zb_file.precheck().unwrap();
+        let ZBFileContentType::RawContent(content) = &zb_file.content_type else {
+            panic!("unexpected content: {:?}", zb_file.content_type);
+        };
+        assert_eq!(String::from_utf8_lossy(content), "name,email\njohn,john@example.com\n");
+        assert!(zb_file.remove_duplicate);
+
+        let addresses = prechecked.iter().map(|item| item.address.as_str()).collect::<Vec<_>>();
+        assert_eq!(addresses, vec!["jane.example.com", "doe@example..com"]);
+        assert!(prechecked.iter().all(ZBValidation::is_precheck_result));
+
+        let streamed = ZBFile::from_stream(&b"a@example.com"[..], None, "emails.csv");
+        assert!(matches!(streamed.precheck(), Err(ZBError::Validation(_))));
+    }
+
+    #[test]
+    fn test_precheck_keeps_delimiter() {
+        let content = "name;email\n\"Doe, John\";john@example.com\njane;jane.example.com\nbob;bob@example.com\n";
+        let zb_file = ZBFile::from_content(content.as_bytes().to_vec()).set_email_address_column(2);
+
+        let (zb_file, prechecked) = zb_file.precheck().unwrap();
+        let ZBFileContentType::RawContent(content) = &zb_file.content_type else {
+            panic!("unexpected content: {:?}", zb_file.content_type);
+        };
+        assert_eq!(String::from_utf8_lossy(content), "name;email\nDoe, John;john@example.com\nbob;bob@example.com\n");
+        let addresses = prechecked.iter().map(|item| item.address.as_str()).collect::<Vec<_>>();
+        assert_eq!(addresses, vec!["jane.example.com"]);
+    }
+
+    #[test]
+    fn test_stream_file_is_sent_once() {
+        let zb_file = ZBFile::from_stream(&b"email\nexample@example.com\n"[..], Some(26), "emails.csv");
+        assert!(zb_file.is_streamed());
+
+        let fields = zb_file.multipart_fields().unwrap();
+        let ZBMultipartField::File { file_name, content: ZBPartContent::Stream(stream), .. } = &fields[0] else {
+            panic!("unexpected file field: {:#?}", fields[0]);
+        };
+        assert_eq!(file_name.as_deref(), Some("emails.csv"));
+        assert_eq!(stream.length(), Some(26));
+
+        assert!(zb_file.generate_multipart().is_ok());
+        assert!(stream.is_consumed());
+        assert!(matches!(zb_file.generate_multipart(), Err(ZBError::Validation(_))));
+    }
+
+    #[test]
+    fn test_file_status_serializes_to_the_api_format() {
+        for body in [BULK_VALIDATION_STATUS_OK, BULK_VALIDATION_STATUS_DELETED] {
+            let status: ZBFileStatus = from_str(body).unwrap();
+            let expected = from_str::<serde_json::Value>(body).unwrap();
+            assert_eq!(serde_json::to_value(&status).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_typed_file_status() {
+        let status: ZBTypedFileStatus = from_str(BULK_VALIDATION_STATUS_DELETED).unwrap();
+        assert_eq!(status.file_status, ZBFileProcessingStatus::Deleted);
+
+        let with_phase_2 = BULK_VALIDATION_STATUS_OK.replace(r#""return_url""#, r#""file_phase_2_status": "Queued", "return_url""#);
+        let status: ZBFileStatus = from_str(&with_phase_2).unwrap();
+        let typed = status.typed();
+        assert_eq!(typed.file_status, ZBFileProcessingStatus::Processing);
+        assert_eq!(typed.file_phase_2_status, Some(ZBFileProcessingStatus::Queued));
+    }
+
+    #[test]
+    fn test_inspect_reports_issues() {
+        let content = b"name;email\nJohn;john@example.com\nJane;jane@example.com\n".to_vec();
+        let zb_file = ZBFile::from_content(content.clone())
+            .set_has_header_row(false)
+            .set_ip_address_column(Some(4));
+        let inspection = zb_file.inspect().unwrap();
+        assert_eq!(inspection.delimiter, ';');
+        assert_eq!(inspection.row_count, 2);
+        assert_eq!(inspection.issues, vec![
+            ZBFileIssue::ColumnOutOfRange { setting: "ip_address_column", column: 4, column_count: 2 },
+            ZBFileIssue::EmailColumnMismatch { configured: 1, detected: 2 },
+            ZBFileIssue::HeaderRowMismatch { configured: false, detected: true },
+        ]);
+
+        let zb_file = ZBFile::from_content(content).set_email_address_column(2);
+        assert!(zb_file.inspect().unwrap().is_ok());
+
+        let zb_file = ZBFile::from_stream(std::io::empty(), None, "emails.csv");
+        assert!(matches!(zb_file.inspect(), Err(ZBError::Validation(_))));
+    }
+
+    #[test]
+    fn test_auto_detect_settings() {
+        let text_fields = |zb_file: &ZBFile| -> Vec<(String, String)> {
+            zb_file.multipart_fields().unwrap().into_iter()
+                .filter_map(|field| match field {
+                    ZBMultipartField::Text { name, value } => Some((name, value)),
+                    ZBMultipartField::File { .. } => None,
+                })
+                .collect()
+        };
+        let content = b"Doe,john@example.com\nRoe,jane@example.com\n".to_vec();
+
+        let fields = text_fields(&ZBFile::from_content(content.clone()));
+        assert!(fields.contains(&("has_header_row".to_string(), "true".to_string())));
+        assert!(fields.contains(&("email_address_column".to_string(), "1".to_string())));
+
+        let zb_file = ZBFile::from_content(content).set_auto_detect(true);
+        let fields = text_fields(&zb_file);
+        assert!(fields.contains(&("has_header_row".to_string(), "false".to_string())));
+        assert!(fields.contains(&("email_address_column".to_string(), "2".to_string())));
+
+        let inspection = zb_file.inspect().unwrap();
+        let fields = text_fields(&zb_file.set_auto_detect(false).apply_inspection(&inspection));
+        assert!(fields.contains(&("email_address_column".to_string(), "2".to_string())));
+    }
+
+    #[test]
+    fn test_auto_detect_inspects_once() {
+        let path = std::env::temp_dir().join(format!("zb-auto-detect-{}.csv", std::process::id()));
+        std::fs::write(&path, b"name,email\nDoe,john@example.com\n").unwrap();
+        let zb_file = ZBFile::from_path(path.to_string_lossy().into_owned()).set_auto_detect(true);
+        let email_column = |zb_file: &ZBFile| zb_file.multipart_text_fields().unwrap().into_iter()
+            .find(|(name, _)| *name == "email_address_column")
+            .map(|(_, value)| value);
+        assert_eq!(email_column(&zb_file).as_deref(), Some("2"));
+
+        // later uploads reuse the first inspection
+        std::fs::write(&path, b"john@example.com\n").unwrap();
+        assert_eq!(email_column(&zb_file).as_deref(), Some("2"));
+        assert_eq!(email_column(&zb_file.set_auto_detect(true)).as_deref(), Some("1"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_uploaded_content_is_converted() {
+        use std::io::Write;
+        use flate2::{write::GzEncoder, Compression};
+
+        let file_part = |zb_file: &ZBFile| match zb_file.multipart_fields().unwrap().remove(0) {
+            ZBMultipartField::File { file_name, content, .. } => (file_name.unwrap(), content),
+            ZBMultipartField::Text { .. } => panic!("the file part comes first"),
+        };
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"email\njohn@example.com\n").unwrap();
+        let zb_file = ZBFile::from_content_with_filename(encoder.finish().unwrap(), "emails.csv.gz");
+        let (file_name, content) = file_part(&zb_file);
+        assert_eq!(file_name, "emails.csv");
+        assert!(matches!(content, ZBPartContent::Bytes(bytes) if bytes == b"email\njohn@example.com\n"));
+        assert_eq!(zb_file.row_count().unwrap(), 1);
+
+        let path = std::env::temp_dir().join(format!("zb-latin1-{}.csv", std::process::id()));
+        std::fs::write(&path, b"email,name\nfrancois@example.com,Fran\xe7ois\n").unwrap();
+        let zb_file = ZBFile::from_path(path.to_string_lossy().into_owned());
+        let (_, content) = file_part(&zb_file);
+        assert!(matches!(content, ZBPartContent::Bytes(bytes) if bytes == "email,name\nfrancois@example.com,François\n".as_bytes()));
+        let (_, content) = file_part(&zb_file.set_convert_content(false));
+        assert!(matches!(content, ZBPartContent::File(_)));
+
+        std::fs::write(&path, b"email\njohn@example.com\n").unwrap();
+        let (_, content) = file_part(&ZBFile::from_path(path.to_string_lossy().into_owned()));
+        assert!(matches!(content, ZBPartContent::File(_)));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_uploaded_file_name_of_path() {
+        let file_name = |zb_file: &ZBFile| match zb_file.multipart_fields().unwrap().remove(0) {
+            ZBMultipartField::File { file_name, .. } => file_name.unwrap(),
+            ZBMultipartField::Text { .. } => panic!("the file part comes first"),
+        };
+
+        let path = std::env::temp_dir().join(format!("zb-file-name-{}.csv", std::process::id()));
+        std::fs::write(&path, b"email\njohn@example.com\n").unwrap();
+        let zb_file = ZBFile::from_path(path.to_string_lossy().into_owned());
+        assert_eq!(file_name(&zb_file), path.file_name().unwrap().to_string_lossy());
+
+        // the name set on the file is used whether the file is streamed or converted
+        let zb_file = zb_file.set_file_name(Some("emails.csv"));
+        assert_eq!(file_name(&zb_file), "emails.csv");
+        std::fs::write(&path, b"email,name\nfrancois@example.com,Fran\xe7ois\n").unwrap();
+        assert_eq!(file_name(&zb_file), "emails.csv");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_split_repeats_header() {
+        let content = "name;email\nJohn;john@example.com\nJane;jane@example.com\nBob;bob@example.com\n";
+        let zb_file = ZBFile::from_content_with_filename(content.as_bytes().to_vec(), "emails.csv")
+            .set_email_address_column(2)
+            .set_remove_duplicate(true);
+        let chunks = zb_file.split(ZBSplitLimits::default().set_max_rows(2)).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert!(matches!(&chunks[1].content_type, ZBFileContentType::RawContent(chunk) if chunk == b"name;email\nBob;bob@example.com\n"));
+        assert_eq!(chunks[1].file_name.as_deref(), Some("emails_part2.csv"));
+        assert_eq!((chunks[1].email_address_column, chunks[1].remove_duplicate), (2, true));
+        assert_eq!(chunks.iter().map(|chunk| chunk.row_count().unwrap()).sum::<u64>(), 3);
+
+        let zb_file = ZBFile::from_content(b"john@example.com\n".to_vec()).set_has_header_row(false);
+        let chunks = zb_file.split(ZBSplitLimits::default()).unwrap();
+        assert_eq!((chunks.len(), chunks[0].has_header_row), (1, false));
+        assert!(ZBFile::from_content(b"email\n".to_vec()).split(ZBSplitLimits::default()).is_err());
+    }
+
+    #[test]
+    fn test_join_results_uses_settings() {
+        // The first row looks like data, but the file says it is a header
+        let zb_file = ZBFile::from_content(b"x@example.com,id\ny@example.com,1\n".to_vec()).set_has_header_row(true);
+        let results = "\"x@example.com\",\"id\",\"ZB Status\"\n\"y@example.com\",\"1\",\"valid\"\n";
+        let joined = zb_file.join_results(results.as_bytes()).unwrap();
+        assert_eq!(joined, b"x@example.com,id,ZB Status,ZB Join Status\ny@example.com,1,valid,matched\n");
+    }
 }
@@ -0,0 +1,168 @@
+//! Conversion of bulk file contents to UTF-8 CSV before their upload.
+//!
+//! Gzip and zip inputs are decompressed, taking the first CSV or TXT entry
+//! of a zip archive. The text is then transcoded to UTF-8: UTF-8 and UTF-16
+//! contents are recognized by their byte order mark, and legacy encodings
+//! (Latin-1, Windows-1252, ...) are guessed from the content.
+
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_8};
+use flate2::read::MultiGzDecoder;
+use zip::ZipArchive;
+
+use crate::utility::{ZBError, ZBResult};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// Bytes read from the start of a file to decide whether it needs to be
+/// converted (see `needs_conversion`).
+pub const SNIFF_LENGTH: usize = 64 * 1024;
+
+/// Whether content starting with `prefix` is compressed, starts with a byte
+/// order mark or is not UTF-8, and so is changed by `convert_content`.
+pub fn needs_conversion(prefix: &[u8]) -> bool {
+    if prefix.starts_with(GZIP_MAGIC) || prefix.starts_with(ZIP_MAGIC) || Encoding::for_bom(prefix).is_some() {
+        return true;
+    }
+    // A character may be cut at the end of the prefix
+    match std::str::from_utf8(prefix) {
+        Ok(_) => false,
+        Err(error) => error.error_len().is_some(),
+    }
+}
+
+/// Decompress and transcode content to UTF-8 CSV. `file_name` is the name
+/// of the original file; the name of the converted file is returned along,
+/// with a `.csv` extension unless it is a `.csv` or `.txt` file.
+pub fn convert_content(content: Vec<u8>, file_name: Option<&str>) -> ZBResult<(Vec<u8>, Option<String>)> {
+    let (content, file_name) = if content.starts_with(GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        MultiGzDecoder::new(content.as_slice()).read_to_end(&mut decompressed)?;
+        let file_name = file_name.map(|name| {
+            let stem = name.strip_suffix(".gz").or_else(|| name.strip_suffix(".GZ")).unwrap_or(name);
+            with_text_extension(stem)
+        });
+        (decompressed, file_name)
+    } else if content.starts_with(ZIP_MAGIC) {
+        let (decompressed, entry_name) = first_text_entry(content)?;
+        (decompressed, Some(with_text_extension(&entry_name)))
+    } else {
+        (content, file_name.map(str::to_string))
+    };
+
+    Ok((transcode(content), file_name))
+}
+
+fn with_text_extension(file_name: &str) -> String {
+    let path = Path::new(file_name);
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("csv") || extension.eq_ignore_ascii_case("txt") => {
+            file_name.to_string()
+        }
+        _ => path.with_extension("csv").to_string_lossy().into_owned(),
+    }
+}
+
+// Content and base name of the first CSV or TXT file of a zip archive.
+fn first_text_entry(content: Vec<u8>) -> ZBResult<(Vec<u8>, String)> {
+    let zip_error = |error: zip::result::ZipError| ZBError::IOError(error.into());
+    let mut archive = ZipArchive::new(Cursor::new(content)).map_err(zip_error)?;
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(zip_error)?;
+        let name = entry.name().to_string();
+        let base_name = name.rsplit('/').next().unwrap_or_default().to_string();
+        let is_text = [".csv", ".txt"].iter().any(|extension| base_name.to_lowercase().ends_with(extension));
+        // macOS stores resource forks as `__MACOSX/._name.csv`
+        if entry.is_dir() || !is_text || name.starts_with("__MACOSX/") || base_name.starts_with("._") {
+            continue;
+        }
+
+        let mut decompressed = Vec::new();
+        entry.read_to_end(&mut decompressed)?;
+        return Ok((decompressed, base_name));
+    }
+
+    Err(ZBError::validation("the zip archive holds no CSV or TXT file"))
+}
+
+// UTF-8 content without byte order mark.
+fn transcode(content: Vec<u8>) -> Vec<u8> {
+    let encoding = match Encoding::for_bom(&content) {
+        Some((encoding, bom_length)) if encoding == UTF_8 => return content[bom_length..].to_vec(),
+        Some((encoding, _)) => encoding,
+        None if std::str::from_utf8(&content).is_ok() => return content,
+        None => {
+            let mut detector = EncodingDetector::new();
+            detector.feed(&content, true);
+            detector.guess(None, true)
+        }
+    };
+
+    // Decoding with BOM sniffing removes the byte order mark
+    let (text, _) = encoding.decode_with_bom_removal(&content);
+    text.into_owned().into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    use super::*;
+
+    const CSV: &str = "email,name\nfrancois@example.com,François\n";
+
+    #[test]
+    fn test_transcode() {
+        let utf16le: Vec<u8> = [0xff, 0xfe].into_iter()
+            .chain(CSV.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        assert!(needs_conversion(&utf16le));
+        assert_eq!(convert_content(utf16le, None).unwrap().0, CSV.as_bytes());
+
+        let with_bom = [b"\xef\xbb\xbf".as_slice(), CSV.as_bytes()].concat();
+        assert_eq!(convert_content(with_bom, None).unwrap().0, CSV.as_bytes());
+
+        let latin1 = CSV.chars().map(|c| c as u8).collect::<Vec<u8>>();
+        assert!(needs_conversion(&latin1));
+        assert_eq!(convert_content(latin1, Some("list.csv")).unwrap(), (CSV.as_bytes().to_vec(), Some("list.csv".to_string())));
+
+        assert!(!needs_conversion(CSV.as_bytes()));
+        assert!(!needs_conversion(&CSV.as_bytes()[..CSV.find('ç').unwrap() + 1]));
+    }
+
+    #[test]
+    fn test_decompress() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(CSV.as_bytes()).unwrap();
+        let gzip = encoder.finish().unwrap();
+        assert!(needs_conversion(&gzip));
+        assert_eq!(convert_content(gzip.clone(), Some("list.csv.gz")).unwrap(), (CSV.as_bytes().to_vec(), Some("list.csv".to_string())));
+        assert_eq!(convert_content(gzip, Some("list.gz")).unwrap().1.as_deref(), Some("list.csv"));
+
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer.add_directory("export/", SimpleFileOptions::default()).unwrap();
+        writer.start_file("__MACOSX/export/._emails.txt", SimpleFileOptions::default()).unwrap();
+        writer.write_all(b"\x00\x05\x16\x07").unwrap();
+        writer.start_file("export/readme.md", SimpleFileOptions::default()).unwrap();
+        writer.write_all(b"# Export").unwrap();
+        writer.start_file("export/emails.TXT", SimpleFileOptions::default()).unwrap();
+        writer.write_all(CSV.as_bytes()).unwrap();
+        let zip = writer.finish().unwrap().into_inner();
+        assert_eq!(convert_content(zip, Some("export.zip")).unwrap(), (CSV.as_bytes().to_vec(), Some("emails.TXT".to_string())));
+
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("readme.md", SimpleFileOptions::default()).unwrap();
+        writer.write_all(b"# Export").unwrap();
+        let zip = writer.finish().unwrap().into_inner();
+        assert!(matches!(convert_content(zip, None), Err(ZBError::Validation(_))));
+    }
+}
@@ -0,0 +1,214 @@
+//! Local inspection of bulk files before their submission (see
+//! `ZBFile::inspect`).
+//!
+//! The delimiter, the header row and the column of the email addresses are
+//! guessed from the first rows of the file; rows and duplicate addresses are
+//! then counted over the whole file.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use csv::{ByteRecord, Reader, ReaderBuilder};
+
+use crate::utility::ZBResult;
+use crate::utility::precheck::is_valid_syntax;
+
+/// Delimiters recognized by the inspection, by order of preference.
+pub const DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+
+// Rows used to guess the layout of the file.
+const SAMPLE_ROWS: usize = 50;
+
+/// Layout of a bulk file, as detected by `ZBFile::inspect`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZBFileInspection {
+    pub delimiter: char,
+    /// Whether the first row is a header rather than data.
+    pub has_header_row: bool,
+    /// Cells of the header row, when there is one.
+    pub headers: Option<Vec<String>>,
+    /// Column holding the email addresses, numbered from 1; `None` when no
+    /// column does.
+    pub email_address_column: Option<u32>,
+    /// Largest number of cells in a row.
+    pub column_count: u32,
+    /// Rows of data, without the header row.
+    pub row_count: u64,
+    /// Rows whose email address, regardless of case, appears in a previous
+    /// row.
+    pub duplicate_count: u64,
+    /// Settings of the file that do not match its content.
+    pub issues: Vec<ZBFileIssue>,
+}
+
+impl ZBFileInspection {
+    /// Whether the settings of the file match its content.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Setting of a `ZBFile` that does not match its content.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ZBFileIssue {
+    /// A column setting (`email_address_column`, `first_name_column`, ...)
+    /// points outside of the columns of the file.
+    ColumnOutOfRange { setting: &'static str, column: u32, column_count: u32 },
+    /// The email addresses are in another column than `email_address_column`.
+    EmailColumnMismatch { configured: u32, detected: u32 },
+    /// No column holds email addresses.
+    NoEmailColumn,
+    /// `has_header_row` does not match the first row of the file.
+    HeaderRowMismatch { configured: bool, detected: bool },
+}
+
+impl fmt::Display for ZBFileIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZBFileIssue::ColumnOutOfRange { setting, column, column_count } => {
+                write!(f, "{} is {}, but the file has {} column(s)", setting, column, column_count)
+            }
+            ZBFileIssue::EmailColumnMismatch { configured, detected } => {
+                write!(f, "email_address_column is {}, but the addresses are in column {}", configured, detected)
+            }
+            ZBFileIssue::NoEmailColumn => write!(f, "no column holds email addresses"),
+            ZBFileIssue::HeaderRowMismatch { configured, detected } => {
+                write!(f, "has_header_row is {}, but the first row looks like {}", configured,
+                    if *detected { "a header" } else { "data" })
+            }
+        }
+    }
+}
+
+fn csv_reader(content: &[u8], delimiter: char) -> Reader<&[u8]> {
+    ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter as u8)
+        .from_reader(content)
+}
+
+fn cell(record: &ByteRecord, index: usize) -> String {
+    String::from_utf8_lossy(record.get(index).unwrap_or_default())
+        .trim_start_matches('\u{feff}')
+        .trim()
+        .to_string()
+}
+
+// The delimiter splitting the sample rows into the same number of cells most
+// often, then into the most cells. Single column files are comma separated.
+pub(crate) fn detect_delimiter(content: &[u8]) -> char {
+    let mut best = (DELIMITERS[0], 0, 0);
+    for delimiter in DELIMITERS {
+        let lengths: Vec<usize> = csv_reader(content, delimiter)
+            .byte_records()
+            .take(SAMPLE_ROWS)
+            .map_while(Result::ok)
+            .map(|record| record.len())
+            .collect();
+        let Some(&length) = lengths.iter().max_by_key(|&&length| lengths.iter().filter(|&&other| other == length).count()) else {
+            continue;
+        };
+        let matching = lengths.iter().filter(|&&other| other == length).count();
+        if length > 1 && (matching, length) > (best.1, best.2) {
+            best = (delimiter, matching, length);
+        }
+    }
+    best.0
+}
+
+/// Detect the layout of CSV content. `issues` is left empty.
+pub(crate) fn inspect_content(content: &[u8]) -> ZBResult<ZBFileInspection> {
+    let delimiter = detect_delimiter(content);
+    let sample = csv_reader(content, delimiter)
+        .byte_records()
+        .take(SAMPLE_ROWS)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Column holding the most addresses, ignoring the first row which may be
+    // a header
+    let data_sample = if sample.len() > 1 { &sample[1..] } else { &sample[..] };
+    let sample_columns = sample.iter().map(ByteRecord::len).max().unwrap_or(0);
+    let email_index = (0..sample_columns)
+        .map(|index| {
+            let emails = data_sample.iter().filter(|record| is_valid_syntax(&cell(record, index))).count();
+            (index, emails)
+        })
+        .filter(|&(_, emails)| emails > 0)
+        .max_by_key(|&(index, emails)| (emails, std::cmp::Reverse(index)))
+        .map(|(index, _)| index);
+
+    let has_header_row = match (sample.first(), email_index) {
+        (None, _) => false,
+        (Some(first), Some(index)) => !is_valid_syntax(&cell(first, index)),
+        (Some(first), None) => (0..first.len()).any(|index| cell(first, index).to_lowercase().contains("email")),
+    };
+
+    let mut inspection = ZBFileInspection {
+        delimiter,
+        has_header_row,
+        headers: None,
+        email_address_column: email_index.map(|index| index as u32 + 1),
+        column_count: 0,
+        row_count: 0,
+        duplicate_count: 0,
+        issues: Vec::new(),
+    };
+
+    let mut emails = HashSet::new();
+    for (row, record) in csv_reader(content, delimiter).byte_records().enumerate() {
+        let record = record?;
+        inspection.column_count = inspection.column_count.max(record.len() as u32);
+        if row == 0 && has_header_row {
+            inspection.headers = Some((0..record.len()).map(|index| cell(&record, index)).collect());
+            continue;
+        }
+
+        inspection.row_count += 1;
+        if let Some(index) = email_index {
+            let email = cell(&record, index).to_lowercase();
+            if !email.is_empty() && !emails.insert(email) {
+                inspection.duplicate_count += 1;
+            }
+        }
+    }
+
+    Ok(inspection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_delimiter() {
+        assert_eq!(detect_delimiter(b"email;name\na@example.com;Doe, John\nb@example.com;Roe, Jane\n"), ';');
+        assert_eq!(detect_delimiter(b"a@example.com\tJohn\tDoe\nb@example.com\tJane\tRoe\n"), '\t');
+        assert_eq!(detect_delimiter(b"a@example.com|1\nb@example.com|2\n"), '|');
+        assert_eq!(detect_delimiter(b"\"Doe; John\",a@example.com\n\"Roe; Jane\",b@example.com\n"), ',');
+        assert_eq!(detect_delimiter(b"a@example.com\nb@example.com\n"), ',');
+    }
+
+    #[test]
+    fn test_inspect_content() {
+        let content = b"\xef\xbb\xbfName;E-mail\nJohn;john@example.com\nJane;JANE@example.com\nJane;jane@example.com\nBob;\n";
+        let inspection = inspect_content(content).unwrap();
+        assert_eq!(inspection.delimiter, ';');
+        assert!(inspection.has_header_row);
+        assert_eq!(inspection.headers, Some(vec!["Name".to_string(), "E-mail".to_string()]));
+        assert_eq!(inspection.email_address_column, Some(2));
+        assert_eq!(inspection.column_count, 2);
+        assert_eq!(inspection.row_count, 4);
+        assert_eq!(inspection.duplicate_count, 1);
+
+        let inspection = inspect_content(b"john@example.com\njane@example.com\n").unwrap();
+        assert!(!inspection.has_header_row);
+        assert_eq!(inspection.headers, None);
+        assert_eq!(inspection.email_address_column, Some(1));
+        assert_eq!(inspection.row_count, 2);
+
+        let inspection = inspect_content(b"Email,Name\nnot an address,John\n").unwrap();
+        assert!(inspection.has_header_row);
+        assert_eq!(inspection.email_address_column, None);
+    }
+}
@@ -0,0 +1,257 @@
+//! Bulk files built from in-memory records.
+//!
+//! `ZBFileBuilder` writes the records as CSV, with a header row and the
+//! quoting needed by commas, quotes and line breaks in the values, and sets
+//! the column numbers of the resulting `ZBFile` to match.
+
+use crate::utility::{ZBError, ZBResult};
+use crate::utility::structures::bulk::ZBFile;
+
+/// Row of a file built with `ZBFileBuilder`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ZBFileRecord {
+    pub email: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub gender: Option<String>,
+    pub ip_address: Option<String>,
+    /// Other columns, as `(header, value)` pairs. They are not used by the
+    /// API and come back unchanged in the result file.
+    pub extra: Vec<(String, String)>,
+}
+
+impl ZBFileRecord {
+    pub fn new(email: impl Into<String>) -> ZBFileRecord {
+        ZBFileRecord {
+            email: email.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_first_name(mut self, first_name: impl Into<String>) -> Self {
+        self.first_name = Some(first_name.into());
+        self
+    }
+
+    pub fn set_last_name(mut self, last_name: impl Into<String>) -> Self {
+        self.last_name = Some(last_name.into());
+        self
+    }
+
+    pub fn set_gender(mut self, gender: impl Into<String>) -> Self {
+        self.gender = Some(gender.into());
+        self
+    }
+
+    pub fn set_ip_address(mut self, ip_address: impl Into<String>) -> Self {
+        self.ip_address = Some(ip_address.into());
+        self
+    }
+
+    /// Add a passthrough column; the record keeps the first value given for
+    /// a header.
+    pub fn add_column(mut self, header: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra.push((header.into(), value.into()));
+        self
+    }
+
+    fn extra_value(&self, header: &str) -> &str {
+        self.extra.iter()
+            .find(|(name, _)| name == header)
+            .map_or("", |(_, value)| value.as_str())
+    }
+}
+
+impl<T: Into<String>> From<T> for ZBFileRecord {
+    fn from(email: T) -> Self {
+        ZBFileRecord::new(email)
+    }
+}
+
+// Reads the value of an optional column from a record.
+type ColumnValue = fn(&ZBFileRecord) -> Option<&String>;
+
+// Header and content of the optional columns, in file order.
+const OPTIONAL_COLUMNS: [(&str, ColumnValue); 4] = [
+    ("first_name", |record| record.first_name.as_ref()),
+    ("last_name", |record| record.last_name.as_ref()),
+    ("gender", |record| record.gender.as_ref()),
+    ("ip_address", |record| record.ip_address.as_ref()),
+];
+
+/// Builder of a bulk `ZBFile` from records.
+///
+/// The file starts with an `email` column, followed by the `first_name`,
+/// `last_name`, `gender` and `ip_address` columns given by at least one
+/// record, then by the passthrough columns in the order they first appear.
+/// Records missing a column get an empty cell.
+///
+/// # Example
+/// ```no_run
+/// use zero_bounce::{ZeroBounce, ZBFileBuilder, ZBFileRecord};
+/// use zero_bounce::utility::ZBResult;
+///
+/// # fn main() -> ZBResult<()> {
+/// let zb = ZeroBounce::new("your_api_key");
+/// let mut builder = ZBFileBuilder::new();
+/// builder.push(ZBFileRecord::new("john.doe@example.com").set_first_name("John").add_column("crm_id", "42"));
+/// builder.push(ZBFileRecord::new("jane@example.com").set_last_name("Doe, Jr."));
+///
+/// let zb_file = builder.build()?.set_remove_duplicate(true);
+/// let submit_result = zb.bulk_validation_file_submit(&zb_file)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ZBFileBuilder {
+    records: Vec<ZBFileRecord>,
+}
+
+impl ZBFileBuilder {
+    pub fn new() -> ZBFileBuilder {
+        Self::default()
+    }
+
+    pub fn push(&mut self, record: impl Into<ZBFileRecord>) -> &mut Self {
+        self.records.push(record.into());
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    // Whether each of `OPTIONAL_COLUMNS` is given by at least one record.
+    fn optional_columns(&self) -> [bool; 4] {
+        OPTIONAL_COLUMNS.map(|(_, value)| self.records.iter().any(|record| value(record).is_some()))
+    }
+
+    /// CSV content of the records, with its header row.
+    pub fn to_csv(&self) -> ZBResult<Vec<u8>> {
+        let optional_columns: Vec<_> = OPTIONAL_COLUMNS.iter()
+            .zip(self.optional_columns())
+            .filter_map(|(column, present)| present.then_some(column))
+            .collect();
+        let mut extra_headers: Vec<&str> = Vec::new();
+        for (header, _) in self.records.iter().flat_map(|record| &record.extra) {
+            if !extra_headers.contains(&header.as_str()) {
+                extra_headers.push(header);
+            }
+        }
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        let mut headers = vec!["email"];
+        headers.extend(optional_columns.iter().map(|(header, _)| *header));
+        headers.extend(&extra_headers);
+        writer.write_record(&headers)?;
+
+        for record in &self.records {
+            let mut row = vec![record.email.as_str()];
+            row.extend(optional_columns.iter().map(|(_, value)| value(record).map_or("", String::as_str)));
+            row.extend(extra_headers.iter().map(|header| record.extra_value(header)));
+            writer.write_record(&row)?;
+        }
+
+        writer.into_inner().map_err(|error| ZBError::IOError(error.into_error()))
+    }
+
+    /// File holding the records, with a header row and the column settings
+    /// matching its layout. Fails when no record was pushed.
+    pub fn build(&self) -> ZBResult<ZBFile> {
+        if self.records.is_empty() {
+            return Err(ZBError::validation("bulk content cannot be empty"));
+        }
+
+        // Columns are numbered from 1, the email being the first one
+        let mut column = 1;
+        let [first_name_column, last_name_column, gender_column, ip_address_column] =
+            self.optional_columns().map(|present| present.then(|| {
+                column += 1;
+                column
+            }));
+
+        Ok(ZBFile::from_content(self.to_csv()?)
+            .set_has_header_row(true)
+            .set_email_address_column(1)
+            .set_first_name_column(first_name_column)
+            .set_last_name_column(last_name_column)
+            .set_gender_column(gender_column)
+            .set_ip_address_column(ip_address_column))
+    }
+}
+
+impl<R: Into<ZBFileRecord>> Extend<R> for ZBFileBuilder {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, records: I) {
+        self.records.extend(records.into_iter().map(Into::into));
+    }
+}
+
+impl<R: Into<ZBFileRecord>> FromIterator<R> for ZBFileBuilder {
+    fn from_iter<I: IntoIterator<Item = R>>(records: I) -> Self {
+        let mut builder = ZBFileBuilder::new();
+        builder.extend(records);
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::ZBMultipartField;
+
+    #[test]
+    fn test_records_are_escaped() {
+        let mut builder = ZBFileBuilder::new();
+        builder.push(ZBFileRecord::new("john@example.com").set_last_name("Doe, Jr.").add_column("note", "says \"hi\""));
+        builder.push(ZBFileRecord::new("jane@example.com").set_ip_address("1.1.1.1").add_column("note", "two\nlines"));
+        builder.push("bob@example.com");
+
+        let content = String::from_utf8(builder.to_csv().unwrap()).unwrap();
+        assert_eq!(content, concat!(
+            "email,last_name,ip_address,note\n",
+            "john@example.com,\"Doe, Jr.\",,\"says \"\"hi\"\"\"\n",
+            "jane@example.com,,1.1.1.1,\"two\nlines\"\n",
+            "bob@example.com,,,\n",
+        ));
+    }
+
+    #[test]
+    fn test_extra_columns_follow_first_appearance() {
+        let builder: ZBFileBuilder = [
+            ZBFileRecord::new("a@example.com").add_column("b", "1"),
+            ZBFileRecord::new("b@example.com").add_column("a", "2").add_column("b", "3").add_column("b", "ignored"),
+        ].into_iter().collect();
+
+        let content = String::from_utf8(builder.to_csv().unwrap()).unwrap();
+        assert_eq!(content, "email,b,a\na@example.com,1,\nb@example.com,3,2\n");
+        assert_eq!(builder.len(), 2);
+        assert!(ZBFileBuilder::new().build().is_err());
+    }
+
+    #[test]
+    fn test_build_sets_columns() {
+        let builder: ZBFileBuilder = [
+            ZBFileRecord::new("john@example.com").set_first_name("John").set_gender("male"),
+            ZBFileRecord::new("jane@example.com").set_ip_address("1.1.1.1"),
+        ].into_iter().collect();
+
+        let fields: Vec<(String, String)> = builder.build().unwrap().multipart_fields().unwrap()
+            .into_iter()
+            .filter_map(|field| match field {
+                ZBMultipartField::Text { name, value } => Some((name, value)),
+                ZBMultipartField::File { .. } => None,
+            })
+            .collect();
+        let field = |name: &str| fields.iter().find(|(field, _)| field == name).map(|(_, value)| value.as_str());
+        assert_eq!(field("has_header_row"), Some("true"));
+        assert_eq!(field("email_address_column"), Some("1"));
+        assert_eq!(field("first_name_column"), Some("2"));
+        assert_eq!(field("last_name_column"), None);
+        assert_eq!(field("gender_column"), Some("3"));
+        assert_eq!(field("ip_address_column"), Some("4"));
+    }
+}
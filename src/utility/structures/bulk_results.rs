@@ -0,0 +1,513 @@
+//! Typed rows of the result files downloaded from the bulk getfile endpoints.
+//!
+//! A result file holds the columns of the submitted file followed by the
+//! columns appended by ZeroBounce (`ZB Status`, `ZB Sub Status`, ...).
+//! Appended columns are recognized by name, whatever their case, spacing
+//! and position.
+//!
+//! A result file may differ from the submitted file (duplicates removed with
+//! `remove_duplicate`, rows in another order, ...); `join_results` appends
+//! its columns to the rows of the original file instead.
+
+use std::collections::{HashMap, VecDeque};
+use std::io::Read;
+use std::str::FromStr;
+
+use csv::{ByteRecord, ReaderBuilder, StringRecord, WriterBuilder};
+
+use crate::utility::{ZBError, ZBResult};
+use crate::utility::structures::bulk_content::convert_content;
+use crate::utility::structures::bulk_inspection::inspect_content;
+use crate::utility::structures::validate_enums::{ZBValidateStatus, ZBValidateSubStatus};
+
+/// Row of a bulk validation result file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZBValidationResultRow {
+    /// Columns of the submitted file, as `(header, value)` pairs in their
+    /// original order.
+    pub input: Vec<(String, String)>,
+    /// Value of the first input column whose header mentions "email" (the
+    /// first input column when none does).
+    pub email: String,
+    pub status: ZBValidateStatus,
+    pub sub_status: ZBValidateSubStatus,
+    pub account: Option<String>,
+    pub domain: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub gender: Option<String>,
+    pub free_email: Option<bool>,
+    pub mx_found: Option<bool>,
+    pub mx_record: Option<String>,
+    pub smtp_provider: Option<String>,
+    pub did_you_mean: Option<String>,
+    pub domain_age_days: Option<u32>,
+    /// Activity data columns, only present when the file was downloaded with
+    /// `activity_data` enabled.
+    pub last_known_activity: Option<String>,
+    pub activity_data_count: Option<u32>,
+    pub activity_data_types: Option<String>,
+    pub activity_data_channels: Option<String>,
+    /// Appended columns without a dedicated field, as `(header, value)` pairs.
+    pub extra: Vec<(String, String)>,
+}
+
+/// Row of an AI scoring result file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZBScoringResultRow {
+    /// Columns of the submitted file, as `(header, value)` pairs in their
+    /// original order.
+    pub input: Vec<(String, String)>,
+    pub email: String,
+    /// Score from 0 to 10; `None` when the cell is empty.
+    pub score: Option<f32>,
+    /// Appended columns other than the score, as `(header, value)` pairs.
+    pub extra: Vec<(String, String)>,
+}
+
+/// Parse the content of a bulk validation result file, e.g. the bytes of
+/// `ZBBulkResponse::Content` or an opened file.
+///
+/// # Example
+/// ```no_run
+/// use zero_bounce::ZeroBounce;
+/// use zero_bounce::utility::structures::bulk::ZBBulkResponse;
+/// use zero_bounce::utility::structures::bulk_results::parse_validation_results;
+/// use zero_bounce::utility::structures::ZBValidateStatus;
+/// use zero_bounce::utility::ZBResult;
+///
+/// # fn main() -> ZBResult<()> {
+/// let zb = ZeroBounce::new("your_api_key");
+/// if let ZBBulkResponse::Content(content) = zb.bulk_validation_result_fetch("file_id_here")? {
+///     for row in parse_validation_results(&content[..])? {
+///         if row.status == ZBValidateStatus::Valid {
+///             println!("{}", row.email);
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_validation_results<R: Read>(reader: R) -> ZBResult<Vec<ZBValidationResultRow>> {
+    let mut csv_reader = ReaderBuilder::new().flexible(true).from_reader(reader);
+    let columns = ResultColumns::new(csv_reader.headers()?)?;
+    if columns.appended("zbstatus").is_none() {
+        return Err(missing_column("ZB Status"));
+    }
+
+    let mut rows = Vec::new();
+    for record in csv_reader.records() {
+        let record = record?;
+        let row = ResultRow { columns: &columns, record: &record };
+
+        rows.push(ZBValidationResultRow {
+            input: row.input(),
+            email: row.email(),
+            status: row.parsed("zbstatus")?.unwrap_or(ZBValidateStatus::None),
+            sub_status: row.parsed("zbsubstatus")?.unwrap_or(ZBValidateSubStatus::None),
+            account: row.text("zbaccount"),
+            domain: row.text("zbdomain"),
+            first_name: row.text("zbfirstname"),
+            last_name: row.text("zblastname"),
+            gender: row.text("zbgender"),
+            free_email: row.flag("zbfreeemail")?,
+            mx_found: row.flag("zbmxfound")?,
+            mx_record: row.text("zbmxrecord"),
+            smtp_provider: row.text("zbsmtpprovider"),
+            did_you_mean: row.text("zbdidyoumean"),
+            domain_age_days: row.parsed("zbdomainagedays")?,
+            last_known_activity: row.text("zblastknownactivity"),
+            activity_data_count: row.parsed("zbactivitydatacount")?,
+            activity_data_types: row.text("zbactivitydatatypes"),
+            activity_data_channels: row.text("zbactivitydatachannels"),
+            extra: row.extra(VALIDATION_COLUMNS),
+        });
+    }
+    Ok(rows)
+}
+
+/// Parse the content of an AI scoring result file.
+pub fn parse_scoring_results<R: Read>(reader: R) -> ZBResult<Vec<ZBScoringResultRow>> {
+    let mut csv_reader = ReaderBuilder::new().flexible(true).from_reader(reader);
+    let columns = ResultColumns::new(csv_reader.headers()?)?;
+    let score_column = SCORE_COLUMNS.iter()
+        .find(|name| columns.appended(name).is_some())
+        .ok_or_else(|| missing_column("ZeroBounce Score"))?;
+
+    let mut rows = Vec::new();
+    for record in csv_reader.records() {
+        let record = record?;
+        let row = ResultRow { columns: &columns, record: &record };
+
+        rows.push(ZBScoringResultRow {
+            input: row.input(),
+            email: row.email(),
+            score: row.parsed(score_column)?,
+            extra: row.extra(&[score_column]),
+        });
+    }
+    Ok(rows)
+}
+
+/// Column added by `join_results` after the columns of the result file,
+/// telling how each row of the original file was matched.
+pub const JOIN_STATUS_COLUMN: &str = "ZB Join Status";
+/// The row got its own row of the result file.
+pub const JOIN_MATCHED: &str = "matched";
+/// The row was dropped from the result file as a duplicate; it holds the
+/// result of a previous row with the same address.
+pub const JOIN_DUPLICATE: &str = "duplicate";
+/// The address of the row is not in the result file; its result columns
+/// are empty.
+pub const JOIN_DROPPED: &str = "dropped";
+
+/// Append the columns of a result file to the rows of the original input
+/// file, matching rows by email address regardless of case and surrounding
+/// spaces. The input keeps its rows, order and delimiter; it is converted
+/// to UTF-8 first (see `bulk_content`), and its delimiter, header row and
+/// email column are detected (see `ZBFile::inspect`). Use
+/// `ZBFile::join_results` to rely on the settings of a file instead.
+///
+/// The output always starts with a header row, and ends with the
+/// `JOIN_STATUS_COLUMN` column (`JOIN_MATCHED`, `JOIN_DUPLICATE` or
+/// `JOIN_DROPPED`).
+///
+/// # Example
+/// ```no_run
+/// use std::fs::File;
+/// use zero_bounce::{ZeroBounce, ZBBulkResponse};
+/// use zero_bounce::utility::structures::join_results;
+/// use zero_bounce::utility::ZBResult;
+///
+/// # fn main() -> ZBResult<()> {
+/// let zb = ZeroBounce::new("your_api_key");
+/// if let ZBBulkResponse::Content(content) = zb.bulk_validation_result_fetch("file_id_here")? {
+///     let joined = join_results(File::open("emails.csv")?, &content[..])?;
+///     std::fs::write("emails_validated.csv", joined)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn join_results<I: Read, R: Read>(mut input: I, results: R) -> ZBResult<Vec<u8>> {
+    let mut content = Vec::new();
+    input.read_to_end(&mut content)?;
+    let (content, _) = convert_content(content, None)?;
+
+    let inspection = inspect_content(&content)?;
+    let email_column = inspection.email_address_column
+        .ok_or_else(|| ZBError::validation("no column of the input holds email addresses"))?;
+    join_content(&content, inspection.delimiter as u8, inspection.has_header_row, email_column, results)
+}
+
+// Append the columns of a result file to the rows of UTF-8 input content.
+// `email_column` is numbered from 1.
+pub(crate) fn join_content<R: Read>(
+    input: &[u8],
+    delimiter: u8,
+    has_header_row: bool,
+    email_column: u32,
+    results: R,
+) -> ZBResult<Vec<u8>> {
+    let email_index = (email_column as usize).saturating_sub(1);
+    let mut rows = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(input)
+        .into_byte_records()
+        .collect::<Result<Vec<_>, _>>()?;
+    let input_header = match has_header_row && !rows.is_empty() {
+        true => Some(rows.remove(0)),
+        false => None,
+    };
+
+    let mut csv_reader = ReaderBuilder::new().flexible(true).from_reader(results);
+    let result_headers = csv_reader.headers()?.clone();
+    let columns = ResultColumns::new(&result_headers)?;
+    // The result file repeats the input columns, hence the email column
+    let result_email = columns.input.get(email_index).map_or(columns.email, |(index, _)| *index);
+
+    let mut results_by_email: HashMap<String, VecDeque<Vec<String>>> = HashMap::new();
+    for record in csv_reader.records() {
+        let record = record?;
+        let appended = columns.appended.iter()
+            .map(|(index, _, _)| record.get(*index).unwrap_or_default().to_string())
+            .collect();
+        let email = normalize_email(record.get(result_email).unwrap_or_default().as_bytes());
+        results_by_email.entry(email).or_default().push_back(appended);
+    }
+
+    let input_width = rows.iter().chain(&input_header).map(ByteRecord::len).max().unwrap_or(0);
+    let mut header = match &input_header {
+        Some(header) => header.clone(),
+        None => (0..input_width)
+            .map(|index| match columns.input.get(index) {
+                Some((_, name)) => name.clone(),
+                None => format!("column {}", index + 1),
+            })
+            .collect(),
+    };
+    pad(&mut header, input_width);
+    for (_, _, name) in &columns.appended {
+        header.push_field(name.as_bytes());
+    }
+    header.push_field(JOIN_STATUS_COLUMN.as_bytes());
+
+    let mut writer = WriterBuilder::new().flexible(true).delimiter(delimiter).from_writer(Vec::new());
+    writer.write_byte_record(&header)?;
+
+    let mut matched: HashMap<String, Vec<String>> = HashMap::new();
+    let empty = vec![String::new(); columns.appended.len()];
+    for mut row in rows {
+        let email = normalize_email(row.get(email_index).unwrap_or_default());
+        let (appended, status) = match results_by_email.get_mut(&email).and_then(VecDeque::pop_front) {
+            Some(appended) => {
+                matched.insert(email.clone(), appended);
+                (&matched[&email], JOIN_MATCHED)
+            }
+            None => match matched.get(&email) {
+                Some(appended) => (appended, JOIN_DUPLICATE),
+                None => (&empty, JOIN_DROPPED),
+            },
+        };
+
+        pad(&mut row, input_width);
+        for value in appended {
+            row.push_field(value.as_bytes());
+        }
+        row.push_field(status.as_bytes());
+        writer.write_byte_record(&row)?;
+    }
+
+    writer.into_inner().map_err(|error| ZBError::IOError(error.into_error()))
+}
+
+fn normalize_email(email: &[u8]) -> String {
+    String::from_utf8_lossy(email).trim().to_lowercase()
+}
+
+fn pad(record: &mut ByteRecord, width: usize) {
+    while record.len() < width {
+        record.push_field(b"");
+    }
+}
+
+// Normalized names of the appended columns mapped onto fields.
+const VALIDATION_COLUMNS: &[&str] = &[
+    "zbstatus", "zbsubstatus", "zbaccount", "zbdomain", "zbfirstname", "zblastname", "zbgender",
+    "zbfreeemail", "zbmxfound", "zbmxrecord", "zbsmtpprovider", "zbdidyoumean", "zbdomainagedays",
+    "zblastknownactivity", "zbactivitydatacount", "zbactivitydatatypes", "zbactivitydatachannels",
+];
+const SCORE_COLUMNS: &[&str] = &["zerobouncescore", "zbscore"];
+
+// Lowercase alphanumeric form of a header: "ZB Sub Status" -> "zbsubstatus".
+fn normalize(header: &str) -> String {
+    header.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn missing_column(name: &str) -> ZBError {
+    ZBError::CsvError { line: Some(1), message: format!("missing column {:?}", name) }
+}
+
+// Position of the input and appended columns of a result file.
+struct ResultColumns {
+    input: Vec<(usize, String)>,
+    appended: Vec<(usize, String, String)>,
+    email: usize,
+}
+
+impl ResultColumns {
+    fn new(headers: &StringRecord) -> ZBResult<ResultColumns> {
+        let mut input = Vec::new();
+        let mut appended = Vec::new();
+        for (index, header) in headers.iter().enumerate() {
+            let normalized = normalize(header);
+            if normalized.starts_with("zb") || normalized.starts_with("zerobounce") {
+                appended.push((index, normalized, header.to_string()));
+            } else {
+                input.push((index, header.to_string()));
+            }
+        }
+
+        let email = input.iter()
+            .find(|(_, header)| normalize(header).contains("email"))
+            .or_else(|| input.first())
+            .map(|(index, _)| *index)
+            .ok_or_else(|| ZBError::CsvError {
+                line: Some(1),
+                message: "no input column in the result file".to_string(),
+            })?;
+
+        Ok(ResultColumns { input, appended, email })
+    }
+
+    fn appended(&self, name: &str) -> Option<usize> {
+        self.appended.iter()
+            .find(|(_, normalized, _)| normalized == name)
+            .map(|(index, _, _)| *index)
+    }
+}
+
+struct ResultRow<'a> {
+    columns: &'a ResultColumns,
+    record: &'a StringRecord,
+}
+
+impl ResultRow<'_> {
+    fn line(&self) -> Option<u64> {
+        self.record.position().map(|position| position.line())
+    }
+
+    fn input(&self) -> Vec<(String, String)> {
+        self.columns.input.iter()
+            .map(|(index, header)| (header.clone(), self.record.get(*index).unwrap_or_default().to_string()))
+            .collect()
+    }
+
+    fn email(&self) -> String {
+        self.record.get(self.columns.email).unwrap_or_default().trim().to_string()
+    }
+
+    fn extra(&self, known: &[&str]) -> Vec<(String, String)> {
+        self.columns.appended.iter()
+            .filter(|(_, normalized, _)| !known.contains(&normalized.as_str()))
+            .map(|(index, _, header)| (header.clone(), self.record.get(*index).unwrap_or_default().to_string()))
+            .collect()
+    }
+
+    // Trimmed value of an appended column; empty cells and missing columns
+    // give `None`.
+    fn text(&self, name: &str) -> Option<String> {
+        let value = self.record.get(self.columns.appended(name)?)?.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    fn parsed<T: FromStr>(&self, name: &str) -> ZBResult<Option<T>>
+    where
+        T::Err: std::fmt::Display,
+    {
+        self.text(name)
+            .map(|value| value.to_ascii_lowercase().parse::<T>().map_err(|error| ZBError::CsvError {
+                line: self.line(),
+                message: format!("invalid {} {:?}: {}", name, value, error),
+            }))
+            .transpose()
+    }
+
+    fn flag(&self, name: &str) -> ZBResult<Option<bool>> {
+        self.text(name)
+            .map(|value| match value.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "1" => Ok(true),
+                "false" | "no" | "0" => Ok(false),
+                _ => Err(ZBError::CsvError {
+                    line: self.line(),
+                    message: format!("invalid {} {:?}: expected true or false", name, value),
+                }),
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utility::mock_constants::{AI_SCORING_RESULT_CSV, BULK_VALIDATION_RESULT_CSV};
+
+    #[test]
+    fn test_parse_validation_results() {
+        let rows = parse_validation_results(BULK_VALIDATION_RESULT_CSV.as_bytes()).unwrap();
+        assert_eq!(rows.len(), 3);
+
+        let valid = &rows[0];
+        assert_eq!(valid.email, "valid@example.com");
+        assert_eq!(valid.input, vec![
+            ("email".to_string(), "valid@example.com".to_string()),
+            ("first name".to_string(), "John".to_string()),
+        ]);
+        assert_eq!(valid.status, ZBValidateStatus::Valid);
+        assert_eq!(valid.sub_status, ZBValidateSubStatus::None);
+        assert_eq!(valid.free_email, Some(false));
+        assert_eq!(valid.mx_found, Some(true));
+        assert_eq!(valid.domain_age_days, Some(9692));
+        assert_eq!(valid.activity_data_count, Some(12));
+        assert_eq!(valid.activity_data_types.as_deref(), Some("login,purchase"));
+        assert!(valid.extra.is_empty());
+
+        assert_eq!(rows[1].sub_status, ZBValidateSubStatus::MailboxNotFound);
+        assert_eq!(rows[1].last_known_activity, None);
+        assert_eq!(rows[2].sub_status, ZBValidateSubStatus::PossibleTypo);
+        assert_eq!(rows[2].did_you_mean.as_deref(), Some("user@gmail.com"));
+        assert_eq!(rows[2].domain_age_days, None);
+    }
+
+    #[test]
+    fn test_parse_validation_results_minimal_and_unknown_columns() {
+        let content = "Email Address,ZB Status,zb sub status,ZB Processed At\n\
+                       a@example.com,Catch-All,,2024-01-01\n\
+                       b@example.com,brand_new_status,,2024-01-01\n";
+        let rows = parse_validation_results(content.as_bytes()).unwrap();
+        assert_eq!(rows[0].status, ZBValidateStatus::CatchAll);
+        assert_eq!(rows[0].free_email, None);
+        assert_eq!(rows[0].extra, vec![("ZB Processed At".to_string(), "2024-01-01".to_string())]);
+        assert_eq!(rows[1].status, ZBValidateStatus::UnknownValue("brand_new_status".to_string()));
+    }
+
+    #[test]
+    fn test_parse_results_errors() {
+        let error = parse_validation_results("email,other\na@example.com,x\n".as_bytes()).unwrap_err();
+        assert!(matches!(error, ZBError::CsvError { line: Some(1), .. }), "{:?}", error);
+
+        let content = "email,ZB Status,ZB Free Email\na@example.com,valid,maybe\n";
+        let error = parse_validation_results(content.as_bytes()).unwrap_err();
+        assert!(matches!(error, ZBError::CsvError { line: Some(2), .. }), "{:?}", error);
+
+        let content = "email,ZeroBounce Score\na@example.com,high\n";
+        let error = parse_scoring_results(content.as_bytes()).unwrap_err();
+        assert!(matches!(error, ZBError::CsvError { line: Some(2), .. }), "{:?}", error);
+    }
+
+    #[test]
+    fn test_parse_scoring_results() {
+        let rows = parse_scoring_results(AI_SCORING_RESULT_CSV.as_bytes()).unwrap();
+        let scores = rows.iter().map(|row| row.score).collect::<Vec<_>>();
+        assert_eq!(scores, vec![Some(10.), Some(0.), None]);
+        assert_eq!(rows[1].email, "invalid@example.com");
+        assert!(rows[0].extra.is_empty());
+    }
+
+    #[test]
+    fn test_join_results() {
+        let input = "name;email;crm_id\n\
+                     John;valid@example.com;1\n\
+                     Jane;INVALID@example.com ;2\n\
+                     John;Valid@Example.com;3\n\
+                     Bob;bob@example.com;4\n";
+        // Reordered, duplicates removed, and an unexpected column
+        let results = "\"name\",\"email\",\"crm_id\",\"ZB Status\",\"ZB Sub Status\"\n\
+                       \"Jane\",\"invalid@example.com\",\"2\",\"invalid\",\"mailbox_not_found\"\n\
+                       \"John\",\"valid@example.com\",\"1\",\"valid\",\"\"\n";
+        let joined = join_results(input.as_bytes(), results.as_bytes()).unwrap();
+        assert_eq!(String::from_utf8(joined).unwrap(), "\
+            name;email;crm_id;ZB Status;ZB Sub Status;ZB Join Status\n\
+            John;valid@example.com;1;valid;;matched\n\
+            Jane;INVALID@example.com ;2;invalid;mailbox_not_found;matched\n\
+            John;Valid@Example.com;3;valid;;duplicate\n\
+            Bob;bob@example.com;4;;;dropped\n");
+    }
+
+    #[test]
+    fn test_join_results_without_header() {
+        let input = "a@example.com,x\nb@example.com\n";
+        let results = "\"email\",\"column 2\",\"ZeroBounce Score\"\n\"b@example.com\",\"\",\"7\"\n\"a@example.com\",\"x\",\"10\"\n";
+        let joined = join_results(input.as_bytes(), results.as_bytes()).unwrap();
+        assert_eq!(String::from_utf8(joined).unwrap(), "\
+            email,column 2,ZeroBounce Score,ZB Join Status\n\
+            a@example.com,x,10,matched\n\
+            b@example.com,,7,matched\n");
+
+        assert!(join_results("name\nJohn\n".as_bytes(), results.as_bytes()).is_err());
+    }
+}
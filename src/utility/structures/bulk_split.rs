@@ -0,0 +1,155 @@
+//! Splitting of bulk files too large for a single submission, and merging
+//! of the result files of their chunks (see `ZBFile::split` and
+//! `BulkJob::run_split`).
+
+use bytes::Bytes;
+use csv::{ByteRecord, ReaderBuilder, WriterBuilder};
+
+use crate::utility::{ZBError, ZBResult};
+
+/// Default largest number of data rows of a chunk.
+pub const DEFAULT_MAX_ROWS: u64 = 100_000;
+/// Default largest size of a chunk, in bytes, header row included.
+pub const DEFAULT_MAX_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Size and row limits of the chunks produced by `ZBFile::split`.
+///
+/// The defaults are conservative; set them to the limits of the account
+/// when they are known.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZBSplitLimits {
+    /// Largest number of rows of a chunk, without its header row.
+    pub max_rows: u64,
+    /// Largest size of a chunk, in bytes, header row included.
+    pub max_bytes: u64,
+}
+
+impl Default for ZBSplitLimits {
+    fn default() -> Self {
+        ZBSplitLimits {
+            max_rows: DEFAULT_MAX_ROWS,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
+impl ZBSplitLimits {
+    pub fn set_max_rows(mut self, max_rows: u64) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    pub fn set_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+}
+
+/// Rows of a file grouped into chunks within limits, each chunk encoded as
+/// CSV with `delimiter` and starting with `header` when given.
+pub(crate) fn split_records(
+    records: impl IntoIterator<Item = ByteRecord>,
+    header: Option<&ByteRecord>,
+    delimiter: u8,
+    limits: ZBSplitLimits,
+) -> ZBResult<Vec<Vec<u8>>> {
+    if limits.max_rows == 0 {
+        return Err(ZBError::validation("max_rows must be at least 1"));
+    }
+    let encode = |record: &ByteRecord| -> ZBResult<Vec<u8>> {
+        let mut writer = WriterBuilder::new().flexible(true).delimiter(delimiter).from_writer(Vec::new());
+        writer.write_byte_record(record)?;
+        writer.into_inner().map_err(|error| ZBError::IOError(error.into_error()))
+    };
+    let header = header.map(encode).transpose()?.unwrap_or_default();
+
+    let mut chunks = Vec::new();
+    let mut chunk = header.clone();
+    let mut rows = 0;
+    for record in records {
+        let row = encode(&record)?;
+        if (header.len() + row.len()) as u64 > limits.max_bytes {
+            return Err(ZBError::validation("a row does not fit in max_bytes"));
+        }
+        if rows == limits.max_rows || (chunk.len() + row.len()) as u64 > limits.max_bytes {
+            chunks.push(std::mem::replace(&mut chunk, header.clone()));
+            rows = 0;
+        }
+        chunk.extend(row);
+        rows += 1;
+    }
+    if rows > 0 {
+        chunks.push(chunk);
+    }
+
+    Ok(chunks)
+}
+
+/// Concatenate the result files of the chunks of a file, in order, into one
+/// result file. Result files start with a header row: the one of the first
+/// file is kept and the others are dropped.
+///
+/// The rows of each result file are kept as downloaded; `BulkJob::run_split`
+/// then joins the merged file to the original one to restore its row order.
+pub fn merge_result_files<C: AsRef<[u8]>>(contents: &[C]) -> ZBResult<Bytes> {
+    let mut writer = WriterBuilder::new().flexible(true).from_writer(Vec::new());
+    let mut record = ByteRecord::new();
+
+    for (index, content) in contents.iter().enumerate() {
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(content.as_ref());
+        let mut first = true;
+        while reader.read_byte_record(&mut record)? {
+            if first && index > 0 {
+                first = false;
+                continue;
+            }
+            first = false;
+            writer.write_byte_record(&record)?;
+        }
+    }
+
+    let merged = writer.into_inner().map_err(|error| ZBError::IOError(error.into_error()))?;
+    Ok(Bytes::from(merged))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(cells: &[&str]) -> ByteRecord {
+        ByteRecord::from(cells.to_vec())
+    }
+
+    #[test]
+    fn test_split_records() {
+        let header = record(&["email", "name"]);
+        let rows = (1..=5).map(|index| record(&[&format!("user{}@example.com", index), "Doe, John"]));
+        let limits = ZBSplitLimits::default().set_max_rows(2);
+        let chunks = split_records(rows.clone(), Some(&header), b',', limits).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0], b"email,name\nuser1@example.com,\"Doe, John\"\nuser2@example.com,\"Doe, John\"\n");
+        assert_eq!(chunks[2], b"email,name\nuser5@example.com,\"Doe, John\"\n");
+
+        // 11 bytes of header, 30 bytes per row
+        let limits = ZBSplitLimits::default().set_max_bytes(75);
+        let chunks = split_records(rows.clone(), Some(&header), b';', limits).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 75 && chunk.starts_with(b"email;name\n")));
+
+        assert!(split_records(rows.clone(), None, b',', ZBSplitLimits::default().set_max_bytes(20)).is_err());
+        assert!(split_records(rows, None, b',', ZBSplitLimits::default().set_max_rows(0)).is_err());
+    }
+
+    #[test]
+    fn test_merge_result_files() {
+        let merged = merge_result_files(&[
+            "\"email\",\"ZB Status\"\n\"a@example.com\",\"valid\"\n",
+            "\"email\",\"ZB Status\"\n\"b@example.com\",\"invalid\"\n\"c@example.com\",\"valid\"\n",
+            "\"email\",\"ZB Status\"\n",
+        ]).unwrap();
+        assert_eq!(&merged[..], b"email,ZB Status\na@example.com,valid\nb@example.com,invalid\nc@example.com,valid\n");
+    }
+}
@@ -1,7 +1,14 @@
-use chrono::{DateTime, FixedOffset, NaiveDateTime, NaiveDate};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, NaiveDate, SecondsFormat};
 
 use serde::de::Error as SerdeError;
+use serde::Serializer;
 
+// Each deserializer below has a serializer writing the value back in the
+// format of the API, so that serialized responses can be read again.
+
+// "%.f" reads and writes the fraction of the second only when there is one:
+// "2023-03-23 13:30:28.105" and "2023-03-23 13:30:28" both round-trip.
+const NAIVE_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
 
 pub(crate) fn deserialize_naive_date<'de, D>(
     deserializer: D,
@@ -10,11 +17,17 @@ where
     D: serde::Deserializer<'de>,
 {
     let string: &str = serde::Deserialize::deserialize(deserializer)?;
-    let format = "%Y-%m-%d %H:%M:%S.%3f";
-    NaiveDateTime::parse_from_str(string, format)
+    NaiveDateTime::parse_from_str(string, NAIVE_DATE_FORMAT)
         .map_err(SerdeError::custom)
 }
 
+pub(crate) fn serialize_naive_date<S>(date: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(&date.format(NAIVE_DATE_FORMAT))
+}
+
 
 pub(crate) fn deserialize_only_date<'de, D>(
     deserializer: D,
@@ -28,6 +41,14 @@ where
         .map_err(SerdeError::custom)
 }
 
+// The API does not pad months and days: "1/12/2010".
+pub(crate) fn serialize_only_date<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(&date.format("%-m/%-d/%Y"))
+}
+
 pub(crate) fn deserialize_stringified_uint<'de, D>(
     deserializer: D,
 ) -> Result<Option<u128>, D::Error>
@@ -48,6 +69,16 @@ where
     Ok(Some(value))
 }
 
+pub(crate) fn serialize_stringified_uint<S>(value: &Option<u128>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
 
 pub(crate) fn deserialize_date_rfc<'de, D>(
     deserializer: D,
@@ -59,6 +90,13 @@ where
     DateTime::parse_from_rfc3339(string).map_err(SerdeError::custom)
 }
 
+pub(crate) fn serialize_date_rfc<S>(date: &DateTime<FixedOffset>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&date.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
 // Expecting a
 pub(crate) fn deserialize_percentage_float<'de, D>(deserializer: D) -> Result<f32, D::Error>
 where
@@ -75,3 +113,65 @@ where
 
     Ok(amount)
 }
+
+pub(crate) fn serialize_percentage_float<S>(amount: &f32, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(&format_args!("{}%", amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Wire {
+        #[serde(deserialize_with = "deserialize_naive_date", serialize_with = "serialize_naive_date")]
+        naive_date: NaiveDateTime,
+        #[serde(deserialize_with = "deserialize_only_date", serialize_with = "serialize_only_date")]
+        only_date: NaiveDate,
+        #[serde(deserialize_with = "deserialize_stringified_uint", serialize_with = "serialize_stringified_uint")]
+        some_uint: Option<u128>,
+        #[serde(deserialize_with = "deserialize_stringified_uint", serialize_with = "serialize_stringified_uint")]
+        no_uint: Option<u128>,
+        #[serde(deserialize_with = "deserialize_date_rfc", serialize_with = "serialize_date_rfc")]
+        date_rfc: DateTime<FixedOffset>,
+        #[serde(deserialize_with = "deserialize_date_rfc", serialize_with = "serialize_date_rfc")]
+        date_rfc_offset: DateTime<FixedOffset>,
+        #[serde(deserialize_with = "deserialize_percentage_float", serialize_with = "serialize_percentage_float")]
+        percentage: f32,
+    }
+
+    #[test]
+    fn test_serializers_write_the_api_format() {
+        let json = r#"{"naive_date":"2023-03-23 13:30:28.105","only_date":"1/12/2010","some_uint":"180","no_uint":null,"date_rfc":"2023-04-26T17:52:23Z","date_rfc_offset":"2023-04-26T17:52:23.250+02:00","percentage":"42.5%"}"#;
+        let wire = serde_json::from_str::<Wire>(json).unwrap();
+        assert_eq!(serde_json::to_string(&wire).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Wire>(&serde_json::to_string(&wire).unwrap()).unwrap(), wire);
+    }
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct NaiveDateWire {
+        #[serde(deserialize_with = "deserialize_naive_date", serialize_with = "serialize_naive_date")]
+        naive_date: NaiveDateTime,
+    }
+
+    #[test]
+    fn test_naive_date_with_fraction_round_trips() {
+        for json in [r#"{"naive_date":"2023-03-23 13:30:28.105"}"#, r#"{"naive_date":"2023-03-23 13:30:28.105250"}"#] {
+            let wire = serde_json::from_str::<NaiveDateWire>(json).unwrap();
+            assert_eq!(serde_json::to_string(&wire).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn test_naive_date_without_fraction_round_trips() {
+        let json = r#"{"naive_date":"2023-03-23 13:30:28"}"#;
+        let wire = serde_json::from_str::<NaiveDateWire>(json).unwrap();
+        assert_eq!(wire.naive_date, NaiveDate::from_ymd_opt(2023, 3, 23).unwrap().and_hms_opt(13, 30, 28).unwrap());
+        assert_eq!(serde_json::to_string(&wire).unwrap(), json);
+    }
+}
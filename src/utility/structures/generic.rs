@@ -1,10 +1,11 @@
 use chrono::NaiveDate;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::utility::structures::custom_deserialize::deserialize_only_date;
-use crate::utility::structures::custom_deserialize::deserialize_stringified_uint;
+use crate::utility::structures::custom_deserialize::{deserialize_only_date, serialize_only_date};
+use crate::utility::structures::validate_enums::ZBConfidence;
+use crate::utility::structures::custom_deserialize::{deserialize_stringified_uint, serialize_stringified_uint};
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ApiUsage {
     pub total: u64,
     pub status_valid: u64,
@@ -42,28 +43,28 @@ pub struct ApiUsage {
     pub sub_status_gold: u64,
     pub sub_status_role_based_accept_all: u64,
 
-    #[serde(deserialize_with="deserialize_only_date")]
+    #[serde(deserialize_with="deserialize_only_date", serialize_with="serialize_only_date")]
     pub start_date: NaiveDate,
 
-    #[serde(deserialize_with="deserialize_only_date")]
+    #[serde(deserialize_with="deserialize_only_date", serialize_with="serialize_only_date")]
     pub end_date: NaiveDate,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ActivityData {
     pub found: bool,
 
-    #[serde(deserialize_with="deserialize_stringified_uint")]
+    #[serde(deserialize_with="deserialize_stringified_uint", serialize_with="serialize_stringified_uint")]
     pub active_in_days: Option<u128>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct DomainFormats {
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DomainFormats<C = String> {
     pub format: String,
-    pub confidence: String,
+    pub confidence: C,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FindEmailResponse {
     pub email: String,
     pub domain: String,
@@ -79,13 +80,16 @@ pub struct FindEmailResponse {
 /// Response structure for the new find_email_v2 API endpoint.
 /// This structure matches the new API response format which includes
 /// `email_confidence` and `company_name` fields.
-#[derive(Clone, Debug, Deserialize)]
-pub struct FindEmailResponseV2 {
+///
+/// `ZBTypedFindEmailResponse` reads the confidence directly into a
+/// `ZBConfidence`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FindEmailResponseV2<C = String> {
     pub email: String,
     #[serde(default)]
     pub domain: String,
     #[serde(rename = "email_confidence", default)]
-    pub confidence: String,
+    pub confidence: C,
     #[serde(default)]
     pub company_name: String,
     #[serde(default)]
@@ -97,8 +101,11 @@ pub struct FindEmailResponseV2 {
 /// Response structure for the new domain_search_v2 API endpoint.
 /// This structure matches the domain search API response format which includes
 /// `format`, `confidence`, and `other_domain_formats` fields.
-#[derive(Clone, Debug, Deserialize)]
-pub struct DomainSearchResponseV2 {
+///
+/// `ZBTypedDomainSearchResponse` reads the confidences directly into
+/// `ZBConfidence`s.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DomainSearchResponseV2<C = String> {
     #[serde(default)]
     pub domain: String,
     #[serde(default)]
@@ -106,13 +113,60 @@ pub struct DomainSearchResponseV2 {
     #[serde(default)]
     pub format: String,
     #[serde(default)]
-    pub confidence: String,
+    pub confidence: C,
     #[serde(default)]
     pub did_you_mean: String,
     #[serde(default)]
     pub failure_reason: String,
     #[serde(default)]
-    pub other_domain_formats: Vec<DomainFormats>,
+    pub other_domain_formats: Vec<DomainFormats<C>>,
+}
+
+/// `FindEmailResponseV2` with a typed confidence.
+pub type ZBTypedFindEmailResponse = FindEmailResponseV2<ZBConfidence>;
+
+/// `DomainSearchResponseV2` with typed confidences.
+pub type ZBTypedDomainSearchResponse = DomainSearchResponseV2<ZBConfidence>;
+
+fn parse_confidence(confidence: String) -> ZBConfidence {
+    let Ok(confidence) = confidence.parse();
+    confidence
+}
+
+impl DomainFormats {
+    /// Same format with a typed confidence.
+    pub fn typed(self) -> DomainFormats<ZBConfidence> {
+        DomainFormats { format: self.format, confidence: parse_confidence(self.confidence) }
+    }
+}
+
+impl FindEmailResponseV2 {
+    /// Same response with a typed confidence.
+    pub fn typed(self) -> ZBTypedFindEmailResponse {
+        FindEmailResponseV2 {
+            email: self.email,
+            domain: self.domain,
+            confidence: parse_confidence(self.confidence),
+            company_name: self.company_name,
+            did_you_mean: self.did_you_mean,
+            failure_reason: self.failure_reason,
+        }
+    }
+}
+
+impl DomainSearchResponseV2 {
+    /// Same response with typed confidences.
+    pub fn typed(self) -> ZBTypedDomainSearchResponse {
+        DomainSearchResponseV2 {
+            domain: self.domain,
+            company_name: self.company_name,
+            format: self.format,
+            confidence: parse_confidence(self.confidence),
+            did_you_mean: self.did_you_mean,
+            failure_reason: self.failure_reason,
+            other_domain_formats: self.other_domain_formats.into_iter().map(DomainFormats::typed).collect(),
+        }
+    }
 }
 
 
@@ -133,7 +187,7 @@ mod tests {
         assert!(activity_data_res.is_ok(), "error: {}", activity_data_res.unwrap_err());
 
         let activity_data = activity_data_res.unwrap();
-        assert_eq!(activity_data.found, false);
+        assert!(!activity_data.found);
         assert_eq!(activity_data.active_in_days, None);
     }
 
@@ -143,7 +197,7 @@ mod tests {
         assert!(activity_data_res.is_ok());
 
         let activity_data = activity_data_res.unwrap();
-        assert_eq!(activity_data.found, true);
+        assert!(activity_data.found);
         assert_eq!(activity_data.active_in_days, Some(180));
     }
 
@@ -181,5 +235,37 @@ mod tests {
         assert_eq!(find_mail_object.other_domain_formats[1].confidence, "medium");
     }
 
+    #[test]
+    fn test_responses_serialize_to_the_api_format() {
+        use crate::utility::mock_constants::{MOCK_DOMAIN_SEARCH_V2_WITH_FORMATS, MOCK_FIND_EMAIL_V2_COMPANY_VALID};
+
+        fn assert_round_trip<T: serde::de::DeserializeOwned + Serialize>(body: &str) {
+            let value = serde_json::from_str::<T>(body).unwrap();
+            let expected = serde_json::from_str::<serde_json::Value>(body).unwrap();
+            assert_eq!(serde_json::to_value(&value).unwrap(), expected);
+        }
+
+        assert_round_trip::<ApiUsage>(API_USAGE_RESPONSE);
+        assert_round_trip::<ActivityData>(ACTIVITY_DATA_RESPONSE_ACTIVE);
+        assert_round_trip::<ActivityData>(ACTIVITY_DATA_RESPONSE_INACTIVE);
+        assert_round_trip::<FindEmailResponse>(MOCK_FIND_MAIL_VALID);
+        assert_round_trip::<FindEmailResponseV2>(MOCK_FIND_EMAIL_V2_COMPANY_VALID);
+        assert_round_trip::<DomainSearchResponseV2>(MOCK_DOMAIN_SEARCH_V2_WITH_FORMATS);
+    }
+
+    #[test]
+    fn test_typed_finder_responses() {
+        use crate::utility::mock_constants::{MOCK_DOMAIN_SEARCH_V2_WITH_FORMATS, MOCK_FIND_EMAIL_V2_INVALID};
+
+        let found: ZBTypedFindEmailResponse = serde_json::from_str(MOCK_FIND_EMAIL_V2_INVALID).unwrap();
+        assert_eq!(found.confidence, ZBConfidence::Undetermined);
+        let found: ZBTypedFindEmailResponse = serde_json::from_str(r#"{"email": ""}"#).unwrap();
+        assert_eq!(found.confidence, ZBConfidence::None);
+
+        let search: DomainSearchResponseV2 = serde_json::from_str(MOCK_DOMAIN_SEARCH_V2_WITH_FORMATS).unwrap();
+        let typed = search.typed();
+        assert_eq!(typed.confidence, ZBConfidence::High);
+        assert_eq!(typed.other_domain_formats[1].confidence, ZBConfidence::Medium);
+    }
 
 }
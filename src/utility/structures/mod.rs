@@ -1,9 +1,19 @@
 pub mod bulk;
+pub mod bulk_content;
+pub mod bulk_inspection;
+pub mod bulk_records;
+pub mod bulk_results;
+pub mod bulk_split;
 pub mod custom_deserialize;
 pub mod generic;
 pub mod validate_enums;
 pub mod validation;
 
 pub use generic::{ActivityData, ApiUsage, FindEmailResponse, FindEmailResponseV2, DomainSearchResponseV2};
-pub use validate_enums::{ZBValidateStatus, ZBValidateSubStatus};
+pub use generic::{ZBTypedDomainSearchResponse, ZBTypedFindEmailResponse};
+pub use bulk_inspection::{ZBFileInspection, ZBFileIssue};
+pub use bulk_split::{merge_result_files, ZBSplitLimits};
+pub use bulk_records::{ZBFileBuilder, ZBFileRecord};
+pub use bulk_results::{join_results, parse_scoring_results, parse_validation_results, ZBScoringResultRow, ZBValidationResultRow};
+pub use validate_enums::{ZBConfidence, ZBFileProcessingStatus, ZBValidateStatus, ZBValidateSubStatus};
 
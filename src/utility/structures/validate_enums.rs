@@ -1,13 +1,47 @@
-//! Validation status and sub-status enums for API responses.
+//! Status enums for API responses: validation status and sub-status, bulk
+//! file status and finder confidence.
 //!
-//! Use for type-safe comparison. Unknown/future API values map to `Unknown(String)`.
+//! Use for type-safe comparison. Unknown/future API values map to `UnknownValue(String)`.
+//! The enums (de)serialize from and to the strings of the API, so that the
+//! typed versions of the responses (e.g. `ZBTypedValidation`) can be read
+//! directly.
 
+use std::fmt;
 use std::str::FromStr;
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// `Display`, `Serialize` and `Deserialize` of an enum through its `as_str`
+// and infallible `FromStr` implementations.
+macro_rules! impl_api_string {
+    ($name:ident) => {
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let string: std::borrow::Cow<'de, str> = Deserialize::deserialize(deserializer)?;
+                let Ok(value) = string.parse();
+                Ok(value)
+            }
+        }
+    };
+}
+
 /// Validation status values returned by the API (validate, validate_batch).
 /// Parse from string: `let s: ZBValidateStatus = response.status.parse().unwrap_or(ZBValidateStatus::Unknown(response.status.clone()));`
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub enum ZBValidateStatus {
+    #[default]
     None,
     Valid,
     Invalid,
@@ -38,9 +72,29 @@ impl FromStr for ZBValidateStatus {
     }
 }
 
+impl ZBValidateStatus {
+    /// Value sent by the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ZBValidateStatus::None => "",
+            ZBValidateStatus::Valid => "valid",
+            ZBValidateStatus::Invalid => "invalid",
+            ZBValidateStatus::CatchAll => "catch-all",
+            ZBValidateStatus::Unknown => "unknown",
+            ZBValidateStatus::Spamtrap => "spamtrap",
+            ZBValidateStatus::Abuse => "abuse",
+            ZBValidateStatus::DoNotMail => "do_not_mail",
+            ZBValidateStatus::UnknownValue(value) => value,
+        }
+    }
+}
+
+impl_api_string!(ZBValidateStatus);
+
 /// Validation sub-status values returned by the API (validate, validate_batch).
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub enum ZBValidateSubStatus {
+    #[default]
     None,
     AntispamSystem,
     Greylisted,
@@ -116,3 +170,168 @@ impl FromStr for ZBValidateSubStatus {
         })
     }
 }
+
+impl ZBValidateSubStatus {
+    /// Value sent by the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ZBValidateSubStatus::None => "",
+            ZBValidateSubStatus::AntispamSystem => "antispam_system",
+            ZBValidateSubStatus::Greylisted => "greylisted",
+            ZBValidateSubStatus::MailServerTemporaryError => "mail_server_temporary_error",
+            ZBValidateSubStatus::ForcibleDisconnect => "forcible_disconnect",
+            ZBValidateSubStatus::MailServerDidNotRespond => "mail_server_did_not_respond",
+            ZBValidateSubStatus::TimeoutExceeded => "timeout_exceeded",
+            ZBValidateSubStatus::FailedSmtpConnection => "failed_smtp_connection",
+            ZBValidateSubStatus::MailboxQuotaExceeded => "mailbox_quota_exceeded",
+            ZBValidateSubStatus::ExceptionOccurred => "exception_occurred",
+            ZBValidateSubStatus::PossibleTrap => "possible_trap",
+            ZBValidateSubStatus::RoleBased => "role_based",
+            ZBValidateSubStatus::GlobalSuppression => "global_suppression",
+            ZBValidateSubStatus::MailboxNotFound => "mailbox_not_found",
+            ZBValidateSubStatus::NoDnsEntries => "no_dns_entries",
+            ZBValidateSubStatus::FailedSyntaxCheck => "failed_syntax_check",
+            ZBValidateSubStatus::PossibleTypo => "possible_typo",
+            ZBValidateSubStatus::UnroutableIpAddress => "unroutable_ip_address",
+            ZBValidateSubStatus::LeadingPeriodRemoved => "leading_period_removed",
+            ZBValidateSubStatus::DoesNotAcceptMail => "does_not_accept_mail",
+            ZBValidateSubStatus::AliasAddress => "alias_address",
+            ZBValidateSubStatus::RoleBasedCatchAll => "role_based_catch_all",
+            ZBValidateSubStatus::Disposable => "disposable",
+            ZBValidateSubStatus::Toxic => "toxic",
+            ZBValidateSubStatus::Alternate => "alternate",
+            ZBValidateSubStatus::MxForward => "mx_forward",
+            ZBValidateSubStatus::Blocked => "blocked",
+            ZBValidateSubStatus::Allowed => "allowed",
+            ZBValidateSubStatus::AcceptAll => "accept_all",
+            ZBValidateSubStatus::RoleBasedAcceptAll => "role_based_accept_all",
+            ZBValidateSubStatus::Gold => "gold",
+            ZBValidateSubStatus::UnknownValue(value) => value,
+        }
+    }
+}
+
+impl_api_string!(ZBValidateSubStatus);
+
+/// Status of a bulk validation or AI scoring file (`file_status` and
+/// `file_phase_2_status` of `ZBFileStatus`). Values are matched regardless
+/// of case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ZBFileProcessingStatus {
+    Uploading,
+    Queued,
+    Processing,
+    Complete,
+    Deleted,
+    Failed,
+    /// Unknown or future API value (backward compatible)
+    UnknownValue(String),
+}
+
+impl FromStr for ZBFileProcessingStatus {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "uploading" => ZBFileProcessingStatus::Uploading,
+            "queued" => ZBFileProcessingStatus::Queued,
+            "processing" => ZBFileProcessingStatus::Processing,
+            "complete" => ZBFileProcessingStatus::Complete,
+            "deleted" => ZBFileProcessingStatus::Deleted,
+            "failed" => ZBFileProcessingStatus::Failed,
+            _ => ZBFileProcessingStatus::UnknownValue(s.to_string()),
+        })
+    }
+}
+
+impl ZBFileProcessingStatus {
+    /// Value sent by the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ZBFileProcessingStatus::Uploading => "Uploading",
+            ZBFileProcessingStatus::Queued => "Queued",
+            ZBFileProcessingStatus::Processing => "Processing",
+            ZBFileProcessingStatus::Complete => "Complete",
+            ZBFileProcessingStatus::Deleted => "Deleted",
+            ZBFileProcessingStatus::Failed => "Failed",
+            ZBFileProcessingStatus::UnknownValue(value) => value,
+        }
+    }
+}
+
+impl_api_string!(ZBFileProcessingStatus);
+
+/// Confidence of the email finder and domain search results
+/// (`FindEmailResponseV2`, `DomainSearchResponseV2`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum ZBConfidence {
+    #[default]
+    None,
+    High,
+    Medium,
+    Low,
+    Undetermined,
+    /// Unknown or future API value (backward compatible)
+    UnknownValue(String),
+}
+
+impl FromStr for ZBConfidence {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "" => ZBConfidence::None,
+            "high" => ZBConfidence::High,
+            "medium" => ZBConfidence::Medium,
+            "low" => ZBConfidence::Low,
+            "undetermined" => ZBConfidence::Undetermined,
+            other => ZBConfidence::UnknownValue(other.to_string()),
+        })
+    }
+}
+
+impl ZBConfidence {
+    /// Value sent by the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ZBConfidence::None => "",
+            ZBConfidence::High => "high",
+            ZBConfidence::Medium => "medium",
+            ZBConfidence::Low => "low",
+            ZBConfidence::Undetermined => "undetermined",
+            ZBConfidence::UnknownValue(value) => value,
+        }
+    }
+}
+
+impl_api_string!(ZBConfidence);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enums_use_the_api_strings() {
+        let statuses = serde_json::from_str::<Vec<ZBValidateStatus>>(r#"["catch-all", "", "brand_new"]"#).unwrap();
+        assert_eq!(statuses, [
+            ZBValidateStatus::CatchAll,
+            ZBValidateStatus::None,
+            ZBValidateStatus::UnknownValue("brand_new".to_string()),
+        ]);
+        assert_eq!(serde_json::to_string(&statuses).unwrap(), r#"["catch-all","","brand_new"]"#);
+
+        for sub_status in ["role_based_catch_all", "alias_address", "mx_forward", "new_sub_status"] {
+            let parsed = sub_status.parse::<ZBValidateSubStatus>().unwrap();
+            assert_eq!(parsed.as_str(), sub_status);
+        }
+        assert_eq!(ZBValidateSubStatus::Greylisted.to_string(), "greylisted");
+
+        let file_status = serde_json::from_str::<ZBFileProcessingStatus>(r#""complete""#).unwrap();
+        assert_eq!(file_status, ZBFileProcessingStatus::Complete);
+        assert_eq!(file_status.as_str(), "Complete");
+        assert_eq!("Paused".parse::<ZBFileProcessingStatus>().unwrap().as_str(), "Paused");
+
+        assert_eq!(serde_json::from_str::<ZBConfidence>(r#""undetermined""#).unwrap(), ZBConfidence::Undetermined);
+        assert_eq!(ZBConfidence::default(), ZBConfidence::None);
+    }
+}
@@ -1,15 +1,23 @@
-use chrono::NaiveDateTime;
-use serde::Deserialize;
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-use crate::utility::structures::custom_deserialize::deserialize_naive_date;
+use crate::utility::{ZBError, BATCH_VALIDATE_MAX_EMAILS, S_INVALID, SS_FAILED_SYNTAX_CHECK};
+use crate::utility::precheck::PrecheckError;
+use crate::utility::structures::custom_deserialize::{deserialize_naive_date, serialize_naive_date};
 use crate::utility::structures::validate_enums::{ZBValidateStatus, ZBValidateSubStatus};
 
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct ZBValidation {
+/// Result of the validation of an address.
+///
+/// `status` and `sub_status` are strings by default; `ZBTypedValidation`
+/// reads them directly into `ZBValidateStatus` and `ZBValidateSubStatus`
+/// (unknown API values become `UnknownValue`), and `typed` converts a
+/// validation received from the client.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ZBValidation<S = String, SS = String> {
     pub address: String,
-    pub status: String,
-    pub sub_status: String,
+    pub status: S,
+    pub sub_status: SS,
     pub free_email: bool,
     pub did_you_mean: Option<String>,
     pub account: Option<String>,
@@ -27,11 +35,44 @@ pub struct ZBValidation {
     pub city: Option<String>,
     pub zipcode: Option<String>,
 
-    #[serde(deserialize_with="deserialize_naive_date")]
+    #[serde(deserialize_with="deserialize_naive_date", serialize_with="serialize_naive_date")]
     pub processed_at: NaiveDateTime,
+
+    /// Set when the validation was produced locally by the syntax pre-check
+    /// instead of the API, with the reason the address was rejected.
+    #[serde(skip)]
+    pub precheck_error: Option<PrecheckError>,
 }
 
 impl ZBValidation {
+    /// Validation produced locally for an address rejected by the syntax
+    /// pre-check: status `invalid`, sub-status `failed_syntax_check`.
+    pub fn failed_syntax_check(address: &str, error: PrecheckError) -> ZBValidation {
+        ZBValidation {
+            address: address.to_string(),
+            status: S_INVALID.to_string(),
+            sub_status: SS_FAILED_SYNTAX_CHECK.to_string(),
+            free_email: false,
+            did_you_mean: None,
+            account: None,
+            domain: None,
+            domain_age_days: None,
+            smtp_provider: None,
+            catchall_domain: None,
+            mx_record: None,
+            mx_found: None,
+            firstname: None,
+            lastname: None,
+            gender: None,
+            country: None,
+            region: None,
+            city: None,
+            zipcode: None,
+            processed_at: Utc::now().naive_utc(),
+            precheck_error: Some(error),
+        }
+    }
+
     /// Parse `status` string into typed enum (unknown API values become `ZBValidateStatus::UnknownValue`).
     pub fn status_enum(&self) -> ZBValidateStatus {
         self.status.parse().unwrap_or_else(|_| ZBValidateStatus::UnknownValue(self.status.clone()))
@@ -41,20 +82,132 @@ impl ZBValidation {
     pub fn sub_status_enum(&self) -> ZBValidateSubStatus {
         self.sub_status.parse().unwrap_or_else(|_| ZBValidateSubStatus::UnknownValue(self.sub_status.clone()))
     }
+
+    /// Same validation with typed `status` and `sub_status`.
+    pub fn typed(self) -> ZBTypedValidation {
+        let status = self.status_enum();
+        let sub_status = self.sub_status_enum();
+        self.with_statuses(status, sub_status)
+    }
 }
 
+impl<S, SS> ZBValidation<S, SS> {
+    /// Whether the validation was produced locally by the syntax pre-check.
+    pub fn is_precheck_result(&self) -> bool {
+        self.precheck_error.is_some()
+    }
 
-#[derive(Clone, Debug, Deserialize)]
+    fn with_statuses<S2, SS2>(self, status: S2, sub_status: SS2) -> ZBValidation<S2, SS2> {
+        ZBValidation {
+            address: self.address,
+            status,
+            sub_status,
+            free_email: self.free_email,
+            did_you_mean: self.did_you_mean,
+            account: self.account,
+            domain: self.domain,
+            domain_age_days: self.domain_age_days,
+            smtp_provider: self.smtp_provider,
+            catchall_domain: self.catchall_domain,
+            mx_record: self.mx_record,
+            mx_found: self.mx_found,
+            firstname: self.firstname,
+            lastname: self.lastname,
+            gender: self.gender,
+            country: self.country,
+            region: self.region,
+            city: self.city,
+            zipcode: self.zipcode,
+            processed_at: self.processed_at,
+            precheck_error: self.precheck_error,
+        }
+    }
+}
+
+impl ZBTypedValidation {
+    /// Same validation with `status` and `sub_status` as strings.
+    pub fn untyped(self) -> ZBValidation {
+        let status = self.status.to_string();
+        let sub_status = self.sub_status.to_string();
+        self.with_statuses(status, sub_status)
+    }
+}
+
+/// `ZBValidation` with typed `status` and `sub_status`.
+pub type ZBTypedValidation = ZBValidation<ZBValidateStatus, ZBValidateSubStatus>;
+
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ZBBatchError {
     pub error: String,
     pub email_address: String,
 }
 
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct ZBBatchValidation {
+/// Results of `batch_validate`; `ZBTypedBatchValidation` holds
+/// `ZBTypedValidation`s.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ZBBatchValidation<V = ZBValidation> {
+    pub email_batch: Vec<V>,
+    pub errors: Vec<ZBBatchError>,
+}
+
+impl ZBBatchValidation {
+    /// Same results with typed statuses.
+    pub fn typed(self) -> ZBTypedBatchValidation {
+        ZBBatchValidation {
+            email_batch: self.email_batch.into_iter().map(ZBValidation::typed).collect(),
+            errors: self.errors,
+        }
+    }
+}
+
+/// `ZBBatchValidation` with typed statuses.
+pub type ZBTypedBatchValidation = ZBBatchValidation<ZBTypedValidation>;
+
+/// Options of `batch_validate_all_with_options`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZBBatchOptions {
+    /// Emails sent per `/validatebatch` request, capped to
+    /// `BATCH_VALIDATE_MAX_EMAILS` (default: 200).
+    pub chunk_size: usize,
+    /// Maximum number of requests in flight (default: 4).
+    pub concurrency: usize,
+}
+
+impl Default for ZBBatchOptions {
+    fn default() -> Self {
+        ZBBatchOptions {
+            chunk_size: BATCH_VALIDATE_MAX_EMAILS,
+            concurrency: 4,
+        }
+    }
+}
+
+/// Chunk of a `batch_validate_all` call whose request failed.
+#[derive(Debug)]
+pub struct ZBBatchChunkFailure {
+    /// Position of the chunk in the input, starting at 0.
+    pub chunk_index: usize,
+    /// Emails and IP addresses of the chunk, to be sent again if needed.
+    pub emails_and_ip_addresses: Vec<(String, String)>,
+    pub error: ZBError,
+}
+
+/// Merged result of a `batch_validate_all` call. Validations and errors
+/// follow the order of the input.
+#[derive(Debug, Default)]
+pub struct ZBBatchValidationReport {
     pub email_batch: Vec<ZBValidation>,
     pub errors: Vec<ZBBatchError>,
+    pub failed_chunks: Vec<ZBBatchChunkFailure>,
+}
+
+impl ZBBatchValidationReport {
+    /// Whether every chunk was validated.
+    pub fn is_complete(&self) -> bool {
+        self.failed_chunks.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -95,7 +248,7 @@ mod test {
         assert_eq!(validation.sub_status, "mailbox_not_found".to_string());
         assert_eq!(validation.did_you_mean, None);
         assert_eq!(validation.smtp_provider, Some("example".to_string()));
-        assert_eq!(validation.free_email, false);
+        assert!(!validation.free_email);
 
         let expected_date = NaiveDateTime::new(
             chrono::NaiveDate::from_ymd_opt(2023, 3, 23).unwrap(),
@@ -115,7 +268,7 @@ mod test {
         assert_eq!(validation.did_you_mean, None);
         assert_eq!(validation.smtp_provider, Some("example".to_string()));
         assert_eq!(validation.catchall_domain, Some(false));
-        assert_eq!(validation.free_email, false);
+        assert!(!validation.free_email);
 
         let expected_date = NaiveDateTime::new(
             chrono::NaiveDate::from_ymd_opt(2023, 3, 23).unwrap(),
@@ -154,4 +307,40 @@ mod test {
         assert_eq!(batch_object.errors.len(), 0);
     }
 
+    #[test]
+    fn test_validation_serializes_to_the_api_format() {
+        let validation: ZBValidation = from_str(VALIDATION_RESPONSE_VALID).unwrap();
+        let expected = from_str::<serde_json::Value>(VALIDATION_RESPONSE_VALID).unwrap();
+        assert_eq!(serde_json::to_value(&validation).unwrap(), expected);
+
+        let batch: ZBBatchValidation = from_str(BATCH_VALIDATION_WITH_ERROR).unwrap();
+        let serialized = serde_json::to_string(&batch).unwrap();
+        let batch_again: ZBBatchValidation = from_str(&serialized).unwrap();
+        assert_eq!(batch_again.email_batch[0].processed_at, batch.email_batch[0].processed_at);
+        assert_eq!(batch_again.errors[0].email_address, "invalid@example.com");
+        assert_eq!(serde_json::to_string(&batch_again).unwrap(), serialized);
+    }
+
+    #[test]
+    fn test_typed_validation() {
+        let typed: ZBTypedValidation = from_str(VALIDATION_RESPONSE_INVALID).unwrap();
+        assert_eq!(typed.status, ZBValidateStatus::Invalid);
+        assert_eq!(typed.sub_status, ZBValidateSubStatus::MailboxNotFound);
+
+        let future = VALIDATION_RESPONSE_VALID.replace(r#""sub_status": """#, r#""sub_status": "new_sub_status""#);
+        let typed: ZBTypedValidation = from_str(&future).unwrap();
+        assert_eq!(typed.sub_status, ZBValidateSubStatus::UnknownValue("new_sub_status".to_string()));
+
+        let validation: ZBValidation = from_str(VALIDATION_RESPONSE_VALID).unwrap();
+        let typed = validation.clone().typed();
+        assert_eq!(typed.status, ZBValidateStatus::Valid);
+        assert_eq!(serde_json::to_value(&typed).unwrap(), serde_json::to_value(&validation).unwrap());
+        assert_eq!(typed.untyped().status, "valid");
+
+        let batch: ZBTypedBatchValidation = from_str(BATCH_VALIDATION_WITH_ERROR).unwrap();
+        assert_eq!(batch.email_batch[0].status, ZBValidateStatus::Valid);
+        let batch: ZBBatchValidation = from_str(BATCH_VALIDATION_WITH_ERROR).unwrap();
+        assert_eq!(batch.typed().errors.len(), 1);
+    }
+
 }
@@ -0,0 +1,333 @@
+//! Receiver of the callbacks sent to the `return_url` of bulk files (cargo
+//! feature `webhook`).
+//!
+//! When a file submitted with `ZBFile::set_return_url` is processed, the API
+//! calls that URL. `CallbackReceiver` is a small HTTP listener answering
+//! these calls: each callback is parsed into a `ZBFileCallback`, matched
+//! against the `file_id`s expected by the application, and handed to a
+//! closure or a channel, from which the results can be fetched.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+use reqwest::Url;
+use serde_json::Value;
+use tiny_http::{Method, Request, Response, Server};
+
+use crate::utility::{ZBError, ZBResult, CONTENT_TYPE_JSON};
+
+/// Path answered by default by a `CallbackReceiver`.
+pub const DEFAULT_CALLBACK_PATH: &str = "/zerobounce/callback";
+
+// Callback bodies larger than this are rejected.
+const MAX_CALLBACK_BODY: u64 = 64 * 1024;
+
+/// Callback received for a processed file.
+///
+/// The payload may be sent as JSON, as a form or in the query string; the
+/// fields of all of them are gathered in `fields`, as text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZBFileCallback {
+    pub file_id: String,
+    pub file_name: Option<String>,
+    pub file_status: Option<String>,
+    /// Every field of the payload, including the ones above.
+    pub fields: HashMap<String, String>,
+}
+
+impl ZBFileCallback {
+    /// Callback of a completed file, as sent by `send_test_callback`.
+    pub fn new(file_id: &str) -> ZBFileCallback {
+        let fields = HashMap::from([
+            ("file_id".to_string(), file_id.to_string()),
+            ("file_status".to_string(), "Complete".to_string()),
+        ]);
+        Self::from_fields(fields).expect("file_id is set")
+    }
+
+    /// Parse a callback from its query string, `Content-Type` and body.
+    pub fn parse(query: &str, content_type: Option<&str>, body: &[u8]) -> ZBResult<ZBFileCallback> {
+        let mut fields = form_fields(query);
+
+        let body_text = String::from_utf8_lossy(body);
+        let body_text = body_text.trim();
+        let is_json = content_type.is_some_and(|content_type| content_type.contains("json"))
+            || body_text.starts_with('{');
+        if is_json {
+            let Value::Object(object) = serde_json::from_str::<Value>(body_text)? else {
+                return Err(ZBError::validation("the callback body is not a JSON object"));
+            };
+            for (name, value) in object {
+                let value = match value {
+                    Value::Null => continue,
+                    Value::String(value) => value,
+                    value => value.to_string(),
+                };
+                fields.insert(name, value);
+            }
+        } else if !body_text.is_empty() {
+            fields.extend(form_fields(body_text));
+        }
+
+        Self::from_fields(fields)
+    }
+
+    fn from_fields(fields: HashMap<String, String>) -> ZBResult<ZBFileCallback> {
+        // "file_id", "fileId", "FileID", ...
+        let field = |name: &str| fields.iter()
+            .find(|(field_name, _)| field_name.replace('_', "").eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+            .filter(|value| !value.is_empty());
+
+        Ok(ZBFileCallback {
+            file_id: field("fileid").ok_or_else(|| ZBError::validation("the callback has no file_id"))?,
+            file_name: field("filename"),
+            file_status: field("filestatus").or_else(|| field("status")),
+            fields,
+        })
+    }
+}
+
+fn form_fields(encoded: &str) -> HashMap<String, String> {
+    let encoded = encoded.trim_start_matches('?');
+    if encoded.is_empty() {
+        return HashMap::new();
+    }
+    let Ok(url) = Url::parse(&format!("http://localhost/?{}", encoded)) else {
+        return HashMap::new();
+    };
+    url.query_pairs().map(|(name, value)| (name.into_owned(), value.into_owned())).collect()
+}
+
+// File ids waiting for their callback, shared with the listening thread.
+#[derive(Clone, Debug, Default)]
+struct PendingFiles(Arc<Mutex<HashSet<String>>>);
+
+impl PendingFiles {
+    fn lock(&self) -> MutexGuard<'_, HashSet<String>> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// HTTP listener for the callbacks of bulk files, not started yet.
+///
+/// Only the callbacks of the files registered with `expect` are delivered,
+/// once each; they are answered `200 OK`. Callbacks of other files get
+/// `404 Not Found`, and requests to other paths or that cannot be parsed are
+/// rejected as well.
+///
+/// # Example
+/// ```no_run
+/// use zero_bounce::{ZeroBounce, ZBFile};
+/// use zero_bounce::webhook::CallbackReceiver;
+/// use zero_bounce::utility::ZBResult;
+///
+/// # fn main() -> ZBResult<()> {
+/// let zb = ZeroBounce::new("your_api_key");
+/// let (server, callbacks) = CallbackReceiver::bind("0.0.0.0:8080")?.start_channel();
+///
+/// let zb_file = ZBFile::from_path("emails.csv".to_string())
+///     .set_return_url(Some("https://my.host.example:8080/zerobounce/callback"));
+/// let file_id = zb.bulk_validation_file_submit(&zb_file)?.file_id.unwrap_or_default();
+/// server.expect(&file_id);
+///
+/// let callback = callbacks.recv().expect("receiver stopped");
+/// let results = zb.bulk_validation_result_fetch(&callback.file_id)?;
+/// server.shutdown();
+/// # Ok(())
+/// # }
+/// ```
+pub struct CallbackReceiver {
+    server: Arc<Server>,
+    path: String,
+    pending: PendingFiles,
+}
+
+impl CallbackReceiver {
+    /// Listen on an address, e.g. `0.0.0.0:8080`, or `127.0.0.1:0` for any
+    /// free local port.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> ZBResult<CallbackReceiver> {
+        let server = Server::http(addr).map_err(ZBError::TransportError)?;
+        Ok(CallbackReceiver {
+            server: Arc::new(server),
+            path: DEFAULT_CALLBACK_PATH.to_string(),
+            pending: PendingFiles::default(),
+        })
+    }
+
+    /// Answer callbacks on this path (default: `DEFAULT_CALLBACK_PATH`).
+    pub fn set_path(mut self, path: &str) -> Self {
+        self.path = format!("/{}", path.trim_start_matches('/'));
+        self
+    }
+
+    /// Wait for the callback of a file.
+    pub fn expect(self, file_id: &str) -> Self {
+        self.pending.lock().insert(file_id.to_string());
+        self
+    }
+
+    /// Start listening in a background thread, calling `handler` for each
+    /// expected callback.
+    pub fn start<F>(self, handler: F) -> CallbackServer
+    where
+        F: Fn(ZBFileCallback) + Send + 'static,
+    {
+        let addr = self.server.server_addr().to_ip();
+        let server = self.server.clone();
+        let pending = self.pending.clone();
+        let thread = thread::spawn(move || {
+            for request in self.server.incoming_requests() {
+                if let Some(callback) = handle_request(request, &self.path, &self.pending) {
+                    handler(callback);
+                }
+            }
+        });
+
+        CallbackServer { server, addr, pending, thread: Some(thread) }
+    }
+
+    /// Start listening in a background thread, sending each expected
+    /// callback to the returned channel.
+    pub fn start_channel(self) -> (CallbackServer, Receiver<ZBFileCallback>) {
+        let (sender, receiver) = mpsc::channel();
+        let server = self.start(move |callback| {
+            let _ = sender.send(callback);
+        });
+        (server, receiver)
+    }
+}
+
+// Answer a request; returns its callback when it was expected.
+fn handle_request(mut request: Request, path: &str, pending: &PendingFiles) -> Option<ZBFileCallback> {
+    let (request_path, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+    if request_path != path {
+        let _ = request.respond(Response::empty(404));
+        return None;
+    }
+    if !matches!(request.method(), Method::Post | Method::Get) {
+        let _ = request.respond(Response::empty(405));
+        return None;
+    }
+
+    let query = query.to_string();
+    let content_type = request.headers().iter()
+        .find(|header| header.field.equiv("Content-Type"))
+        .map(|header| header.value.as_str().to_string());
+    let mut body = Vec::new();
+    let parsed = request.as_reader()
+        .take(MAX_CALLBACK_BODY)
+        .read_to_end(&mut body)
+        .map_err(ZBError::from)
+        .and_then(|_| ZBFileCallback::parse(&query, content_type.as_deref(), &body));
+
+    let callback = match parsed {
+        Ok(callback) => callback,
+        Err(error) => {
+            let _ = request.respond(Response::from_string(error.to_string()).with_status_code(400));
+            return None;
+        }
+    };
+    if !pending.lock().remove(&callback.file_id) {
+        let _ = request.respond(Response::from_string("unknown file_id").with_status_code(404));
+        return None;
+    }
+    let _ = request.respond(Response::empty(200));
+    Some(callback)
+}
+
+/// Running `CallbackReceiver`, stopped by `shutdown` or when dropped.
+pub struct CallbackServer {
+    server: Arc<Server>,
+    addr: Option<SocketAddr>,
+    pending: PendingFiles,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl CallbackServer {
+    /// Wait for the callback of a file.
+    pub fn expect(&self, file_id: &str) {
+        self.pending.lock().insert(file_id.to_string());
+    }
+
+    /// Stop waiting for the callback of a file; returns whether it was
+    /// expected.
+    pub fn forget(&self, file_id: &str) -> bool {
+        self.pending.lock().remove(file_id)
+    }
+
+    /// File ids whose callback has not been received yet.
+    pub fn pending(&self) -> Vec<String> {
+        self.pending.lock().iter().cloned().collect()
+    }
+
+    /// Address listened on.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.addr
+    }
+
+    /// Stop listening and wait for the background thread.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for CallbackServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Send a callback to a receiver the way the API would, as a JSON `POST`;
+/// returns the HTTP status of the answer. Meant to test an application
+/// locally, e.g. with `ZBFileCallback::new(file_id)`.
+pub fn send_test_callback(url: &str, callback: &ZBFileCallback) -> ZBResult<u16> {
+    let mut fields = callback.fields.clone();
+    fields.insert("file_id".to_string(), callback.file_id.clone());
+    let body = serde_json::to_vec(&fields)?;
+
+    let response = reqwest::blocking::Client::new()
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, CONTENT_TYPE_JSON)
+        .body(body)
+        .send()?;
+    Ok(response.status().as_u16())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json_callback() {
+        let body = br#"{"file_id": "abc-123", "file_name": "emails.csv", "file_status": "Complete", "complete_percentage": 100, "error_reason": null}"#;
+        let callback = ZBFileCallback::parse("", Some("application/json; charset=utf-8"), body).unwrap();
+        assert_eq!(callback.file_id, "abc-123");
+        assert_eq!(callback.file_name.as_deref(), Some("emails.csv"));
+        assert_eq!(callback.file_status.as_deref(), Some("Complete"));
+        assert_eq!(callback.fields["complete_percentage"], "100");
+        assert!(!callback.fields.contains_key("error_reason"));
+    }
+
+    #[test]
+    fn test_parse_form_and_query_callback() {
+        let callback = ZBFileCallback::parse("?fileId=abc-123", None, b"status=Complete&file_name=my%20emails.csv").unwrap();
+        assert_eq!(callback.file_id, "abc-123");
+        assert_eq!(callback.file_name.as_deref(), Some("my emails.csv"));
+        assert_eq!(callback.file_status.as_deref(), Some("Complete"));
+
+        assert!(matches!(ZBFileCallback::parse("", None, b"file_name=x.csv"), Err(ZBError::Validation(_))));
+        assert!(ZBFileCallback::parse("", Some(CONTENT_TYPE_JSON), b"[1, 2]").is_err());
+    }
+}
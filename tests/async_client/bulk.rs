@@ -0,0 +1,138 @@
+use zero_bounce::{ZBError, ZBFile};
+use zero_bounce::utility::mock_constants;
+use zero_bounce::utility::structures::bulk::ZBBulkResponse;
+use zero_bounce::utility::{CONTENT_TYPE_JSON, CONTENT_TYPE_STREAM};
+use zero_bounce::utility::{ENDPOINT_FILE_SEND, ENDPOINT_FILE_STATUS, ENDPOINT_FILE_RESULT};
+use zero_bounce::utility::{ENDPOINT_SCORING_SEND, ENDPOINT_SCORING_DELETE};
+
+use crate::common::{instantiate_async, endpoint_matcher, generate_zb_file};
+
+#[tokio::test]
+async fn test_async_bulk_validate_submit_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .create_async()
+        .await;
+
+    let zb_file = &generate_zb_file();
+    let response = zb_instance.bulk_validation_file_submit(zb_file).await;
+    mock.assert_async().await;
+    assert!(response.is_ok(), "{:#?}", response);
+}
+
+#[tokio::test]
+async fn test_async_bulk_validate_status_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_STATUS))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_STATUS_OK)
+        .create_async()
+        .await;
+
+    let response = zb_instance.bulk_validation_file_status_check("mock_file_id").await;
+    mock.assert_async().await;
+    assert_eq!(response.unwrap().complete_percentage, 100.);
+}
+
+#[tokio::test]
+async fn test_async_bulk_validate_result_json_error_http_200() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_RESULT))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_RESULT_DELETED)
+        .create_async()
+        .await;
+
+    let response = zb_instance.bulk_validation_result_fetch("mock_file_id").await;
+    mock.assert_async().await;
+
+    let ZBError::Http { status: 200, body: msg } = response.unwrap_err() else {
+        panic!("expected Http error");
+    };
+    assert!(msg.contains("File deleted"), "{}", msg);
+}
+
+#[tokio::test]
+async fn test_async_bulk_validate_result_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let expected_content = "some raw content";
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_RESULT))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_STREAM)
+        .with_body(expected_content)
+        .create_async()
+        .await;
+
+    let response = zb_instance.bulk_validation_result_fetch("mock_file_id").await;
+    mock.assert_async().await;
+
+    let ZBBulkResponse::Content(content) = response.unwrap() else {
+        panic!("expected Content");
+    };
+    assert_eq!(content.as_ref(), expected_content.as_bytes());
+}
+
+#[tokio::test]
+async fn test_async_ai_scoring_submit_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_SCORING_SEND))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .create_async()
+        .await;
+
+    let zb_file = &generate_zb_file();
+    let response = zb_instance.ai_scoring_file_submit(zb_file).await;
+    mock.assert_async().await;
+    assert!(response.is_ok(), "{:#?}", response);
+}
+
+#[tokio::test]
+async fn test_async_ai_scoring_delete_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_SCORING_DELETE))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_DELETE_OK)
+        .create_async()
+        .await;
+
+    let response = zb_instance.ai_scoring_result_delete("mock_file_id").await;
+    mock.assert_async().await;
+    assert!(response.unwrap().success);
+}
+
+#[tokio::test]
+async fn test_async_bulk_validate_submit_streamed_file() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .match_body(mockito::Matcher::Regex("email\nstreamed@example.com\n".to_string()))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .create_async()
+        .await;
+
+    let content = "email\nstreamed@example.com\n";
+    let zb_file = ZBFile::from_stream(content.as_bytes(), Some(content.len() as u64), "emails.csv");
+    let response = zb_instance.bulk_validation_file_submit(&zb_file).await;
+    mock.assert_async().await;
+    assert!(response.is_ok(), "{:#?}", response);
+
+    // the reader was consumed by the upload
+    let error = zb_instance.bulk_validation_file_submit(&zb_file).await.unwrap_err();
+    assert!(matches!(error, ZBError::Validation(_)), "{:?}", error);
+}
@@ -0,0 +1,96 @@
+use zero_bounce::utility::{ENDPOINT_CREDITS, ENDPOINT_ACTIVITY_DATA, ENDPOINT_EMAIL_FINDER};
+use zero_bounce::utility::{CONTENT_TYPE_JSON, ZBError, mock_constants};
+
+use crate::common::EMAIL;
+use crate::common::{instantiate_async, endpoint_matcher};
+
+#[tokio::test]
+async fn test_async_credits_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_CREDITS))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::CREDITS_RESPONSE_OK)
+        .create_async()
+        .await;
+
+    let credits = zb_instance.get_credits().await;
+    mock.assert_async().await;
+    assert_eq!(credits.unwrap(), 123456);
+}
+
+#[tokio::test]
+async fn test_async_activity_data_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_ACTIVITY_DATA))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::ACTIVITY_DATA_RESPONSE_ACTIVE)
+        .create_async()
+        .await;
+
+    let activity_data = zb_instance.get_activity_data(EMAIL).await;
+    mock.assert_async().await;
+
+    let activity_data = activity_data.unwrap();
+    assert!(activity_data.found);
+    assert_eq!(activity_data.active_in_days, Some(180));
+}
+
+#[tokio::test]
+async fn test_async_find_email_v2_neither_domain_nor_company() {
+    let (_mock_server, zb_instance) = instantiate_async().await;
+
+    let result = zb_instance.find_email_v2()
+        .first_name("John")
+        .call()
+        .await;
+
+    let zb_error = result.unwrap_err();
+    let ZBError::Validation(msg) = zb_error else {
+        panic!("unexpected error: {:#?}", zb_error);
+    };
+    assert!(msg.contains("either domain or company_name"));
+}
+
+#[tokio::test]
+async fn test_async_find_email_v2_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_EMAIL_FINDER))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::MOCK_FIND_EMAIL_V2_COMPANY_VALID)
+        .match_query(mockito::Matcher::UrlEncoded("company_name".to_string(), "Example Inc".to_string()))
+        .create_async()
+        .await;
+
+    let result = zb_instance.find_email_v2()
+        .first_name("John")
+        .company_name("Example Inc")
+        .call()
+        .await;
+    mock.assert_async().await;
+    assert_eq!(result.unwrap().email, "john.doe@example.com");
+}
+
+#[tokio::test]
+async fn test_async_domain_search_v2_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_EMAIL_FINDER))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::MOCK_DOMAIN_SEARCH_V2_WITH_FORMATS)
+        .create_async()
+        .await;
+
+    let result = zb_instance.domain_search_v2()
+        .domain("example.com")
+        .call()
+        .await;
+    mock.assert_async().await;
+    assert_eq!(result.unwrap().other_domain_formats.len(), 2);
+}
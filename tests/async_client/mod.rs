@@ -0,0 +1,3 @@
+mod bulk;
+mod generic_endpoints;
+mod validation;
@@ -0,0 +1,93 @@
+use zero_bounce::utility::{ENDPOINT_VALIDATE, ENDPOINT_BATCH_VALIDATE, CONTENT_TYPE_JSON, ZBError, mock_constants};
+
+use crate::common::{EMAIL, SANDBOX_IP};
+use crate::common::{instantiate_async, endpoint_matcher};
+
+#[tokio::test]
+async fn test_async_validation_bad_request() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_VALIDATE))
+        .with_status(400)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::INVALID_API_RESPONSE)
+        .create_async()
+        .await;
+
+    let validation = zb_instance.validate_email(EMAIL).await;
+    assert!(validation.is_err());
+    mock.assert_async().await;
+
+    let zb_error = validation.unwrap_err();
+    let ZBError::Http { status: 400, .. } = zb_error else {
+        panic!("unexpected error: {:#?}", zb_error);
+    };
+}
+
+#[tokio::test]
+async fn test_async_validation_with_ip_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_VALIDATE))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::VALIDATION_RESPONSE_VALID)
+        .match_query(mockito::Matcher::UrlEncoded("email".to_string(), EMAIL.to_string()))
+        .match_query(mockito::Matcher::UrlEncoded(
+            "ip_address".to_string(), SANDBOX_IP.to_string()
+        ))
+        .create_async()
+        .await;
+
+    let validation = zb_instance.validate_email_and_ip(EMAIL, SANDBOX_IP).await;
+    assert!(validation.is_ok(), "{:#?}", validation);
+    mock.assert_async().await;
+    assert_eq!(validation.unwrap().status, "valid");
+}
+
+#[tokio::test]
+async fn test_async_batch_validation_invalid_json() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_BATCH_VALIDATE))
+        .with_status(200)
+        .with_body("")
+        .create_async()
+        .await;
+
+    let emails_and_ip_addresses = vec![
+        ("valid@example.com".to_string(), "".to_string()),
+    ];
+    let validation = zb_instance.batch_validate(emails_and_ip_addresses).await;
+    assert!(validation.is_err());
+    mock.assert_async().await;
+
+    let zb_error = validation.unwrap_err();
+    let ZBError::Deserialize { .. } = zb_error else {
+        panic!("unexpected error: {:#?}", zb_error);
+    };
+}
+
+#[tokio::test]
+async fn test_async_batch_validation_ok() {
+    let (mut mock_server, zb_instance) = instantiate_async().await;
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_BATCH_VALIDATE))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BATCH_VALIDATION_WITH_ERROR)
+        .create_async()
+        .await;
+
+    let emails_and_ip_addresses = vec![
+        ("valid@example.com".to_string(), "123.123.123.123".to_string()),
+        ("invalid@example.com".to_string(), "".to_string()),
+    ];
+    let validation = zb_instance.batch_validate(emails_and_ip_addresses).await;
+    assert!(validation.is_ok(), "{:#?}", validation);
+    mock.assert_async().await;
+
+    let batch = validation.unwrap();
+    assert_eq!(batch.email_batch.len(), 1);
+    assert_eq!(batch.errors.len(), 1);
+}
@@ -23,7 +23,7 @@ fn test_ai_scoring_submit_invalid_json() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -44,7 +44,7 @@ fn test_ai_scoring_submit_bad_request() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -79,7 +79,7 @@ fn test_ai_scoring_status_invalid_json() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -99,7 +99,7 @@ fn test_ai_scoring_status_bad_request() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -153,7 +153,7 @@ fn test_ai_scoring_result_bad_request() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -172,8 +172,8 @@ fn test_ai_scoring_result_json_error_http_200() {
     mock.assert();
     assert!(response.is_err());
 
-    let ZBError::ExplicitError(msg) = response.unwrap_err() else {
-        panic!("expected ExplicitError");
+    let ZBError::Http { status: 200, body: msg } = response.unwrap_err() else {
+        panic!("expected Http error");
     };
     assert!(msg.contains("File deleted"), "{}", msg);
 }
@@ -202,6 +202,25 @@ fn test_ai_scoring_result_ok() {
     }
 }
 
+#[test]
+fn test_ai_scoring_result_download_to() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let expected_content = mock_constants::AI_SCORING_RESULT_CSV;
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_SCORING_RESULT))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_STREAM)
+        .with_body(expected_content)
+        .create();
+
+    let mut written = Vec::new();
+    let download = zb_instance.ai_scoring_result_download_to("mock_file_id", &mut written, None).unwrap();
+    mock.assert();
+
+    assert_eq!(download.bytes_written, expected_content.len() as u64);
+    assert_eq!(written, expected_content.as_bytes());
+}
+
 
 #[test]
 fn test_ai_scoring_delete_invalid_json() {
@@ -217,7 +236,7 @@ fn test_ai_scoring_delete_invalid_json() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -237,7 +256,7 @@ fn test_ai_scoring_delete_bad_request() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -257,7 +276,7 @@ fn test_ai_scoring_delete_not_ok() {
     assert!(response.is_ok());
 
     let response_obj = response.unwrap();
-    assert_eq!(response_obj.success, false, "{:#?}", response_obj);
+    assert!(!response_obj.success, "{:#?}", response_obj);
 }
 
 #[test]
@@ -275,7 +294,7 @@ fn test_ai_scoring_delete_ok() {
     assert!(response.is_ok());
 
     let response_obj = response.unwrap();
-    assert_eq!(response_obj.success, true, "{:#?}", response_obj);
+    assert!(response_obj.success, "{:#?}", response_obj);
 }
 
 
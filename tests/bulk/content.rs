@@ -0,0 +1,99 @@
+use std::io::Write;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use zero_bounce::ZBFile;
+use zero_bounce::utility::mock_constants;
+use zero_bounce::utility::{CONTENT_TYPE_JSON, ENDPOINT_FILE_SEND};
+
+use crate::common::{instantiate, endpoint_matcher};
+
+fn gzip(content: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_bulk_validate_submit_gzip_converted() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .match_body(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::Regex("filename=\"emails.csv\"".into()),
+            mockito::Matcher::Regex("\r\n\r\nemail\njohn@example.com\n\r\n".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .create();
+
+    let zb_file = ZBFile::from_content_with_filename(gzip(b"email\njohn@example.com\n"), "emails.csv.gz");
+    let response = zb_instance.bulk_validation_file_submit(&zb_file);
+    mock.assert();
+    assert!(response.is_ok(), "{:#?}", response);
+}
+
+#[test]
+fn test_bulk_validate_submit_latin1_converted() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .match_body(mockito::Matcher::Regex("francois@example.com,François\n".into()))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .create();
+
+    let path = std::env::temp_dir().join(format!("zb-submit-latin1-{}.csv", std::process::id()));
+    std::fs::write(&path, b"email,name\nfrancois@example.com,Fran\xe7ois\n").unwrap();
+    let zb_file = ZBFile::from_path(path.to_string_lossy().into_owned());
+    let response = zb_instance.bulk_validation_file_submit(&zb_file);
+    std::fs::remove_file(&path).unwrap();
+    mock.assert();
+    assert!(response.is_ok(), "{:#?}", response);
+}
+
+#[test]
+fn test_bulk_validate_submit_conversion_disabled() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .match_body(mockito::Matcher::Regex("filename=\"emails.csv.gz\"".into()))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .create();
+
+    // with the conversion disabled the compressed bytes are uploaded
+    let zb_file = ZBFile::from_content_with_filename(gzip(b"email\njohn@example.com\n"), "emails.csv.gz")
+        .set_convert_content(false);
+    let response = zb_instance.bulk_validation_file_submit(&zb_file);
+    mock.assert();
+    assert!(response.is_ok(), "{:#?}", response);
+}
+
+#[test]
+fn test_bulk_validate_submit_auto_detected_layout() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .match_body(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::Regex("name=\"has_header_row\"\r\n\r\nfalse\r\n".into()),
+            mockito::Matcher::Regex("name=\"email_address_column\"\r\n\r\n3\r\n".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .create();
+
+    let zb_file = ZBFile::from_content(b"John;Doe;john@example.com\nJane;Roe;jane@example.com\n".to_vec())
+        .set_auto_detect(true);
+    let inspection = zb_file.inspect().unwrap();
+    assert_eq!(inspection.delimiter, ';');
+    assert!(!inspection.is_ok());
+
+    let response = zb_instance.bulk_validation_file_submit(&zb_file);
+    mock.assert();
+    assert!(response.is_ok(), "{:#?}", response);
+}
@@ -0,0 +1,181 @@
+use std::time::Duration;
+
+use zero_bounce::{BulkJobKind, BulkJobOutcome, ZBError, ZBGetFileOptions};
+use zero_bounce::utility::mock_constants;
+use zero_bounce::utility::{CONTENT_TYPE_JSON, CONTENT_TYPE_STREAM};
+use zero_bounce::utility::{ENDPOINT_FILE_SEND, ENDPOINT_FILE_STATUS, ENDPOINT_FILE_RESULT, ENDPOINT_FILE_DELETE};
+use zero_bounce::utility::{ENDPOINT_SCORING_SEND, ENDPOINT_SCORING_STATUS, ENDPOINT_SCORING_RESULT};
+
+use crate::common::{instantiate, endpoint_matcher, generate_zb_file};
+
+const RESULT_CSV: &str = "\"Email Address\",\"ZB Status\"\n\"valid@example.com\",\"valid\"\n";
+
+fn file_status(file_status: &str, complete_percentage: &str, error_reason: &str) -> String {
+    format!(
+        r#"{{
+            "success": true,
+            "file_id": "e90e9b1d-8dc7-40eb-a7d9-999d52086a56",
+            "file_name": "emails2.txt",
+            "upload_date": "2023-04-26T17:52:23Z",
+            "file_status": "{}",
+            "complete_percentage": "{}",
+            "error_reason": {},
+            "return_url": null
+        }}"#,
+        file_status, complete_percentage, error_reason,
+    )
+}
+
+#[test]
+fn test_bulk_job_validation_complete_and_deleted() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let submit_mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .create();
+    let pending_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_STATUS))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(file_status("Processing", "40%", "null"))
+        .expect(2)
+        .create();
+    let complete_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_STATUS))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(file_status("Complete", "100%", "null"))
+        .expect(1)
+        .create();
+    let result_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_RESULT))
+        .match_query(mockito::Matcher::UrlEncoded("activity_data".into(), "true".into()))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_STREAM)
+        .with_body(RESULT_CSV)
+        .create();
+    let delete_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_DELETE))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_DELETE_OK)
+        .create();
+
+    let options = ZBGetFileOptions { download_type: None, activity_data: Some(true) };
+    let outcome = zb_instance.bulk_job(BulkJobKind::Validation)
+        .poll_interval(Duration::from_millis(1))
+        .max_poll_interval(Duration::from_millis(5))
+        .get_file_options(options)
+        .delete_after_download(true)
+        .run(&generate_zb_file())
+        .unwrap();
+
+    submit_mock.assert();
+    pending_mock.assert();
+    complete_mock.assert();
+    result_mock.assert();
+    delete_mock.assert();
+
+    let BulkJobOutcome::Complete { file_id, content, deleted, .. } = outcome else {
+        panic!("unexpected outcome: {:#?}", outcome);
+    };
+    assert_eq!(file_id, "e90e9b1d-8dc7-40eb-a7d9-999d52086a56");
+    assert_eq!(content, RESULT_CSV.as_bytes());
+    assert!(deleted);
+}
+
+#[test]
+fn test_bulk_job_scoring_failed() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let submit_mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_SCORING_SEND))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .create();
+    let status_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_SCORING_STATUS))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(file_status("Error", "0%", r#""Invalid file format""#))
+        .create();
+    let result_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_SCORING_RESULT))
+        .expect(0)
+        .create();
+
+    let outcome = zb_instance.bulk_job(BulkJobKind::AiScoring)
+        .poll_interval(Duration::from_millis(1))
+        .run(&generate_zb_file())
+        .unwrap();
+
+    submit_mock.assert();
+    status_mock.assert();
+    result_mock.assert();
+
+    let BulkJobOutcome::Failed { error_reason, .. } = outcome else {
+        panic!("unexpected outcome: {:#?}", outcome);
+    };
+    assert_eq!(error_reason, "Invalid file format");
+}
+
+#[test]
+fn test_bulk_job_resume_deleted_file() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let status_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_STATUS))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_STATUS_DELETED)
+        .create();
+
+    let outcome = zb_instance.bulk_job(BulkJobKind::Validation)
+        .resume("e90e9b1d-8dc7-40eb-a7d9-999d52086a56")
+        .unwrap();
+    status_mock.assert();
+
+    let BulkJobOutcome::Deleted { .. } = outcome else {
+        panic!("unexpected outcome: {:#?}", outcome);
+    };
+}
+
+#[test]
+fn test_bulk_job_timeout() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let status_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_STATUS))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(file_status("Processing", "10%", "null"))
+        .expect_at_least(2)
+        .create();
+
+    let result = zb_instance.bulk_job(BulkJobKind::Validation)
+        .poll_interval(Duration::from_millis(10))
+        .timeout(Duration::from_millis(50))
+        .resume("e90e9b1d-8dc7-40eb-a7d9-999d52086a56");
+    status_mock.assert();
+
+    let zb_error = result.unwrap_err();
+    let ZBError::BulkJobTimeout { file_id, status } = zb_error else {
+        panic!("unexpected error: {:#?}", zb_error);
+    };
+    assert_eq!(file_id, "e90e9b1d-8dc7-40eb-a7d9-999d52086a56");
+    assert_eq!(status.complete_percentage, 10.);
+}
+
+#[test]
+fn test_bulk_job_submit_rejected() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let submit_mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_ERROR)
+        .create();
+
+    let result = zb_instance.bulk_job(BulkJobKind::Validation).run(&generate_zb_file());
+    submit_mock.assert();
+
+    let zb_error = result.unwrap_err();
+    let ZBError::ExplicitError(msg) = zb_error else {
+        panic!("unexpected error: {:#?}", zb_error);
+    };
+    assert!(msg.contains("wrong format"), "{}", msg);
+}
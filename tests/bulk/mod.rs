@@ -1,2 +1,7 @@
 mod ai_scoring;
+mod content;
+mod job;
+mod streaming;
 mod validation;
+#[cfg(feature = "webhook")]
+mod webhook;
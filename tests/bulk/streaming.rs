@@ -0,0 +1,166 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use zero_bounce::{RetryPolicy, ZBError, ZBFile, ZeroBounce};
+use zero_bounce::transport::{InMemoryResponse, InMemoryTransport, ZBMethod};
+use zero_bounce::utility::{ENDPOINT_FILE_SEND, mock_constants};
+
+use crate::common::MOCK_API_KEY;
+
+const MIB: u64 = 1024 * 1024;
+
+// What the stand-in server received for the upload.
+struct ReceivedUpload {
+    content_length: Option<u64>,
+    chunked: bool,
+    body_length: u64,
+    body_start: Vec<u8>,
+}
+
+// Counts the bytes written to it, keeping only the first few kilobytes.
+#[derive(Default)]
+struct BodySink {
+    length: u64,
+    start: Vec<u8>,
+}
+
+impl Write for BodySink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let kept = buf.len().min(4096usize.saturating_sub(self.start.len()));
+        self.start.extend_from_slice(&buf[..kept]);
+        self.length += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Minimal HTTP server accepting a single sendfile request. The body is read
+// and counted as it arrives, without being stored.
+fn spawn_upload_server() -> (String, JoinHandle<ReceivedUpload>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut content_length = None;
+        let mut chunked = false;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end().to_ascii_lowercase();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("content-length:") {
+                content_length = Some(value.trim().parse::<u64>().unwrap());
+            }
+            if line.starts_with("transfer-encoding:") && line.contains("chunked") {
+                chunked = true;
+            }
+        }
+
+        let mut sink = BodySink::default();
+        if chunked {
+            loop {
+                let mut size_line = String::new();
+                reader.read_line(&mut size_line).unwrap();
+                let size_hex = size_line.trim().split(';').next().unwrap();
+                let size = u64::from_str_radix(size_hex, 16).unwrap();
+                if size == 0 {
+                    reader.read_line(&mut String::new()).unwrap();
+                    break;
+                }
+                io::copy(&mut reader.by_ref().take(size), &mut sink).unwrap();
+                reader.read_line(&mut String::new()).unwrap();
+            }
+        } else if let Some(length) = content_length {
+            io::copy(&mut reader.by_ref().take(length), &mut sink).unwrap();
+        }
+
+        let body = mock_constants::BULK_VALIDATION_SUBMIT_OK;
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            body.len(),
+            body,
+        ).unwrap();
+
+        ReceivedUpload { content_length, chunked, body_length: sink.length, body_start: sink.start }
+    });
+
+    (url, handle)
+}
+
+// CSV-like content of `size` bytes generated on the fly.
+fn synthetic_csv(size: u64) -> impl Read + Send + 'static {
+    let header: &'static [u8] = b"email\n";
+    header.chain(io::repeat(b'a').take(size - header.len() as u64))
+}
+
+fn upload_synthetic_file(size: u64, known_length: bool) -> ReceivedUpload {
+    let (url, server) = spawn_upload_server();
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .bulk_url(url)
+        .build()
+        .unwrap();
+
+    let length = known_length.then_some(size);
+    let zb_file = ZBFile::from_stream(synthetic_csv(size), length, "emails.csv");
+    let feedback = zb_instance.bulk_validation_file_submit(&zb_file).unwrap();
+    assert!(feedback.success);
+
+    let received = server.join().unwrap();
+    assert!(received.body_length > size, "{} <= {}", received.body_length, size);
+    let body_start = String::from_utf8_lossy(&received.body_start);
+    assert!(body_start.contains(r#"filename="emails.csv""#), "{}", body_start);
+    received
+}
+
+#[test]
+fn test_stream_upload_with_known_length() {
+    let received = upload_synthetic_file(16 * MIB, true);
+    assert!(!received.chunked);
+    assert_eq!(received.content_length, Some(received.body_length));
+}
+
+#[test]
+fn test_stream_upload_with_unknown_length() {
+    let received = upload_synthetic_file(16 * MIB, false);
+    assert!(received.chunked);
+    assert_eq!(received.content_length, None);
+}
+
+#[test]
+#[ignore = "uploads a 3 GiB synthetic file to a local server"]
+fn test_stream_upload_multi_gigabyte_file() {
+    let received = upload_synthetic_file(3 * 1024 * MIB, false);
+    assert!(received.chunked);
+}
+
+#[test]
+fn test_stream_upload_is_not_retried() {
+    let transport = Arc::new(InMemoryTransport::new());
+    transport
+        .push_response(ZBMethod::Post, ENDPOINT_FILE_SEND, InMemoryResponse::new(503, ""))
+        .push_response(ZBMethod::Post, ENDPOINT_FILE_SEND, InMemoryResponse::json(200, mock_constants::BULK_VALIDATION_SUBMIT_OK));
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .transport(transport.clone())
+        .retry_policy(RetryPolicy::new(3))
+        .build()
+        .unwrap();
+
+    let zb_file = ZBFile::from_stream(synthetic_csv(1024), None, "emails.csv");
+    let error = zb_instance.bulk_validation_file_submit(&zb_file).unwrap_err();
+    assert!(matches!(error, ZBError::Http { status: 503, .. }), "{:?}", error);
+    assert_eq!(transport.request_count(), 1);
+}
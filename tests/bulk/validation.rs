@@ -1,6 +1,7 @@
-use zero_bounce::{ZBError, get_file_json_indicates_error};
+use zero_bounce::{ZBError, ZBFileBuilder, ZBFileRecord, get_file_json_indicates_error};
 use zero_bounce::utility::mock_constants;
 use zero_bounce::utility::structures::bulk::{zb_download_type, ZBBulkResponse, ZBGetFileOptions};
+use zero_bounce::utility::structures::{parse_validation_results, ZBValidateStatus, ZBValidateSubStatus};
 use zero_bounce::utility::{CONTENT_TYPE_JSON, CONTENT_TYPE_STREAM};
 use zero_bounce::utility::{ENDPOINT_FILE_SEND, ENDPOINT_FILE_STATUS, ENDPOINT_FILE_RESULT, ENDPOINT_FILE_DELETE};
 
@@ -23,7 +24,7 @@ fn test_bulk_validate_submit_invalid_json() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -44,7 +45,7 @@ fn test_bulk_validate_submit_bad_request() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -65,6 +66,29 @@ fn test_bulk_validate_submit_ok() {
     assert!(response.is_ok());
 }
 
+#[test]
+fn test_bulk_validate_submit_built_file() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .match_body(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::Regex("email,first_name,crm_id\r?\n".into()),
+            mockito::Matcher::Regex("john@example.com,\"John, Jr.\",42".into()),
+            mockito::Matcher::Regex("name=\"first_name_column\"\r\n\r\n2\r\n".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .create();
+
+    let mut builder = ZBFileBuilder::new();
+    builder.push(ZBFileRecord::new("john@example.com").set_first_name("John, Jr.").add_column("crm_id", "42"));
+    builder.push("jane@example.com");
+    let response = zb_instance.bulk_validation_file_submit(&builder.build().unwrap());
+    mock.assert();
+    assert!(response.is_ok());
+}
+
 #[test]
 fn test_bulk_validate_status_invalid_json() {
     let (mut mock_server, zb_instance) = instantiate();
@@ -79,7 +103,7 @@ fn test_bulk_validate_status_invalid_json() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -99,7 +123,7 @@ fn test_bulk_validate_status_bad_request() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -153,7 +177,7 @@ fn test_bulk_validate_result_bad_request() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -172,8 +196,8 @@ fn test_bulk_validate_result_json_error_http_200() {
     mock.assert();
     assert!(response.is_err());
 
-    let ZBError::ExplicitError(msg) = response.unwrap_err() else {
-        panic!("expected ExplicitError");
+    let ZBError::Http { status: 200, body: msg } = response.unwrap_err() else {
+        panic!("expected Http error");
     };
     assert!(msg.contains("File deleted"), "{}", msg);
 }
@@ -195,9 +219,10 @@ fn test_bulk_validate_result_fetch_with_options() {
         .with_body(expected_content)
         .create();
 
-    let mut opts = ZBGetFileOptions::default();
-    opts.download_type = Some(zb_download_type::COMBINED.to_string());
-    opts.activity_data = Some(true);
+    let opts = ZBGetFileOptions {
+        download_type: Some(zb_download_type::COMBINED.to_string()),
+        activity_data: Some(true),
+    };
 
     let response = zb_instance.bulk_validation_result_fetch_with_options("mock_file_id", &opts);
     mock.assert();
@@ -233,6 +258,81 @@ fn test_bulk_validate_result_ok() {
 }
 
 
+#[test]
+fn test_bulk_validate_result_parsed_rows() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_RESULT))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_STREAM)
+        .with_body(mock_constants::BULK_VALIDATION_RESULT_CSV)
+        .create();
+
+    let response = zb_instance.bulk_validation_result_fetch("mock_file_id").unwrap();
+    mock.assert();
+
+    let ZBBulkResponse::Content(content) = response else {
+        panic!("unexpected response type: {:#?}", response);
+    };
+    let rows = parse_validation_results(&content[..]).unwrap();
+    let statuses = rows.iter()
+        .map(|row| (row.email.as_str(), row.status.clone(), row.sub_status.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(statuses, vec![
+        ("valid@example.com", ZBValidateStatus::Valid, ZBValidateSubStatus::None),
+        ("invalid@example.com", ZBValidateStatus::Invalid, ZBValidateSubStatus::MailboxNotFound),
+        ("user@gmial.com", ZBValidateStatus::Invalid, ZBValidateSubStatus::PossibleTypo),
+    ]);
+}
+
+#[test]
+fn test_bulk_validate_result_download_to() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let expected_content = "email,ZB Status\n".repeat(4096);
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_RESULT))
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("file_id".into(), "mock_file_id".into()),
+            mockito::Matcher::UrlEncoded("activity_data".into(), "true".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_STREAM)
+        .with_body(&expected_content)
+        .create();
+
+    let opts = ZBGetFileOptions { download_type: None, activity_data: Some(true) };
+    let mut written = Vec::new();
+    let download = zb_instance
+        .bulk_validation_result_download_to("mock_file_id", &mut written, Some(&opts))
+        .unwrap();
+    mock.assert();
+
+    assert_eq!(download.bytes_written, expected_content.len() as u64);
+    assert_eq!(download.content_type, CONTENT_TYPE_STREAM);
+    assert_eq!(written, expected_content.as_bytes());
+}
+
+#[test]
+fn test_bulk_validate_result_download_to_json_error() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_RESULT))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_STREAM)
+        .with_body(mock_constants::BULK_VALIDATION_RESULT_DELETED)
+        .create();
+
+    let mut written = Vec::new();
+    let response = zb_instance.bulk_validation_result_download_to("mock_file_id", &mut written, None);
+    mock.assert();
+
+    let ZBError::Http { status: 200, body: msg } = response.unwrap_err() else {
+        panic!("expected Http error");
+    };
+    assert!(msg.contains("File deleted"), "{}", msg);
+    assert!(written.is_empty());
+}
+
 #[test]
 fn test_bulk_validate_delete_invalid_json() {
     let (mut mock_server, zb_instance) = instantiate();
@@ -247,7 +347,7 @@ fn test_bulk_validate_delete_invalid_json() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -267,7 +367,7 @@ fn test_bulk_validate_delete_bad_request() {
     assert!(response.is_err());
 
     let zb_error = response.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -287,7 +387,7 @@ fn test_bulk_validate_delete_not_ok() {
     assert!(response.is_ok());
 
     let response_obj = response.unwrap();
-    assert_eq!(response_obj.success, false, "{:#?}", response_obj);
+    assert!(!response_obj.success, "{:#?}", response_obj);
 }
 
 #[test]
@@ -305,7 +405,7 @@ fn test_bulk_validate_delete_ok() {
     assert!(response.is_ok());
 
     let response_obj = response.unwrap();
-    assert_eq!(response_obj.success, true, "{:#?}", response_obj);
+    assert!(response_obj.success, "{:#?}", response_obj);
 }
 
 
@@ -0,0 +1,73 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use zero_bounce::utility::{ENDPOINT_FILE_RESULT, CONTENT_TYPE_STREAM};
+use zero_bounce::webhook::{send_test_callback, CallbackReceiver, ZBFileCallback, DEFAULT_CALLBACK_PATH};
+use zero_bounce::utility::structures::bulk::ZBBulkResponse;
+
+use crate::common::{instantiate, endpoint_matcher};
+
+const FILE_ID: &str = "e90e9b1d-8dc7-40eb-a7d9-999d52086a56";
+
+fn callback_url(server: &zero_bounce::webhook::CallbackServer, path: &str) -> String {
+    format!("http://{}{}", server.local_addr().unwrap(), path)
+}
+
+#[test]
+fn test_callback_triggers_result_fetch() {
+    let (mut mock_server, zb_instance) = instantiate();
+    let result_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_RESULT))
+        .match_query(mockito::Matcher::UrlEncoded("file_id".into(), FILE_ID.into()))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_STREAM)
+        .with_body("\"Email Address\",\"ZB Status\"\n\"valid@example.com\",\"valid\"\n")
+        .create();
+
+    let (server, callbacks) = CallbackReceiver::bind("127.0.0.1:0").unwrap()
+        .expect(FILE_ID)
+        .start_channel();
+    let url = callback_url(&server, DEFAULT_CALLBACK_PATH);
+
+    assert_eq!(send_test_callback(&url, &ZBFileCallback::new(FILE_ID)).unwrap(), 200);
+    let callback = callbacks.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(callback.file_id, FILE_ID);
+    assert_eq!(callback.file_status.as_deref(), Some("Complete"));
+
+    let response = zb_instance.bulk_validation_result_fetch(&callback.file_id).unwrap();
+    assert!(matches!(response, ZBBulkResponse::Content(_)), "{:?}", response);
+    result_mock.assert();
+
+    // delivered once
+    assert_eq!(send_test_callback(&url, &ZBFileCallback::new(FILE_ID)).unwrap(), 404);
+    assert!(server.pending().is_empty());
+    server.shutdown();
+}
+
+#[test]
+fn test_unexpected_callbacks_are_rejected() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let handler_received = received.clone();
+    let server = CallbackReceiver::bind("127.0.0.1:0").unwrap()
+        .set_path("hooks/zb")
+        .start(move |callback| handler_received.lock().unwrap().push(callback.file_id));
+    server.expect("known");
+    let url = callback_url(&server, "/hooks/zb");
+
+    assert_eq!(send_test_callback(&url, &ZBFileCallback::new("unknown")).unwrap(), 404);
+    assert_eq!(send_test_callback(&callback_url(&server, DEFAULT_CALLBACK_PATH), &ZBFileCallback::new("known")).unwrap(), 404);
+
+    let bad_payload = reqwest::blocking::Client::new()
+        .post(&url)
+        .body("file_name=emails.csv")
+        .send()
+        .unwrap();
+    assert_eq!(bad_payload.status().as_u16(), 400);
+
+    // the API may send the payload in the query string
+    let query = reqwest::blocking::get(format!("{}?file_id=known&file_status=Complete", url)).unwrap();
+    assert_eq!(query.status().as_u16(), 200);
+
+    assert!(!server.forget("known"));
+    server.shutdown();
+    assert_eq!(*received.lock().unwrap(), vec!["known".to_string()]);
+}
@@ -0,0 +1,133 @@
+use std::process::{Command, Output};
+
+use mockito::{Matcher, Server, ServerGuard};
+use zero_bounce::utility::{CONTENT_TYPE_JSON, ENDPOINT_BATCH_VALIDATE, ENDPOINT_CREDITS, ENDPOINT_FILE_RESULT};
+use zero_bounce::utility::{ENDPOINT_SCORING_STATUS, ENDPOINT_VALIDATE, mock_constants};
+
+use crate::common::{endpoint_matcher, MOCK_API_KEY};
+
+// Run `zb` against the mock server, with the API key in the environment
+fn zb(mock_server: &ServerGuard, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_zb"))
+        .args(["--base-url", &mock_server.url(), "--bulk-url", &mock_server.url()])
+        .args(args)
+        .env("ZEROBOUNCE_API_KEY", MOCK_API_KEY)
+        .env_remove("ZERO_BOUNCE_API_KEY")
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn json_mock(mock_server: &mut ServerGuard, method: &str, endpoint: &str, body: &str) -> mockito::Mock {
+    mock_server.mock(method, endpoint_matcher(endpoint))
+        .match_query(Matcher::UrlEncoded("api_key".into(), MOCK_API_KEY.into()))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(body)
+        .create()
+}
+
+#[test]
+fn test_credits() {
+    let mut mock_server = Server::new();
+    let mock = json_mock(&mut mock_server, "GET", ENDPOINT_CREDITS, mock_constants::CREDITS_RESPONSE_OK).expect(2);
+
+    let output = zb(&mock_server, &["credits"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "Credits: 123456\n");
+
+    let output = zb(&mock_server, &["credits", "--json"]);
+    let json = serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap();
+    assert_eq!(json["Credits"], "123456");
+    mock.assert();
+}
+
+#[test]
+fn test_validate_prints_fields() {
+    let mut mock_server = Server::new();
+    let mock = json_mock(&mut mock_server, "GET", ENDPOINT_VALIDATE, mock_constants::VALIDATION_RESPONSE_VALID);
+
+    let output = zb(&mock_server, &["validate", "valid@example.com", "--ip", "99.110.204.1"]);
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert!(stdout.lines().any(|line| line.split_whitespace().eq(["status", "valid"])), "{}", stdout);
+    assert!(stdout.lines().any(|line| line.split_whitespace().eq(["address", "valid@example.com"])));
+    mock.assert();
+}
+
+#[test]
+fn test_batch_prints_table() {
+    let mut mock_server = Server::new();
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_BATCH_VALIDATE))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BATCH_VALIDATION_NO_ERROR)
+        .create();
+    let path = std::env::temp_dir().join(format!("zb-cli-batch-{}.csv", std::process::id()));
+    std::fs::write(&path, "email,ip\nvalid@example.com,99.110.204.1\n\n").unwrap();
+
+    let output = zb(&mock_server, &["batch", path.to_str().unwrap()]);
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with("ADDRESS"), "{}", stdout);
+    assert!(lines[1].split_whitespace().eq(["valid@example.com", "valid"]), "{}", stdout);
+    mock.assert();
+}
+
+#[test]
+fn test_scoring_status_json() {
+    let mut mock_server = Server::new();
+    let mock = json_mock(&mut mock_server, "GET", ENDPOINT_SCORING_STATUS, mock_constants::BULK_VALIDATION_STATUS_OK);
+
+    let output = zb(&mock_server, &["--json", "scoring", "status", "e90e9b1d"]);
+    assert!(output.status.success());
+    let json = serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap();
+    assert_eq!(json["complete_percentage"], "100%");
+    mock.assert();
+}
+
+#[test]
+fn test_bulk_fetch_writes_results() {
+    let mut mock_server = Server::new();
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_RESULT))
+        .with_status(200)
+        .with_header("content-type", "application/octet-stream")
+        .with_body(mock_constants::BULK_VALIDATION_RESULT_CSV)
+        .create();
+
+    let output = zb(&mock_server, &["bulk", "fetch", "e90e9b1d"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), mock_constants::BULK_VALIDATION_RESULT_CSV);
+    mock.assert();
+}
+
+#[test]
+fn test_errors_are_reported() {
+    let mut mock_server = Server::new();
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_CREDITS))
+        .with_status(400)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::INVALID_API_RESPONSE)
+        .create();
+
+    let output = zb(&mock_server, &["credits"]);
+    assert!(!output.status.success());
+    // debug builds of the library also log the raw response
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.ends_with("error: HTTP 400: Missing parameter: api_key.\n"), "{}", stderr);
+    mock.assert();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_zb"))
+        .args(["credits"])
+        .env_remove("ZEROBOUNCE_API_KEY")
+        .env_remove("ZERO_BOUNCE_API_KEY")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("ZEROBOUNCE_API_KEY"));
+}
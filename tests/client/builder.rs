@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use mockito::Server;
+use zero_bounce::ZeroBounce;
+use zero_bounce::builder::SDK_USER_AGENT;
+use zero_bounce::utility::{ENDPOINT_CREDITS, ENDPOINT_FILE_STATUS, CONTENT_TYPE_JSON, mock_constants};
+
+use crate::common::{MOCK_API_KEY, endpoint_matcher};
+
+#[test]
+fn test_builder_sends_user_agent_and_default_headers() {
+    let mut mock_server = Server::new();
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .base_url(mock_server.url())
+        .default_header("x-request-source", "crm-sync")
+        .connect_timeout(Duration::from_secs(5))
+        .read_timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_CREDITS))
+        .match_header("user-agent", SDK_USER_AGENT)
+        .match_header("x-request-source", "crm-sync")
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::CREDITS_RESPONSE_OK)
+        .create();
+
+    let credits = zb_instance.get_credits();
+    mock.assert();
+    assert_eq!(credits.unwrap(), 123456);
+}
+
+#[test]
+fn test_builder_custom_client_and_bulk_url() {
+    let mut mock_server = Server::new();
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("custom-agent")
+        .build()
+        .unwrap();
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .bulk_url(mock_server.url())
+        .client(client)
+        .build()
+        .unwrap();
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_FILE_STATUS))
+        .match_header("user-agent", "custom-agent")
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_STATUS_OK)
+        .create();
+
+    let status = zb_instance.bulk_validation_file_status_check("mock_file_id");
+    mock.assert();
+    assert!(status.is_ok(), "{:?}", status);
+}
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use zero_bounce::transport::{InMemoryResponse, InMemoryTransport, ZBMethod};
+use zero_bounce::utility::{ENDPOINT_BATCH_VALIDATE, ENDPOINT_CREDITS, ENDPOINT_FILE_SEND, ENDPOINT_SCORING_SEND, ENDPOINT_VALIDATE};
+use zero_bounce::utility::mock_constants;
+use zero_bounce::{CreditGuard, ZBError, ZBFile, ZeroBounce};
+
+use crate::common::MOCK_API_KEY;
+
+fn guarded_instance(credit_guard: &CreditGuard) -> (Arc<InMemoryTransport>, ZeroBounce) {
+    let transport = Arc::new(InMemoryTransport::new());
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .transport(transport.clone())
+        .credit_guard(credit_guard.clone())
+        .build()
+        .unwrap();
+    (transport, zb_instance)
+}
+
+fn emails(count: usize) -> Vec<(String, String)> {
+    (0..count).map(|index| (format!("user{}@example.com", index), String::new())).collect()
+}
+
+#[test]
+fn test_max_spend_refuses_calls() {
+    let credit_guard = CreditGuard::new().set_max_spend(3);
+    let (transport, zb_instance) = guarded_instance(&credit_guard);
+    transport
+        .push_response(ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID))
+        .push_response(ZBMethod::Post, ENDPOINT_BATCH_VALIDATE, InMemoryResponse::json(200, mock_constants::BATCH_VALIDATION_NO_ERROR));
+
+    zb_instance.validate_email("valid@example.com").unwrap();
+    let error = zb_instance.batch_validate(emails(3)).unwrap_err();
+    assert!(matches!(error, ZBError::CreditBudgetExceeded { cost: 3, available: 2 }), "{:?}", error);
+    assert_eq!(transport.request_count(), 1);
+
+    zb_instance.batch_validate(emails(2)).unwrap();
+    assert_eq!(credit_guard.spent(), 3);
+    assert_eq!(credit_guard.remaining(), Some(0));
+}
+
+#[test]
+fn test_floor_checks_the_balance() {
+    let credit_guard = CreditGuard::new().set_floor(123_451);
+    let (transport, zb_instance) = guarded_instance(&credit_guard);
+    transport
+        .push_response(ZBMethod::Get, ENDPOINT_CREDITS, InMemoryResponse::json(200, mock_constants::CREDITS_RESPONSE_OK))
+        .push_response(ZBMethod::Post, ENDPOINT_BATCH_VALIDATE, InMemoryResponse::json(200, mock_constants::BATCH_VALIDATION_NO_ERROR));
+
+    // the balance of 123456 credits leaves 5 above the floor
+    zb_instance.batch_validate(emails(5)).unwrap();
+    let error = zb_instance.validate_email("valid@example.com").unwrap_err();
+    assert!(matches!(error, ZBError::CreditBudgetExceeded { cost: 1, available: 0 }), "{:?}", error);
+    // only one balance request, then the balance is estimated
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[test]
+fn test_failed_calls_give_credits_back() {
+    let credit_guard = CreditGuard::new().set_max_spend(1);
+    let (transport, zb_instance) = guarded_instance(&credit_guard);
+    transport
+        .push_response(ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::new(500, ""))
+        .push_response(ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID));
+
+    assert!(zb_instance.validate_email("valid@example.com").is_err());
+    assert_eq!(credit_guard.spent(), 0);
+    zb_instance.validate_email("valid@example.com").unwrap();
+    assert_eq!(credit_guard.spent(), 1);
+}
+
+#[test]
+fn test_bulk_submit_is_estimated_from_rows() {
+    let credit_guard = CreditGuard::new().set_max_spend(3);
+    let (transport, zb_instance) = guarded_instance(&credit_guard);
+    transport.push_response(
+        ZBMethod::Post, ENDPOINT_FILE_SEND, InMemoryResponse::json(200, mock_constants::BULK_VALIDATION_SUBMIT_OK)
+    );
+
+    let zb_file = ZBFile::from_content(Vec::from("email\nvalid@example.com\n\ninvalid@example.com\n"));
+    assert_eq!(zb_file.row_count().unwrap(), 2);
+    zb_instance.bulk_validation_file_submit(&zb_file).unwrap();
+    assert_eq!(credit_guard.spent(), 2);
+
+    let zb_file = crate::common::generate_zb_file();
+    let error = zb_instance.bulk_validation_file_submit(&zb_file).unwrap_err();
+    assert!(matches!(error, ZBError::CreditBudgetExceeded { cost: 4, available: 1 }), "{:?}", error);
+
+    let streamed = ZBFile::from_stream(std::io::empty(), None, "emails.csv");
+    assert!(matches!(zb_instance.bulk_validation_file_submit(&streamed), Err(ZBError::Validation(_))));
+    assert_eq!(transport.request_count(), 1);
+}
+
+#[test]
+fn test_streamed_file_is_refused() {
+    let credit_guard = CreditGuard::new().set_max_spend(100);
+    let (transport, zb_instance) = guarded_instance(&credit_guard);
+
+    let streamed = ZBFile::from_stream(&b"email\nvalid@example.com\n"[..], None, "emails.csv");
+    let error = zb_instance.ai_scoring_file_submit(&streamed).unwrap_err();
+    assert!(error.to_string().contains("credit guard"), "{}", error);
+    assert_eq!(transport.request_count(), 0);
+    assert_eq!(credit_guard.spent(), 0);
+
+    // the same content loaded in memory goes through
+    transport.push_response(
+        ZBMethod::Post, ENDPOINT_SCORING_SEND, InMemoryResponse::json(200, mock_constants::BULK_VALIDATION_SUBMIT_OK)
+    );
+    let zb_file = ZBFile::from_reader(&b"email\nvalid@example.com\n"[..], "emails.csv").unwrap();
+    zb_instance.ai_scoring_file_submit(&zb_file).unwrap();
+    assert_eq!(credit_guard.spent(), 1);
+}
@@ -0,0 +1,5 @@
+mod builder;
+mod credit_guard;
+mod rate_limit;
+mod retry;
+mod transport;
@@ -0,0 +1,96 @@
+use std::thread;
+
+use zero_bounce::{EndpointFamily, RateLimit, RateLimitMode, RateLimiter, ZBError};
+use zero_bounce::utility::{ENDPOINT_BATCH_VALIDATE, ENDPOINT_CREDITS, ENDPOINT_VALIDATE, CONTENT_TYPE_JSON, mock_constants};
+
+use crate::common::{EMAIL, instantiate, endpoint_matcher};
+
+fn non_blocking_limiter(family: EndpointFamily, requests: u32) -> Option<RateLimiter> {
+    Some(
+        RateLimiter::new(RateLimitMode::NonBlocking)
+            .set_limit(family, RateLimit::per_minute(requests))
+    )
+}
+
+#[test]
+fn test_rate_limit_non_blocking_validate() {
+    let (mut mock_server, zb_instance) = instantiate();
+    let zb_instance = zb_instance.set_rate_limiter(non_blocking_limiter(EndpointFamily::Validate, 1));
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_VALIDATE))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::VALIDATION_RESPONSE_VALID)
+        .expect(1)
+        .create();
+
+    assert!(zb_instance.validate_email(EMAIL).is_ok());
+
+    let zb_error = zb_instance.validate_email(EMAIL).unwrap_err();
+    let ZBError::RateLimitExceeded { family, .. } = zb_error else {
+        panic!("unexpected error: {:#?}", zb_error);
+    };
+    assert_eq!(family, EndpointFamily::Validate);
+    mock.assert();
+}
+
+#[test]
+fn test_rate_limit_ignores_other_families() {
+    let (mut mock_server, zb_instance) = instantiate();
+    let zb_instance = zb_instance.set_rate_limiter(non_blocking_limiter(EndpointFamily::Validate, 1));
+
+    let credits_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_CREDITS))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::CREDITS_RESPONSE_OK)
+        .expect(3)
+        .create();
+    let batch_mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_BATCH_VALIDATE))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BATCH_VALIDATION_NO_ERROR)
+        .expect(2)
+        .create();
+
+    for _ in 0..3 {
+        assert!(zb_instance.get_credits().is_ok());
+    }
+    for _ in 0..2 {
+        let emails = vec![(EMAIL.to_string(), "".to_string())];
+        assert!(zb_instance.batch_validate(emails).is_ok());
+    }
+    credits_mock.assert();
+    batch_mock.assert();
+}
+
+#[test]
+fn test_rate_limit_shared_between_clones() {
+    let (mut mock_server, zb_instance) = instantiate();
+    let zb_instance = zb_instance.set_rate_limiter(non_blocking_limiter(EndpointFamily::BatchValidate, 2));
+
+    let mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_BATCH_VALIDATE))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BATCH_VALIDATION_NO_ERROR)
+        .expect(2)
+        .create();
+
+    let handles = (0..3)
+        .map(|_| {
+            let zb_clone = zb_instance.clone();
+            thread::spawn(move || {
+                let emails = vec![(EMAIL.to_string(), "".to_string())];
+                zb_clone.batch_validate(emails)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let results = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 2);
+    assert!(results.iter().any(|result| matches!(result, Err(ZBError::RateLimitExceeded { .. }))));
+    mock.assert();
+}
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use zero_bounce::{RetryPolicy, ZBError};
+use zero_bounce::utility::{ENDPOINT_CREDITS, ENDPOINT_FILE_SEND, ENDPOINT_VALIDATE, CONTENT_TYPE_JSON, mock_constants};
+
+use crate::common::{EMAIL, instantiate, invalid_url_zb_instance, endpoint_matcher, generate_zb_file};
+
+fn fast_retry_policy(max_attempts: u32) -> Option<RetryPolicy> {
+    Some(RetryPolicy {
+        max_attempts,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_retry_server_error_then_ok() {
+    let (mut mock_server, zb_instance) = instantiate();
+    let zb_instance = zb_instance.set_retry_policy(fast_retry_policy(3));
+
+    let failing_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_CREDITS))
+        .with_status(503)
+        .with_body("service unavailable")
+        .expect(2)
+        .create();
+    let ok_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_CREDITS))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::CREDITS_RESPONSE_OK)
+        .expect(1)
+        .create();
+
+    let credits = zb_instance.get_credits();
+    failing_mock.assert();
+    ok_mock.assert();
+    assert_eq!(credits.unwrap(), 123456);
+}
+
+#[test]
+fn test_retry_rate_limited_then_ok() {
+    let (mut mock_server, zb_instance) = instantiate();
+    let zb_instance = zb_instance.set_retry_policy(fast_retry_policy(2));
+
+    let failing_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_VALIDATE))
+        .with_status(429)
+        .expect(1)
+        .create();
+    let ok_mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_VALIDATE))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::VALIDATION_RESPONSE_VALID)
+        .expect(1)
+        .create();
+
+    let validation = zb_instance.validate_email(EMAIL);
+    failing_mock.assert();
+    ok_mock.assert();
+    assert!(validation.is_ok(), "{:#?}", validation);
+}
+
+#[test]
+fn test_retry_never_retries_auth_errors() {
+    let (mut mock_server, zb_instance) = instantiate();
+    let zb_instance = zb_instance.set_retry_policy(fast_retry_policy(3));
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_CREDITS))
+        .with_status(401)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::INVALID_API_RESPONSE)
+        .expect(1)
+        .create();
+
+    let credits = zb_instance.get_credits();
+    mock.assert();
+
+    let zb_error = credits.unwrap_err();
+    let ZBError::Http { status: 401, .. } = zb_error else {
+        panic!("unexpected error: {:#?}", zb_error);
+    };
+    assert!(zb_error.is_auth());
+}
+
+#[test]
+fn test_retry_exhausted_keeps_every_failure() {
+    let (mut mock_server, zb_instance) = instantiate();
+    let zb_instance = zb_instance.set_retry_policy(fast_retry_policy(3));
+
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_CREDITS))
+        .with_status(500)
+        .with_body("internal error")
+        .expect(3)
+        .create();
+
+    let credits = zb_instance.get_credits();
+    mock.assert();
+
+    let zb_error = credits.unwrap_err();
+    let ZBError::RetriesExhausted(failures) = zb_error else {
+        panic!("unexpected error: {:#?}", zb_error);
+    };
+    assert_eq!(failures.len(), 3);
+    for failure in failures {
+        let ZBError::Http { status: 500, body } = failure else {
+            panic!("unexpected failure: {:#?}", failure);
+        };
+        assert_eq!(body, "internal error");
+    }
+}
+
+#[test]
+fn test_retry_transport_errors() {
+    let zb_instance = invalid_url_zb_instance()
+        .set_retry_policy(fast_retry_policy(2));
+
+    let zb_error = zb_instance.get_credits().unwrap_err();
+    let ZBError::RetriesExhausted(failures) = zb_error else {
+        panic!("unexpected error: {:#?}", zb_error);
+    };
+    assert_eq!(failures.len(), 2);
+    assert!(failures.iter().all(|failure| matches!(failure, ZBError::RequestError(_))));
+}
+
+#[test]
+fn test_retry_file_submit_rebuilds_multipart() {
+    let (mut mock_server, zb_instance) = instantiate();
+    let zb_instance = zb_instance.set_retry_policy(fast_retry_policy(3));
+
+    // both attempts must carry the whole file content
+    let file_matcher = mockito::Matcher::Regex("donotmail@example.com".to_string());
+    let failing_mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .match_body(file_matcher.clone())
+        .with_status(502)
+        .expect(1)
+        .create();
+    let ok_mock = mock_server.mock("POST", endpoint_matcher(ENDPOINT_FILE_SEND))
+        .match_body(file_matcher)
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(mock_constants::BULK_VALIDATION_SUBMIT_OK)
+        .expect(1)
+        .create();
+
+    let response = zb_instance.bulk_validation_file_submit(&generate_zb_file());
+    failing_mock.assert();
+    ok_mock.assert();
+    assert!(response.is_ok(), "{:#?}", response);
+}
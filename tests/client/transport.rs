@@ -0,0 +1,122 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use zero_bounce::{RetryPolicy, ZBError, ZeroBounce};
+use zero_bounce::transport::{
+    InMemoryResponse, InMemoryTransport, Transport, ZBMethod, ZBMultipartField, ZBRequest, ZBRequestBody, ZBResponse,
+};
+use zero_bounce::utility::{ENDPOINT_BATCH_VALIDATE, ENDPOINT_FILE_SEND, ENDPOINT_VALIDATE, ZBResult, mock_constants};
+
+use crate::common::{EMAIL, MOCK_API_KEY, generate_zb_file};
+
+fn in_memory_instance() -> (Arc<InMemoryTransport>, ZeroBounce) {
+    let transport = Arc::new(InMemoryTransport::new());
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    (transport, zb_instance)
+}
+
+#[test]
+fn test_in_memory_validation() {
+    let (transport, zb_instance) = in_memory_instance();
+    transport.push_response(
+        ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+    );
+
+    let validation = zb_instance.validate_email_and_ip(EMAIL, "99.110.204.1").unwrap();
+    assert_eq!(validation.status, "valid");
+
+    let requests = transport.take_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, ZBMethod::Get);
+    assert_eq!(requests[0].query_value("email"), Some(EMAIL));
+    assert_eq!(requests[0].query_value("ip_address"), Some("99.110.204.1"));
+    assert_eq!(requests[0].query_value("api_key"), Some(MOCK_API_KEY));
+}
+
+#[test]
+fn test_in_memory_batch_validation_body() {
+    let (transport, zb_instance) = in_memory_instance();
+    transport.push_response(
+        ZBMethod::Post, ENDPOINT_BATCH_VALIDATE, InMemoryResponse::json(200, mock_constants::BATCH_VALIDATION_NO_ERROR)
+    );
+
+    let batch = zb_instance.batch_validate(vec![(EMAIL.to_string(), String::new())]);
+    assert!(batch.is_ok(), "{:?}", batch);
+
+    let requests = transport.take_requests();
+    let ZBRequestBody::Bytes { content_type, content } = &requests[0].body else {
+        panic!("unexpected body: {:#?}", requests[0].body);
+    };
+    assert_eq!(content_type, "application/json");
+    let body = serde_json::from_slice::<serde_json::Value>(content).unwrap();
+    assert_eq!(body["api_key"], MOCK_API_KEY);
+    assert_eq!(body["email_batch"][0]["email_address"], EMAIL);
+}
+
+#[test]
+fn test_in_memory_file_submit_fields() {
+    let (transport, zb_instance) = in_memory_instance();
+    transport.push_response(
+        ZBMethod::Post, ENDPOINT_FILE_SEND, InMemoryResponse::json(200, mock_constants::BULK_VALIDATION_SUBMIT_OK)
+    );
+
+    let zb_file = generate_zb_file().set_allow_phase_2(Some(true));
+    let feedback = zb_instance.bulk_validation_file_submit(&zb_file).unwrap();
+    assert!(feedback.success);
+
+    let requests = transport.take_requests();
+    let ZBRequestBody::Multipart(fields) = &requests[0].body else {
+        panic!("unexpected body: {:#?}", requests[0].body);
+    };
+    let names = fields.iter().map(ZBMultipartField::name).collect::<Vec<_>>();
+    assert_eq!(names[0], "file");
+    assert!(names.contains(&"api_key"));
+    assert!(names.contains(&"allow_phase_2"));
+}
+
+// Fails with a transport error until the given amount of calls is reached
+struct FlakyTransport {
+    failures_left: AtomicU32,
+    inner: InMemoryTransport,
+}
+
+impl Transport for FlakyTransport {
+    fn send(&self, request: ZBRequest) -> ZBResult<ZBResponse> {
+        let failures_left = self.failures_left.load(Ordering::SeqCst);
+        if failures_left > 0 {
+            self.failures_left.store(failures_left - 1, Ordering::SeqCst);
+            return Err(ZBError::TransportError("connection reset".into()));
+        }
+        self.inner.send(request)
+    }
+}
+
+#[test]
+fn test_custom_transport_errors_are_retried() {
+    let inner = InMemoryTransport::new();
+    inner.push_response(
+        ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+    );
+    let transport = Arc::new(FlakyTransport { failures_left: AtomicU32::new(2), inner });
+
+    let policy = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::from_millis(1),
+        ..Default::default()
+    };
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .transport(transport.clone())
+        .retry_policy(policy)
+        .build()
+        .unwrap();
+
+    let validation = zb_instance.validate_email(EMAIL);
+    assert!(validation.is_ok(), "{:?}", validation);
+    assert_eq!(transport.inner.request_count(), 1);
+}
@@ -1,23 +1,40 @@
 use mockito::{Matcher, ServerGuard, Server};
-use zero_bounce::{ZBUrlProvider, ZeroBounce, ZBFile};
+use zero_bounce::{ZeroBounce, ZBFile};
+#[cfg(feature = "async")]
+use zero_bounce::{ZBUrlProvider, ZeroBounceAsync};
 
 pub(crate) const EMAIL: &str = "example@example.com";
 pub(crate) const MOCK_API_KEY: &str = "mock_api_key";
 pub(crate) const INVALID_URL: &str = "http://255.255.255.255";
 pub(crate) const SANDBOX_IP: &str = "99.110.204.1";
 
-pub(crate) fn instantiate<'s>() -> (ServerGuard, ZeroBounce) {
+pub(crate) fn instantiate() -> (ServerGuard, ZeroBounce) {
 
     let mock_server = Server::new();
     let mock_url: String = mock_server.url().to_owned();
 
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .base_url(mock_url.clone())
+        .bulk_url(mock_url)
+        .build()
+        .unwrap();
+    (mock_server, zb_instance)
+}
+
+#[cfg(feature = "async")]
+pub(crate) async fn instantiate_async() -> (ServerGuard, ZeroBounceAsync) {
+
+    let mock_server = Server::new_async().await;
+    let mock_url: String = mock_server.url().to_owned();
+
     let mock_url_provider = ZBUrlProvider {
         url: mock_url.clone(), bulk_url: mock_url.clone()
     };
-    let zb_instance = ZeroBounce {
+    let zb_instance = ZeroBounceAsync {
         api_key: MOCK_API_KEY.to_string().clone(),
         base_url: mock_url.clone(),
-        client: reqwest::blocking::Client::default(),
+        client: reqwest::Client::default(),
         url_provider: mock_url_provider,
     };
     (mock_server, zb_instance)
@@ -28,15 +45,12 @@ pub(crate) fn endpoint_matcher(endpoint: &str) -> Matcher {
 }
 
 pub(crate) fn invalid_url_zb_instance() -> ZeroBounce {
-    ZeroBounce {
-        api_key: MOCK_API_KEY.to_string().clone(),
-        base_url: INVALID_URL.to_owned(),
-        client: reqwest::blocking::Client::default(),
-        url_provider: ZBUrlProvider {
-            url: INVALID_URL.to_owned(),
-            bulk_url: INVALID_URL.to_owned(),
-        },
-    }
+    ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .base_url(INVALID_URL)
+        .bulk_url(INVALID_URL)
+        .build()
+        .unwrap()
 }
 
 pub(crate) fn generate_zb_file() -> ZBFile {
@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use zero_bounce::testing::{FakeZeroBounce, DEFAULT_FAKE_CREDITS, FAKE_API_KEY, SANDBOX_ADDRESSES};
+use zero_bounce::utility::structures::{parse_validation_results, ZBValidateStatus};
+use zero_bounce::utility::structures::bulk_results::{JOIN_DROPPED, JOIN_DUPLICATE, JOIN_MATCHED};
+use zero_bounce::utility::{ENDPOINT_FILE_STATUS, ENDPOINT_VALIDATE};
+use zero_bounce::{BulkJobKind, BulkJobOutcome, ZBBulkResponse, ZBError, ZBFile, ZBSplitLimits, ZeroBounce};
+
+fn start() -> (FakeZeroBounce, ZeroBounce) {
+    let fake = FakeZeroBounce::start().unwrap();
+    let zb = fake.client();
+    (fake, zb)
+}
+
+#[test]
+fn test_sandbox_addresses_are_free() {
+    let (fake, zb) = start();
+
+    for (address, status, sub_status) in SANDBOX_ADDRESSES {
+        let validation = zb.validate_email(address).unwrap();
+        assert_eq!((validation.status.as_str(), validation.sub_status.as_str()), (*status, *sub_status), "{}", address);
+    }
+    assert_eq!(zb.get_credits().unwrap(), DEFAULT_FAKE_CREDITS);
+    assert_eq!(fake.request_count(ENDPOINT_VALIDATE), SANDBOX_ADDRESSES.len());
+
+    let validation = zb.validate_email("john.doe@my-company.com").unwrap();
+    assert_eq!(validation.status, "valid");
+    assert_eq!(validation.domain.as_deref(), Some("my-company.com"));
+    assert_eq!(zb.validate_email("john.doe@").unwrap().sub_status, "failed_syntax_check");
+    assert_eq!(fake.credits(), DEFAULT_FAKE_CREDITS - 2);
+}
+
+#[test]
+fn test_credits_and_api_key() {
+    let (fake, zb) = start();
+    fake.set_credits(1);
+
+    let batch = vec![
+        ("valid@example.com".to_string(), String::new()),
+        ("jane@my-company.com".to_string(), String::new()),
+    ];
+    assert_eq!(zb.batch_validate(batch).unwrap().email_batch.len(), 2);
+    assert_eq!(zb.get_credits().unwrap(), 0);
+
+    let error = zb.validate_email("john@my-company.com").unwrap_err();
+    assert!(matches!(error, ZBError::InsufficientCredits), "{:?}", error);
+
+    let usage = zb.get_api_usage_overall().unwrap();
+    assert_eq!((usage.total, usage.status_valid), (2, 2));
+
+    let other_key = ZeroBounce::with_base_url("other-key", fake.url());
+    assert_eq!(other_key.get_credits().unwrap(), -1);
+    assert!(matches!(other_key.validate_email("valid@example.com"), Err(ZBError::InvalidApiKey)));
+    assert_eq!(zb.api_key, FAKE_API_KEY);
+}
+
+#[test]
+fn test_activity_and_finder() {
+    let (fake, zb) = start();
+    fake.set_activity("valid@example.com", 30);
+
+    assert_eq!(zb.get_activity_data("VALID@example.com").unwrap().active_in_days, Some(30));
+    assert!(!zb.get_activity_data("invalid@example.com").unwrap().found);
+
+    let found = zb.find_email_v2().first_name("John").last_name("Doe").company_name("Example Inc").call().unwrap();
+    assert_eq!(found.email, "john.doe@exampleinc.com");
+    let search = zb.domain_search_v2().domain("example.com").call().unwrap();
+    assert_eq!(search.format, "first.last");
+}
+
+#[test]
+fn test_bulk_file_lifecycle() {
+    let (fake, zb) = start();
+    fake.set_processing_time(Duration::from_millis(300));
+
+    let content = "name,email\nJohn,valid@example.com\nJane,jane@my-company.com\nJane,JANE@my-company.com\n";
+    let zb_file = ZBFile::from_content(content.as_bytes().to_vec())
+        .set_has_header_row(true)
+        .set_remove_duplicate(true)
+        .set_email_address_column(2);
+
+    let file_id = zb.bulk_validation_file_submit(&zb_file).unwrap().file_id.unwrap();
+    assert_eq!(fake.credits(), DEFAULT_FAKE_CREDITS - 2);
+    assert_eq!(zb.bulk_validation_file_status_check(&file_id).unwrap().file_status, "Queued");
+    assert!(zb.bulk_validation_result_fetch(&file_id).is_err());
+    assert!(zb.ai_scoring_file_status_check(&file_id).is_err());
+
+    let outcome = zb.bulk_job(BulkJobKind::Validation)
+        .poll_interval(Duration::from_millis(50))
+        .delete_after_download(true)
+        .resume(&file_id)
+        .unwrap();
+    let BulkJobOutcome::Complete { content, deleted: true, .. } = outcome else {
+        panic!("unexpected outcome: {:?}", outcome);
+    };
+    let rows = parse_validation_results(&content[..]).unwrap();
+    let emails = rows.iter().map(|row| row.email.as_str()).collect::<Vec<_>>();
+    assert_eq!(emails, ["valid@example.com", "jane@my-company.com"]);
+    assert!(fake.request_count(ENDPOINT_FILE_STATUS) >= 2);
+
+    assert_eq!(zb.bulk_validation_file_status_check(&file_id).unwrap().file_status, "Deleted");
+    assert!(zb.bulk_validation_result_delete(&file_id).is_err());
+}
+
+#[test]
+fn test_split_file_results_are_merged() {
+    let (fake, zb) = start();
+    fake.set_processing_time(Duration::from_millis(100));
+
+    let mut content = "email,id\n".to_string();
+    for index in 1..=5 {
+        content.push_str(&format!("user{}@my-company.com,{}\n", index, index));
+    }
+    let zb_file = ZBFile::from_content(content.into_bytes());
+
+    let outcome = zb.bulk_job(BulkJobKind::Validation)
+        .poll_interval(Duration::from_millis(50))
+        .run_split(&zb_file, ZBSplitLimits::default().set_max_rows(2))
+        .unwrap();
+    assert_eq!(outcome.file_ids().len(), 3);
+    assert_eq!(fake.credits(), DEFAULT_FAKE_CREDITS - 5);
+
+    let rows = parse_validation_results(&outcome.content.unwrap()[..]).unwrap();
+    let ids = rows.iter().map(|row| row.input[1].1.as_str()).collect::<Vec<_>>();
+    assert_eq!(ids, ["1", "2", "3", "4", "5"]);
+}
+
+#[test]
+fn test_split_results_follow_input_order() {
+    let (fake, zb) = start();
+    fake.set_processing_time(Duration::ZERO);
+    fake.set_reverse_results(true);
+
+    let content = "id,email\n1,user1@my-company.com\n2,valid@example.com\n3,user3@my-company.com\n4,invalid@example.com\n";
+    let zb_file = ZBFile::from_content(content.as_bytes().to_vec()).set_email_address_column(2);
+
+    let outcome = zb.bulk_job(BulkJobKind::Validation)
+        .poll_interval(Duration::from_millis(10))
+        .run_split(&zb_file, ZBSplitLimits::default().set_max_rows(2))
+        .unwrap();
+    let Ok(BulkJobOutcome::Complete { content: chunk, .. }) = &outcome.chunks[0] else {
+        panic!("first chunk not complete");
+    };
+    let chunk_emails = parse_validation_results(&chunk[..]).unwrap().into_iter().map(|row| row.email).collect::<Vec<_>>();
+    assert_eq!(chunk_emails, ["valid@example.com", "user1@my-company.com"]);
+
+    let merged = outcome.content.unwrap();
+    let rows = parse_validation_results(&merged[..]).unwrap();
+    let ids = rows.iter().map(|row| row.input[0].1.as_str()).collect::<Vec<_>>();
+    assert_eq!(ids, ["1", "2", "3", "4"]);
+    assert_eq!(rows[1].status, ZBValidateStatus::Valid);
+    assert_eq!(rows[3].status, ZBValidateStatus::Invalid);
+    assert!(rows.iter().all(|row| row.extra[0].1 == JOIN_MATCHED));
+}
+
+#[test]
+fn test_failed_chunk_keeps_other_outcomes() {
+    let (fake, zb) = start();
+    fake.set_processing_time(Duration::ZERO);
+    // Enough for the first and last chunks only
+    fake.set_credits(3);
+
+    let mut content = "email\n".to_string();
+    for index in 1..=5 {
+        content.push_str(&format!("user{}@my-company.com\n", index));
+    }
+    let zb_file = ZBFile::from_content(content.into_bytes());
+
+    let outcome = zb.bulk_job(BulkJobKind::Validation)
+        .poll_interval(Duration::from_millis(10))
+        .run_split(&zb_file, ZBSplitLimits::default().set_max_rows(2))
+        .unwrap();
+    assert_eq!(outcome.chunks.len(), 3);
+    assert!(matches!(outcome.chunks[0], Ok(BulkJobOutcome::Complete { .. })));
+    assert!(outcome.chunks[1].is_err());
+    assert!(matches!(outcome.chunks[2], Ok(BulkJobOutcome::Complete { .. })));
+    assert_eq!(outcome.file_ids().len(), 2);
+    assert!(!outcome.is_complete());
+    assert_eq!(outcome.content, None);
+
+    let file_ids = outcome.file_ids().into_iter().map(str::to_string).collect::<Vec<_>>();
+    let resumed = zb.bulk_job(BulkJobKind::Validation).resume_split(&zb_file, &file_ids).unwrap();
+    assert!(resumed.is_complete());
+    // the rows of the chunk never submitted come back without results
+    let rows = parse_validation_results(&resumed.content.unwrap()[..]).unwrap();
+    let statuses = rows.iter().map(|row| row.extra[0].1.as_str()).collect::<Vec<_>>();
+    assert_eq!(statuses, [JOIN_MATCHED, JOIN_MATCHED, JOIN_DROPPED, JOIN_DROPPED, JOIN_MATCHED]);
+}
+
+#[test]
+fn test_results_joined_to_input() {
+    let (fake, zb) = start();
+    fake.set_processing_time(Duration::from_millis(100));
+
+    let content = "name,email\nJohn,valid@example.com\nJane,jane@my-company.com\nJane,JANE@my-company.com\n";
+    let zb_file = ZBFile::from_content(content.as_bytes().to_vec())
+        .set_email_address_column(2)
+        .set_remove_duplicate(true);
+
+    let outcome = zb.bulk_job(BulkJobKind::Validation)
+        .poll_interval(Duration::from_millis(50))
+        .run(&zb_file)
+        .unwrap();
+    let joined = zb_file.join_results(&outcome.content().unwrap()[..]).unwrap();
+    let rows = parse_validation_results(&joined[..]).unwrap();
+    let statuses = rows.iter()
+        .map(|row| (row.input[1].1.as_str(), row.extra[0].1.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(statuses, [
+        ("valid@example.com", JOIN_MATCHED),
+        ("jane@my-company.com", JOIN_MATCHED),
+        ("JANE@my-company.com", JOIN_DUPLICATE),
+    ]);
+    assert_eq!(rows[2].status, rows[1].status);
+}
+
+#[test]
+fn test_scoring_and_rejected_file() {
+    let (fake, zb) = start();
+    fake.set_processing_time(Duration::ZERO);
+
+    let zb_file = ZBFile::from_content(b"valid@example.com\ninvalid@example.com\n".to_vec()).set_has_header_row(false);
+    let file_id = zb.ai_scoring_file_submit(&zb_file).unwrap().file_id.unwrap();
+    let ZBBulkResponse::Content(content) = zb.ai_scoring_result_fetch(&file_id).unwrap() else {
+        panic!("no content");
+    };
+    assert_eq!(
+        String::from_utf8_lossy(&content),
+        "\"email\",\"ZeroBounce Score\"\n\"valid@example.com\",\"10\"\n\"invalid@example.com\",\"0\"\n",
+    );
+
+    let malformed = ZBFile::from_content(b"john\njane\nvalid@example.com\n".to_vec()).set_has_header_row(false);
+    let error = zb.bulk_validation_file_submit(&malformed).unwrap_err();
+    assert!(matches!(error, ZBError::Http { status: 400, .. }), "{:?}", error);
+}
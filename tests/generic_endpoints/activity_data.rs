@@ -30,7 +30,7 @@ fn test_activity_data_invalid_json() {
     mock.assert();
 
     let zb_error = activity_data.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -50,7 +50,7 @@ fn test_activity_data_bad_request() {
     mock.assert();
 
     let zb_error = activity_data.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -71,7 +71,7 @@ fn test_activity_data_ok_inactive() {
     mock.assert();
 
     let a_data = activity_data.unwrap();
-    assert_eq!(a_data.found, false);
+    assert!(!a_data.found);
     assert_eq!(a_data.active_in_days, None);
 }
 
@@ -92,6 +92,6 @@ fn test_activity_data_ok_active() {
     mock.assert();
 
     let a_data = activity_data.unwrap();
-    assert_eq!(a_data.found, true);
+    assert!(a_data.found);
     assert_eq!(a_data.active_in_days, Some(180));
 }
@@ -31,7 +31,7 @@ fn test_api_usage_invalid_json() {
     mock.assert();
 
     let zb_error = api_usage.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -51,7 +51,7 @@ fn test_api_usage_bad_request() {
     mock.assert();
 
     let zb_error = api_usage.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -13,7 +13,7 @@ fn test_find_email_v2_empty_first_name() {
     assert!(result.is_err());
     
     let zb_error = result.unwrap_err();
-    let ZBError::ExplicitError(msg) = zb_error else {
+    let ZBError::Validation(msg) = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
     assert!(msg.contains("first_name"));
@@ -31,7 +31,7 @@ fn test_find_email_v2_both_domain_and_company() {
     assert!(result.is_err());
     
     let zb_error = result.unwrap_err();
-    let ZBError::ExplicitError(msg) = zb_error else {
+    let ZBError::Validation(msg) = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
     assert!(msg.contains("exactly one"));
@@ -47,7 +47,7 @@ fn test_find_email_v2_neither_domain_nor_company() {
     assert!(result.is_err());
     
     let zb_error = result.unwrap_err();
-    let ZBError::ExplicitError(msg) = zb_error else {
+    let ZBError::Validation(msg) = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
     assert!(msg.contains("either domain or company_name"));
@@ -64,7 +64,7 @@ fn test_find_email_v2_empty_domain() {
     assert!(result.is_err());
     
     let zb_error = result.unwrap_err();
-    let ZBError::ExplicitError(msg) = zb_error else {
+    let ZBError::Validation(msg) = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
     assert!(msg.contains("domain"));
@@ -81,7 +81,7 @@ fn test_find_email_v2_empty_company_name() {
     assert!(result.is_err());
     
     let zb_error = result.unwrap_err();
-    let ZBError::ExplicitError(msg) = zb_error else {
+    let ZBError::Validation(msg) = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
     assert!(msg.contains("company_name"));
@@ -121,7 +121,7 @@ fn test_find_email_v2_invalid_json() {
     mock.assert();
 
     let zb_error = result.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -149,7 +149,7 @@ fn test_find_email_v2_bad_request() {
     mock.assert();
 
     let zb_error = result.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -1,3 +1,6 @@
+// Covers the deprecated v1 finder methods kept for backward compatibility.
+#![allow(deprecated)]
+
 use zero_bounce::utility::{ENDPOINT_EMAIL_FINDER, CONTENT_TYPE_JSON, ZBError, mock_constants};
 
 use crate::common::{instantiate, invalid_url_zb_instance, endpoint_matcher};
@@ -34,7 +37,7 @@ fn test_find_mail_invalid_json() {
     mock.assert();
 
     let zb_error = find_mail_res.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -56,7 +59,7 @@ fn test_find_bad_request() {
     mock.assert();
 
     let zb_error = find_mail_res.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -137,7 +140,7 @@ fn test_domain_search_invalid_json() {
     mock.assert();
 
     let zb_error = domain_search_res.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -158,7 +161,7 @@ fn test_domain_search_bad_request() {
     mock.assert();
 
     let zb_error = domain_search_res.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -29,7 +29,7 @@ fn test_credits_invalid_json() {
     mock.assert();
 
     let credits_error = credits.unwrap_err();
-    let ZBError::JsonError(_) = credits_error else {
+    let ZBError::Deserialize { .. } = credits_error else {
         panic!("unexpected error: {:#?}", credits_error);
     };
 }
@@ -49,7 +49,7 @@ fn test_credits_bad_request() {
     mock.assert();
 
     let credits_error = credits.unwrap_err();
-    let ZBError::ExplicitError(_) = credits_error else {
+    let ZBError::Http { status: 400, .. } = credits_error else {
         panic!("unexpected error: {:#?}", credits_error);
     };
 }
@@ -1,4 +1,11 @@
+#[cfg(feature = "async")]
+mod async_client;
 mod bulk;
+#[cfg(feature = "cli")]
+mod cli;
+mod client;
 mod common;
+#[cfg(feature = "testing")]
+mod fake_api;
 mod generic_endpoints;
 mod validation;
@@ -0,0 +1,141 @@
+use std::io::Cursor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use serde_json::{json, Value};
+use zero_bounce::{ZBError, ZeroBounce};
+use zero_bounce::transport::{Transport, ZBRequest, ZBRequestBody, ZBResponse};
+use zero_bounce::utility::structures::validation::ZBBatchOptions;
+use zero_bounce::utility::{ZBResult, mock_constants};
+
+use crate::common::MOCK_API_KEY;
+
+// Answers each batch with one validation per email, in reverse order, and
+// fails the batches containing "fail@example.com".
+#[derive(Default)]
+struct BatchEchoTransport {
+    batch_sizes: std::sync::Mutex<Vec<usize>>,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+impl Transport for BatchEchoTransport {
+    fn send(&self, request: ZBRequest) -> ZBResult<ZBResponse> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(20));
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        let ZBRequestBody::Bytes { content, .. } = request.body else {
+            panic!("unexpected body: {:#?}", request.body);
+        };
+        let body = serde_json::from_slice::<Value>(&content).unwrap();
+        let emails = body["email_batch"].as_array().unwrap().iter()
+            .map(|item| item["email_address"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+        self.batch_sizes.lock().unwrap().push(emails.len());
+
+        if emails.iter().any(|email| email == "fail@example.com") {
+            return Ok(ZBResponse::new(500, HeaderMap::new(), Box::new(Cursor::new(Vec::new()))));
+        }
+
+        let template = serde_json::from_str::<Value>(mock_constants::VALIDATION_RESPONSE_VALID).unwrap();
+        let email_batch = emails.iter().rev()
+            .filter(|email| !email.starts_with("bad"))
+            .map(|email| {
+                let mut validation = template.clone();
+                validation["address"] = json!(email);
+                validation
+            })
+            .collect::<Vec<_>>();
+        let errors = emails.iter().rev()
+            .filter(|email| email.starts_with("bad"))
+            .map(|email| json!({"error": "Invalid email", "email_address": email}))
+            .collect::<Vec<_>>();
+
+        let response = json!({"email_batch": email_batch, "errors": errors}).to_string();
+        Ok(ZBResponse::new(200, HeaderMap::new(), Box::new(Cursor::new(response.into_bytes()))))
+    }
+}
+
+fn echo_instance() -> (Arc<BatchEchoTransport>, ZeroBounce) {
+    let transport = Arc::new(BatchEchoTransport::default());
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    (transport, zb_instance)
+}
+
+fn emails(count: usize) -> Vec<(String, String)> {
+    (0..count).map(|i| (format!("user{}@example.com", i), String::new())).collect()
+}
+
+#[test]
+fn test_batch_validate_all_chunks_and_keeps_order() {
+    let (transport, zb_instance) = echo_instance();
+
+    let report = zb_instance.batch_validate_all(emails(450));
+    assert!(report.is_complete());
+    assert!(report.errors.is_empty());
+
+    let addresses = report.email_batch.iter().map(|item| item.address.clone()).collect::<Vec<_>>();
+    let expected = emails(450).into_iter().map(|(email, _)| email).collect::<Vec<_>>();
+    assert_eq!(addresses, expected);
+
+    let mut batch_sizes = transport.batch_sizes.lock().unwrap().clone();
+    batch_sizes.sort();
+    assert_eq!(batch_sizes, vec![50, 200, 200]);
+}
+
+#[test]
+fn test_batch_validate_all_bounded_concurrency() {
+    let (transport, zb_instance) = echo_instance();
+
+    let options = ZBBatchOptions { chunk_size: 10, concurrency: 3 };
+    let report = zb_instance.batch_validate_all_with_options(emails(100), &options);
+    assert!(report.is_complete());
+    assert_eq!(report.email_batch.len(), 100);
+
+    assert_eq!(transport.batch_sizes.lock().unwrap().len(), 10);
+    let max_in_flight = transport.max_in_flight.load(Ordering::SeqCst);
+    assert!((2..=3).contains(&max_in_flight), "{}", max_in_flight);
+}
+
+#[test]
+fn test_batch_validate_all_partial_failure() {
+    let (_, zb_instance) = echo_instance();
+
+    let mut input = emails(25);
+    input[12].0 = "fail@example.com".to_string();
+    input[21].0 = "bad@example.com".to_string();
+
+    let options = ZBBatchOptions { chunk_size: 10, ..Default::default() };
+    let report = zb_instance.batch_validate_all_with_options(input.clone(), &options);
+    assert!(!report.is_complete());
+
+    assert_eq!(report.failed_chunks.len(), 1);
+    let failure = &report.failed_chunks[0];
+    assert_eq!(failure.chunk_index, 1);
+    assert_eq!(failure.emails_and_ip_addresses, input[10..20].to_vec());
+    assert!(matches!(failure.error, ZBError::Http { status: 500, .. }), "{:?}", failure.error);
+
+    assert_eq!(report.email_batch.len(), 14);
+    assert_eq!(report.email_batch[9].address, "user9@example.com");
+    assert_eq!(report.email_batch[10].address, "user20@example.com");
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].email_address, "bad@example.com");
+}
+
+#[test]
+fn test_batch_validate_all_empty_input() {
+    let (transport, zb_instance) = echo_instance();
+
+    let report = zb_instance.batch_validate_all(Vec::new());
+    assert!(report.is_complete());
+    assert!(report.email_batch.is_empty());
+    assert!(transport.batch_sizes.lock().unwrap().is_empty());
+}
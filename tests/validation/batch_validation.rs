@@ -37,7 +37,7 @@ fn test_batch_validation_invalid_json() {
     mock.assert();
 
     let zb_error = validation.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -58,7 +58,7 @@ fn test_batch_validation_bad_request() {
     mock.assert();
 
     let zb_error = validation.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
+use zero_bounce::transport::{InMemoryResponse, InMemoryTransport, ZBMethod, ZBRequestBody};
+use zero_bounce::utility::{ENDPOINT_BATCH_VALIDATE, ENDPOINT_VALIDATE, mock_constants};
+use zero_bounce::{CacheStats, ValidationCache, ZBValidateStatus, ZeroBounce};
+
+use crate::common::MOCK_API_KEY;
+
+fn cached_instance(cache: &ValidationCache) -> (Arc<InMemoryTransport>, ZeroBounce) {
+    let transport = Arc::new(InMemoryTransport::new());
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .transport(transport.clone())
+        .cache(cache.clone())
+        .build()
+        .unwrap();
+    (transport, zb_instance)
+}
+
+fn sent_addresses(body: &ZBRequestBody) -> Vec<String> {
+    let ZBRequestBody::Bytes { content, .. } = body else {
+        panic!("unexpected body: {:#?}", body);
+    };
+    let body = serde_json::from_slice::<Value>(content).unwrap();
+    body["email_batch"].as_array().unwrap().iter()
+        .map(|item| item["email_address"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_validate_email_is_answered_from_cache() {
+    let cache = ValidationCache::new(Duration::from_secs(60), 100);
+    let (transport, zb_instance) = cached_instance(&cache);
+    transport.push_response(
+        ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+    );
+
+    let first = zb_instance.validate_email("valid@example.com").unwrap();
+    let second = zb_instance.validate_email(" Valid@Example.com").unwrap();
+    assert_eq!(transport.request_count(), 1);
+    assert_eq!(first.address, second.address);
+    assert_eq!(second.status_enum(), ZBValidateStatus::Valid);
+    assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1, evictions: 0, entries: 1 });
+
+    // once invalidated, the address is validated again
+    assert!(cache.invalidate("valid@example.com"));
+    transport.push_response(
+        ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+    );
+    zb_instance.validate_email("valid@example.com").unwrap();
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[test]
+fn test_uncached_status_is_validated_again() {
+    let cache = ValidationCache::new(Duration::from_secs(60), 100)
+        .set_status_ttl(ZBValidateStatus::Valid, Duration::ZERO);
+    let (transport, zb_instance) = cached_instance(&cache);
+    for _ in 0..2 {
+        transport.push_response(
+            ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+        );
+    }
+
+    zb_instance.validate_email("valid@example.com").unwrap();
+    zb_instance.validate_email("valid@example.com").unwrap();
+    assert_eq!(transport.request_count(), 2);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_batch_validate_sends_cache_misses_only() {
+    let cache = ValidationCache::new(Duration::from_secs(60), 100);
+    let (transport, zb_instance) = cached_instance(&cache);
+    transport.push_response(
+        ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+    );
+    transport.push_response(
+        ZBMethod::Post, ENDPOINT_BATCH_VALIDATE, InMemoryResponse::json(200, mock_constants::BATCH_VALIDATION_NO_ERROR)
+    );
+    zb_instance.validate_email("valid@example.com").unwrap();
+
+    let emails_and_ips = vec![
+        ("invalid@example.com".to_string(), String::new()),
+        ("valid@example.com".to_string(), String::new()),
+    ];
+    let batch = zb_instance.batch_validate(emails_and_ips).unwrap();
+
+    let requests = transport.take_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(sent_addresses(&requests[1].body), vec!["invalid@example.com"]);
+    // the response of the mock is merged with the cached result, in input order
+    let addresses = batch.email_batch.iter().map(|item| item.address.as_str()).collect::<Vec<_>>();
+    assert_eq!(addresses, vec!["valid@example.com", "valid@example.com"]);
+    assert_eq!(cache.stats().hits, 1);
+}
+
+#[test]
+fn test_batch_validate_all_cached() {
+    let cache = ValidationCache::new(Duration::from_secs(60), 100);
+    let (transport, zb_instance) = cached_instance(&cache);
+    transport.push_response(
+        ZBMethod::Post, ENDPOINT_BATCH_VALIDATE, InMemoryResponse::json(200, mock_constants::BATCH_VALIDATION_NO_ERROR)
+    );
+
+    let emails_and_ips = vec![("valid@example.com".to_string(), String::new())];
+    zb_instance.batch_validate(emails_and_ips.clone()).unwrap();
+    let batch = zb_instance.batch_validate(emails_and_ips).unwrap();
+    assert_eq!(transport.request_count(), 1);
+    assert_eq!(batch.email_batch.len(), 1);
+    assert!(batch.errors.is_empty());
+
+    // results of batch_validate also answer validate_email
+    zb_instance.validate_email("valid@example.com").unwrap();
+    assert_eq!(transport.request_count(), 1);
+}
+
+#[test]
+fn test_ip_address_bypasses_cache() {
+    let cache = ValidationCache::new(Duration::from_secs(60), 100);
+    let (transport, zb_instance) = cached_instance(&cache);
+    for _ in 0..2 {
+        transport.push_response(
+            ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+        );
+    }
+    transport.push_response(
+        ZBMethod::Post, ENDPOINT_BATCH_VALIDATE, InMemoryResponse::json(200, mock_constants::BATCH_VALIDATION_NO_ERROR)
+    );
+
+    // results obtained with an IP address are neither cached nor read from
+    // the cache
+    zb_instance.validate_email_and_ip("valid@example.com", "99.110.204.1").unwrap();
+    assert!(cache.is_empty());
+    zb_instance.validate_email("valid@example.com").unwrap();
+    zb_instance.validate_email_and_ip("valid@example.com", "99.110.204.1").unwrap();
+    assert_eq!(transport.request_count(), 3);
+
+    let emails_and_ips = vec![("valid@example.com".to_string(), "99.110.204.1".to_string())];
+    zb_instance.batch_validate(emails_and_ips).unwrap();
+    let requests = transport.take_requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(sent_addresses(&requests[3].body), vec!["valid@example.com"]);
+    assert_eq!(cache.stats().hits, 0);
+    assert_eq!(cache.len(), 1);
+}
@@ -1,2 +1,6 @@
 mod simple_validation;
 mod batch_validation;
+mod batch_validate_all;
+mod precheck;
+mod cache;
+mod store;
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+use zero_bounce::ZeroBounce;
+use zero_bounce::transport::{InMemoryResponse, InMemoryTransport, ZBMethod, ZBRequestBody};
+use zero_bounce::utility::precheck::PrecheckError;
+use zero_bounce::utility::{ENDPOINT_BATCH_VALIDATE, ENDPOINT_VALIDATE, mock_constants};
+use zero_bounce::{ZBValidateStatus, ZBValidateSubStatus};
+
+use crate::common::MOCK_API_KEY;
+
+fn precheck_instance(precheck: bool) -> (Arc<InMemoryTransport>, ZeroBounce) {
+    let transport = Arc::new(InMemoryTransport::new());
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .transport(transport.clone())
+        .precheck(precheck)
+        .build()
+        .unwrap();
+    (transport, zb_instance)
+}
+
+#[test]
+fn test_validate_email_precheck_short_circuits() {
+    let (transport, zb_instance) = precheck_instance(true);
+
+    let validation = zb_instance.validate_email("not-an-email").unwrap();
+    assert_eq!(transport.request_count(), 0);
+    assert_eq!(validation.address, "not-an-email");
+    assert_eq!(validation.status_enum(), ZBValidateStatus::Invalid);
+    assert_eq!(validation.sub_status_enum(), ZBValidateSubStatus::FailedSyntaxCheck);
+    assert_eq!(validation.precheck_error, Some(PrecheckError::MissingAt));
+}
+
+#[test]
+fn test_validate_email_precheck_passes_valid_syntax() {
+    let (transport, zb_instance) = precheck_instance(true);
+    transport.push_response(
+        ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+    );
+
+    let validation = zb_instance.validate_email("valid@example.com").unwrap();
+    assert_eq!(transport.request_count(), 1);
+    assert!(!validation.is_precheck_result());
+}
+
+#[test]
+fn test_validate_email_without_precheck() {
+    let (transport, zb_instance) = precheck_instance(false);
+    transport.push_response(
+        ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_INVALID)
+    );
+
+    let validation = zb_instance.validate_email("not-an-email").unwrap();
+    assert_eq!(transport.request_count(), 1);
+    assert!(!validation.is_precheck_result());
+}
+
+#[test]
+fn test_batch_validate_precheck_sends_valid_syntax_only() {
+    let (transport, zb_instance) = precheck_instance(true);
+    transport.push_response(
+        ZBMethod::Post, ENDPOINT_BATCH_VALIDATE, InMemoryResponse::json(200, mock_constants::BATCH_VALIDATION_NO_ERROR)
+    );
+
+    let emails_and_ips = vec![
+        ("no-at-sign".to_string(), String::new()),
+        ("valid@example.com".to_string(), "99.110.204.1".to_string()),
+        ("invalid@example.com".to_string(), String::new()),
+        ("double..dot@example.com".to_string(), String::new()),
+    ];
+    let batch = zb_instance.batch_validate(emails_and_ips).unwrap();
+
+    let requests = transport.take_requests();
+    assert_eq!(requests.len(), 1);
+    let ZBRequestBody::Bytes { content, .. } = &requests[0].body else {
+        panic!("unexpected body: {:#?}", requests[0].body);
+    };
+    let body = serde_json::from_slice::<Value>(content).unwrap();
+    let sent = body["email_batch"].as_array().unwrap().iter()
+        .map(|item| item["email_address"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(sent, vec!["valid@example.com", "invalid@example.com"]);
+
+    let addresses = batch.email_batch.iter()
+        .map(|item| (item.address.as_str(), item.is_precheck_result()))
+        .collect::<Vec<_>>();
+    assert_eq!(addresses, vec![
+        ("no-at-sign", true),
+        ("valid@example.com", false),
+        ("double..dot@example.com", true),
+    ]);
+}
+
+#[test]
+fn test_batch_validate_precheck_all_invalid() {
+    let (transport, zb_instance) = precheck_instance(true);
+
+    let batch = zb_instance.batch_validate(vec![("@example.com".to_string(), String::new())]).unwrap();
+    assert_eq!(transport.request_count(), 0);
+    assert_eq!(batch.email_batch.len(), 1);
+    assert_eq!(batch.email_batch[0].precheck_error, Some(PrecheckError::EmptyLocalPart));
+}
@@ -29,7 +29,7 @@ fn test_simple_validation_invalid_json() {
     mock.assert();
 
     let zb_error = validation.unwrap_err();
-    let ZBError::JsonError(_) = zb_error else {
+    let ZBError::Deserialize { .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
@@ -49,11 +49,33 @@ fn test_simple_validation_bad_request() {
     mock.assert();
 
     let zb_error = validation.unwrap_err();
-    let ZBError::ExplicitError(_) = zb_error else {
+    let ZBError::Http { status: 400, .. } = zb_error else {
         panic!("unexpected error: {:#?}", zb_error);
     };
 }
 
+#[test]
+fn test_simple_validation_invalid_api_key() {
+    let (mut mock_server, zb_instance) = instantiate();
+
+    // the API reports an invalid key with a 200 status
+    let mock = mock_server.mock("GET", endpoint_matcher(ENDPOINT_VALIDATE))
+        .with_status(200)
+        .with_header("content-type", CONTENT_TYPE_JSON)
+        .with_body(r#"{"error": "Invalid API key or your account ran out of credits"}"#)
+        .create();
+
+    let validation = zb_instance.validate_email(EMAIL);
+    mock.assert();
+
+    let zb_error = validation.unwrap_err();
+    let ZBError::InvalidApiKey = zb_error else {
+        panic!("unexpected error: {:#?}", zb_error);
+    };
+    assert!(zb_error.is_auth());
+    assert!(!zb_error.is_retryable());
+}
+
 #[test]
 fn test_simple_validation_ok() {
     let (mut mock_server, zb_instance) = instantiate();
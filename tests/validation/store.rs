@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use zero_bounce::store::ValidationStore;
+use zero_bounce::transport::{InMemoryResponse, InMemoryTransport, ZBMethod};
+use zero_bounce::utility::structures::validation::ZBValidation;
+use zero_bounce::utility::{ENDPOINT_BATCH_VALIDATE, ENDPOINT_VALIDATE, ZBError, ZBResult, mock_constants};
+use zero_bounce::{ValidationCache, ZeroBounce};
+
+use crate::common::MOCK_API_KEY;
+
+// Store keeping results in a map, failing on demand
+#[derive(Default)]
+struct MapStore {
+    validations: Mutex<HashMap<String, ZBValidation>>,
+    failing: bool,
+}
+
+impl ValidationStore for MapStore {
+    fn get(&self, address: &str) -> ZBResult<Option<ZBValidation>> {
+        if self.failing {
+            return Err(ZBError::StoreError("database is locked".into()));
+        }
+        Ok(self.validations.lock().unwrap().get(address).cloned())
+    }
+
+    fn put(&self, validations: &[ZBValidation]) -> ZBResult<()> {
+        let mut stored = self.validations.lock().unwrap();
+        for validation in validations {
+            stored.insert(validation.address.clone(), validation.clone());
+        }
+        Ok(())
+    }
+}
+
+fn stored_instance(store: Arc<dyn ValidationStore>) -> (Arc<InMemoryTransport>, ZeroBounce) {
+    let transport = Arc::new(InMemoryTransport::new());
+    let zb_instance = ZeroBounce::builder()
+        .api_key(MOCK_API_KEY)
+        .transport(transport.clone())
+        .store(store)
+        .build()
+        .unwrap();
+    (transport, zb_instance)
+}
+
+#[test]
+fn test_validate_email_reads_through_store() {
+    let store = Arc::new(MapStore::default());
+    let (transport, zb_instance) = stored_instance(store.clone());
+    transport.push_response(
+        ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+    );
+
+    zb_instance.validate_email("valid@example.com").unwrap();
+    assert!(store.validations.lock().unwrap().contains_key("valid@example.com"));
+
+    // another client sharing the store does not send the request again
+    let (other_transport, other_instance) = stored_instance(store);
+    let validation = other_instance.validate_email("valid@example.com").unwrap();
+    assert_eq!(validation.status, "valid");
+    assert_eq!(transport.request_count() + other_transport.request_count(), 1);
+}
+
+#[test]
+fn test_batch_validate_sends_store_misses_only() {
+    let store = Arc::new(MapStore::default());
+    let (transport, zb_instance) = stored_instance(store.clone());
+    transport.push_response(
+        ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+    );
+    transport.push_response(
+        ZBMethod::Post, ENDPOINT_BATCH_VALIDATE, InMemoryResponse::json(200, mock_constants::BATCH_VALIDATION_NO_ERROR)
+    );
+    zb_instance.validate_email("valid@example.com").unwrap();
+
+    let emails_and_ips = vec![
+        ("valid@example.com".to_string(), String::new()),
+        ("invalid@example.com".to_string(), String::new()),
+    ];
+    let batch = zb_instance.batch_validate(emails_and_ips.clone()).unwrap();
+    assert_eq!(transport.request_count(), 2);
+    assert_eq!(batch.email_batch.len(), 2);
+
+    // the cache is looked up before the store, and filled by its results
+    let cache = ValidationCache::new(std::time::Duration::from_secs(60), 10);
+    let zb_instance = zb_instance.set_cache(Some(cache.clone()));
+    zb_instance.validate_email("valid@example.com").unwrap();
+    zb_instance.validate_email("valid@example.com").unwrap();
+    assert_eq!(cache.stats().hits, 1);
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[test]
+fn test_store_failure_fails_the_call() {
+    let store = Arc::new(MapStore { failing: true, ..Default::default() });
+    let (transport, zb_instance) = stored_instance(store);
+
+    let error = zb_instance.validate_email("valid@example.com").unwrap_err();
+    assert!(matches!(error, ZBError::StoreError(_)), "{:?}", error);
+    assert_eq!(error.to_string(), "validation store failed: database is locked");
+    assert_eq!(transport.request_count(), 0);
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::Duration;
+
+    use zero_bounce::store::SqliteStore;
+
+    use super::*;
+
+    struct TempDatabase(PathBuf);
+
+    impl TempDatabase {
+        fn new(name: &str) -> TempDatabase {
+            let path = std::env::temp_dir().join(format!("zb-{}-{}.sqlite", name, std::process::id()));
+            let database = TempDatabase(path);
+            database.remove();
+            database
+        }
+
+        fn remove(&self) {
+            for suffix in ["", "-wal", "-shm"] {
+                let mut path = self.0.clone().into_os_string();
+                path.push(suffix);
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    impl Drop for TempDatabase {
+        fn drop(&mut self) {
+            self.remove();
+        }
+    }
+
+    // The canned responses were processed long ago
+    fn open_store(database: &TempDatabase) -> SqliteStore {
+        SqliteStore::open(&database.0).unwrap().set_max_age(Duration::MAX)
+    }
+
+    #[test]
+    fn test_sqlite_store_is_shared_between_connections() {
+        let database = TempDatabase::new("shared");
+        let (transport, zb_instance) = stored_instance(Arc::new(open_store(&database)));
+        transport.push_response(
+            ZBMethod::Get, ENDPOINT_VALIDATE, InMemoryResponse::json(200, mock_constants::VALIDATION_RESPONSE_VALID)
+        );
+        zb_instance.validate_email("valid@example.com").unwrap();
+
+        // a separate connection, as another process would open
+        let (other_transport, other_instance) = stored_instance(Arc::new(open_store(&database)));
+        let validation = other_instance.validate_email("Valid@Example.com").unwrap();
+        assert_eq!(validation.address, "valid@example.com");
+        assert_eq!(other_transport.request_count(), 0);
+    }
+
+    #[test]
+    fn test_sqlite_store_concurrent_writers() {
+        let database = TempDatabase::new("concurrent");
+        let template = serde_json::from_str::<ZBValidation>(mock_constants::VALIDATION_RESPONSE_VALID).unwrap();
+
+        thread::scope(|scope| {
+            for writer in 0..4 {
+                let store = SqliteStore::open(&database.0).unwrap();
+                let template = template.clone();
+                scope.spawn(move || {
+                    for batch in 0..10 {
+                        let validations = (0..20).map(|index| {
+                            let mut validation = template.clone();
+                            validation.address = format!("user{}-{}-{}@example.com", writer, batch, index);
+                            validation
+                        }).collect::<Vec<_>>();
+                        store.insert(&validations).unwrap();
+                        assert!(store.get_latest(&validations[0].address).unwrap().is_some());
+                    }
+                });
+            }
+        });
+
+        let store = SqliteStore::open(&database.0).unwrap();
+        assert_eq!(store.len().unwrap(), 4 * 10 * 20);
+    }
+}